@@ -0,0 +1,2 @@
+-- 问题列表游标分页使用的索引，与列表排序`sort` desc,`rank`,`create_time` desc,`id`一致
+create index `rc_qa_question_list_IDX` on `rc_qa_question` (`sort` desc, `rank`, `create_time` desc, `id`);
@@ -0,0 +1,26 @@
+-- 问答审核：问题和答案增加审核状态，已有数据视为审核通过
+alter table `rc_qa_question`
+    add column `review_status` tinyint NOT NULL DEFAULT '1' COMMENT '审核状态(0/1/2) (0-待审核，1-审核通过，2-审核拒绝)',
+    add column `reject_reason` varchar(500) DEFAULT NULL COMMENT '审核拒绝原因',
+    add column `reviewer_id` varchar(100) DEFAULT NULL COMMENT '审核人',
+    add column `review_time` datetime DEFAULT NULL COMMENT '审核时间';
+
+alter table `rc_qa_answer`
+    add column `review_status` tinyint NOT NULL DEFAULT '1' COMMENT '审核状态(0/1/2) (0-待审核，1-审核通过，2-审核拒绝)',
+    add column `reject_reason` varchar(500) DEFAULT NULL COMMENT '审核拒绝原因',
+    add column `reviewer_id` varchar(100) DEFAULT NULL COMMENT '审核人',
+    add column `review_time` datetime DEFAULT NULL COMMENT '审核时间';
+
+CREATE TABLE `rc_qa_review_log` (
+    `id` bigint NOT NULL AUTO_INCREMENT COMMENT '主键',
+    `target_type` varchar(20) NOT NULL COMMENT '审核对象类型(question/answer)',
+    `target_id` bigint NOT NULL COMMENT '问题表或答案表的id',
+    `question_code` varchar(100) NOT NULL COMMENT '问题编号',
+    `review_status` tinyint NOT NULL COMMENT '审核结果(1-审核通过，2-审核拒绝)',
+    `reject_reason` varchar(500) DEFAULT NULL COMMENT '审核拒绝原因',
+    `reviewer_id` varchar(100) NOT NULL COMMENT '审核人',
+    `reviewer_name` varchar(100) DEFAULT NULL COMMENT '审核人姓名',
+    `create_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '审核时间',
+    PRIMARY KEY (`id`),
+    KEY `rc_qa_review_log_question_code_IDX` (`question_code`) USING BTREE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci COMMENT='产品问答审核记录表';
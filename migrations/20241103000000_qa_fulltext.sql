@@ -0,0 +1,3 @@
+-- 问题与回答内容的全文索引，使用ngram分词以支持中文检索（默认ngram_token_size=2）
+alter table `rc_qa_question` add fulltext index `rc_qa_question_content_FT` (`question_content`) with parser ngram;
+alter table `rc_qa_answer` add fulltext index `rc_qa_answer_content_FT` (`answer_content`) with parser ngram;
@@ -0,0 +1,12 @@
+-- 问题修订记录：每次新建、修改、置顶、取消置顶、失效、恢复时保存问题及其回答的快照
+CREATE TABLE `rc_qa_question_revision` (
+    `id` bigint NOT NULL AUTO_INCREMENT COMMENT '主键',
+    `question_code` varchar(100) NOT NULL COMMENT '问题编号',
+    `revision` int NOT NULL COMMENT '修订版本号，同一问题从1开始递增',
+    `action` varchar(20) NOT NULL COMMENT '触发动作(create/update/top/cancelTop/disable/restore)',
+    `snapshot` text NOT NULL COMMENT '变更后问题及其回答的快照(JSON)',
+    `operator_id` varchar(100) DEFAULT NULL COMMENT '操作人',
+    `create_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `rc_qa_question_revision_UN` (`question_code`,`revision`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci COMMENT='产品问答修订记录表';
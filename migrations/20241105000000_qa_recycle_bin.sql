@@ -0,0 +1,10 @@
+-- 问答回收站：记录软删除批次，问题与同一次删除中失效的回答使用相同的批次，恢复时只恢复该批次的回答
+-- 本迁移之前已删除的问题没有批次，恢复时不会恢复任何回答
+alter table `rc_qa_question`
+    add column `disable_batch` varchar(100) DEFAULT NULL COMMENT '软删除批次',
+    add column `disable_time` datetime DEFAULT NULL COMMENT '软删除时间';
+
+alter table `rc_qa_answer`
+    add column `disable_batch` varchar(100) DEFAULT NULL COMMENT '软删除批次';
+
+create index `rc_qa_answer_disable_batch_IDX` on `rc_qa_answer` (`disable_batch`);
@@ -0,0 +1,2 @@
+-- 置顶时按商品锁定有效问题计算下一个sort值，按商品和sort建索引使锁定范围限于同一商品
+create index `rc_qa_question_product_sort_IDX` on `rc_qa_question` (`product_code`, `status`, `sort`);
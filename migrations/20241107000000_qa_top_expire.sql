@@ -0,0 +1,5 @@
+-- 限时置顶：置顶到期时间，为空表示不过期；到期后列表排序忽略该置顶，后台任务再将sort重置为0
+alter table `rc_qa_question`
+    add column `top_expire_time` datetime DEFAULT NULL COMMENT '置顶到期时间';
+
+create index `rc_qa_question_top_expire_time_IDX` on `rc_qa_question` (`top_expire_time`);
@@ -0,0 +1,15 @@
+-- 回答有用/没用投票：每个用户对每个回答只保留一票，回答表保存汇总计数
+alter table `rc_qa_answer`
+    add column `helpful_count` int NOT NULL DEFAULT '0' COMMENT '有用票数',
+    add column `unhelpful_count` int NOT NULL DEFAULT '0' COMMENT '没用票数';
+
+CREATE TABLE `rc_qa_answer_vote` (
+    `id` bigint NOT NULL AUTO_INCREMENT COMMENT '主键',
+    `answer_id` bigint NOT NULL COMMENT '回答表的id',
+    `user_id` varchar(100) NOT NULL COMMENT '投票用户',
+    `vote` tinyint NOT NULL COMMENT '投票(1-有用，-1-没用)',
+    `create_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    `update_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '更新时间',
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `rc_qa_answer_vote_UN` (`answer_id`,`user_id`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci COMMENT='产品问答回答投票表';
@@ -0,0 +1,9 @@
+-- 问题合并后的重定向：前台以被合并的问题编号查询时返回保留的问题
+CREATE TABLE `rc_qa_question_redirect` (
+    `source_question_code` varchar(100) NOT NULL COMMENT '被合并（已失效）的问题编号',
+    `target_question_code` varchar(100) NOT NULL COMMENT '保留的问题编号',
+    `operator_id` varchar(100) DEFAULT NULL COMMENT '操作人',
+    `create_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '合并时间',
+    PRIMARY KEY (`source_question_code`),
+    KEY `rc_qa_question_redirect_target_IDX` (`target_question_code`) USING BTREE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci COMMENT='产品问答问题合并重定向表';
@@ -0,0 +1,15 @@
+-- 问答标签：标签与问题多对多关联
+CREATE TABLE `rc_qa_tag` (
+    `id` bigint NOT NULL AUTO_INCREMENT,
+    `tag_name` varchar(50) NOT NULL COMMENT '标签名称，如shipping、sizing、warranty',
+    `create_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `rc_qa_tag_tag_name_IDX` (`tag_name`) USING BTREE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci COMMENT='产品问答标签表';
+
+CREATE TABLE `rc_qa_question_tag` (
+    `question_code` varchar(100) NOT NULL COMMENT '问题编号',
+    `tag_id` bigint NOT NULL COMMENT '标签id',
+    PRIMARY KEY (`question_code`,`tag_id`),
+    KEY `rc_qa_question_tag_tag_id_IDX` (`tag_id`) USING BTREE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci COMMENT='产品问答问题标签关联表';
@@ -0,0 +1,18 @@
+-- 问答内容的多语言翻译：rc_qa_question/rc_qa_answer保存默认语言的内容，其它语言的内容保存在翻译表中
+CREATE TABLE `rc_qa_question_translation` (
+    `question_code` varchar(100) NOT NULL COMMENT '问题编号',
+    `locale` varchar(10) NOT NULL COMMENT '语言，如zh-CN、zh-HK、en',
+    `question_content` varchar(2000) NOT NULL COMMENT '翻译后的问题内容',
+    `update_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '更新时间',
+    PRIMARY KEY (`question_code`,`locale`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci COMMENT='产品问答问题翻译表';
+
+CREATE TABLE `rc_qa_answer_translation` (
+    `answer_id` bigint NOT NULL COMMENT '回答id',
+    `question_code` varchar(100) NOT NULL COMMENT '回答所属的问题编号',
+    `locale` varchar(10) NOT NULL COMMENT '语言，如zh-CN、zh-HK、en',
+    `answer_content` varchar(2000) NOT NULL COMMENT '翻译后的回答内容',
+    `update_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '更新时间',
+    PRIMARY KEY (`answer_id`,`locale`),
+    KEY `rc_qa_answer_translation_question_code_IDX` (`question_code`,`locale`) USING BTREE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci COMMENT='产品问答回答翻译表';
@@ -0,0 +1,3 @@
+-- 回答内容支持Markdown：answer_content保存Markdown原文，answer_html保存写入时渲染并清理后的HTML
+-- 之前的回答answer_html为空，读取时按原文渲染
+ALTER TABLE `rc_qa_answer` ADD COLUMN `answer_html` text DEFAULT NULL COMMENT 'Markdown渲染并清理后的HTML' AFTER `answer_content`;
@@ -0,0 +1,21 @@
+-- 回答下的追问回复：回复可以继续回复，形成树状结构，层级受QA_REPLY_MAX_DEPTH限制
+CREATE TABLE `rc_qa_reply` (
+    `id` bigint NOT NULL AUTO_INCREMENT,
+    `answer_id` bigint NOT NULL COMMENT '所属回答id',
+    `parent_id` bigint DEFAULT NULL COMMENT '上级回复id，直接回复回答时为空',
+    `question_code` varchar(100) NOT NULL COMMENT '所属问题编号',
+    `depth` int NOT NULL DEFAULT '1' COMMENT '回复层级，直接回复回答为1',
+    `reply_content` varchar(1000) NOT NULL COMMENT '回复内容',
+    `create_user_id` varchar(100) DEFAULT NULL COMMENT '回复人',
+    `creator_name` varchar(100) DEFAULT NULL COMMENT '回复人名称',
+    `status` tinyint NOT NULL DEFAULT '1' COMMENT '状态(0/1) (0-失效，1-有效)',
+    `review_status` tinyint NOT NULL DEFAULT '0' COMMENT '审核状态(0/1/2) (0-待审核，1-审核通过，2-审核拒绝)',
+    `reject_reason` varchar(500) DEFAULT NULL COMMENT '审核拒绝原因',
+    `reviewer_id` varchar(100) DEFAULT NULL COMMENT '审核人',
+    `review_time` datetime DEFAULT NULL COMMENT '审核时间',
+    `create_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    `update_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '更新时间',
+    PRIMARY KEY (`id`),
+    KEY `rc_qa_reply_answer_id_IDX` (`answer_id`,`status`,`review_status`) USING BTREE,
+    KEY `rc_qa_reply_review_IDX` (`review_status`,`create_time`) USING BTREE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci COMMENT='产品问答回复表';
@@ -0,0 +1,12 @@
+-- 问题浏览量：按问题和日期汇总，前台浏览先在内存中累加，再定期批量写入
+CREATE TABLE `rc_qa_question_view_daily` (
+    `id` bigint NOT NULL AUTO_INCREMENT COMMENT '主键',
+    `question_code` varchar(100) NOT NULL COMMENT '问题编号',
+    `view_date` date NOT NULL COMMENT '浏览日期',
+    `view_count` bigint NOT NULL DEFAULT '0' COMMENT '浏览次数',
+    `create_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    `update_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '更新时间',
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `rc_qa_question_view_daily_UN` (`question_code`,`view_date`),
+    KEY `rc_qa_question_view_daily_view_date_IDX` (`view_date`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci COMMENT='产品问答问题每日浏览量表';
@@ -0,0 +1,30 @@
+-- 问题订阅：用户订阅问题后，问题新增回答或被更新时发送通知
+CREATE TABLE `rc_qa_subscription` (
+    `id` bigint NOT NULL AUTO_INCREMENT COMMENT '主键',
+    `question_code` varchar(100) NOT NULL COMMENT '问题编号',
+    `subscriber_id` varchar(100) NOT NULL COMMENT '订阅用户',
+    `status` tinyint NOT NULL DEFAULT '1' COMMENT '状态(0-已取消，1-有效)',
+    `create_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    `update_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '更新时间',
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `rc_qa_subscription_UN` (`question_code`,`subscriber_id`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci COMMENT='产品问答问题订阅表';
+
+-- 待发送及已发送的通知，每个订阅每次事件一条，发送失败后按退避时间重试
+CREATE TABLE `rc_qa_notification` (
+    `id` bigint NOT NULL AUTO_INCREMENT COMMENT '主键',
+    `subscription_id` bigint NOT NULL COMMENT '订阅表的id',
+    `question_code` varchar(100) NOT NULL COMMENT '问题编号',
+    `subscriber_id` varchar(100) NOT NULL COMMENT '订阅用户',
+    `event_type` varchar(50) NOT NULL COMMENT '事件类型(answerAdded/questionUpdated)',
+    `payload` text NOT NULL COMMENT '通知内容(JSON)',
+    `status` tinyint NOT NULL DEFAULT '0' COMMENT '发送状态(0-待发送，1-已发送，2-发送失败)',
+    `attempt_count` int NOT NULL DEFAULT '0' COMMENT '已尝试发送的次数',
+    `next_retry_time` datetime NOT NULL COMMENT '下次发送时间',
+    `last_error` varchar(1000) DEFAULT NULL COMMENT '最近一次发送失败的原因',
+    `create_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+    `update_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '更新时间',
+    PRIMARY KEY (`id`),
+    KEY `rc_qa_notification_status_IDX` (`status`,`next_retry_time`),
+    KEY `rc_qa_notification_question_code_IDX` (`question_code`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci COMMENT='产品问答通知发送表';
@@ -0,0 +1,2 @@
+-- 追问回复按一级回复分页后，按parent_id逐层查询下级回复
+create index `rc_qa_reply_parent_id_IDX` on `rc_qa_reply` (`parent_id`);
@@ -0,0 +1,10 @@
+-- 回答时效统计使用问题第一次有回答的时间，写入后不再随回答的修改、删除变化
+alter table `rc_qa_question`
+    add column `first_answer_time` datetime DEFAULT NULL COMMENT '第一次有回答的时间';
+
+-- 之前的回答在更新问题时会被重新生成，按现有回答中最早的创建时间补齐，只是近似值
+update `rc_qa_question` q
+    join (select question_code,min(create_time) as first_answer_time from rc_qa_answer group by question_code) a
+    on a.question_code = q.question_code
+    set q.first_answer_time = a.first_answer_time
+    where q.first_answer_time is null;
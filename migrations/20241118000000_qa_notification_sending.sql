@@ -0,0 +1,3 @@
+-- 通知先领取再发送：领取后状态为发送中，next_retry_time为领取的到期时间，到期仍未记录结果时可以被重新领取
+alter table `rc_qa_notification`
+    modify column `status` tinyint NOT NULL DEFAULT '0' COMMENT '发送状态(0-待发送，1-已发送，2-发送失败，3-发送中)';
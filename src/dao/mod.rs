@@ -1,2 +1,3 @@
 pub mod sku_dao;
-pub mod qa_dao;
\ No newline at end of file
+pub mod qa_dao;
+pub mod subscription_dao;
\ No newline at end of file
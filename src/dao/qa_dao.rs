@@ -1,10 +1,40 @@
 use crate::model::request::operation::find_question_list_for_trad::FindQuestionListForTrad as RequestFindQuestionListForTrad;
+use crate::model::request::operation::find_question_list_for_trad::MatchMode;
 use crate::model::db::qa::Question;
+use crate::model::db::qa::QuestionStatus;
+use crate::model::db::qa::ReviewStatus;
+use crate::model::db::qa::ReviewItem;
+use crate::model::db::qa::ReviewLog;
+use crate::model::request::operation::find_review_queue::FindReviewQueue as RequestFindReviewQueue;
+use crate::model::request::operation::review_qa::ReviewTargetType;
+use crate::model::request::operation::find_recycle_bin::FindRecycleBin as RequestFindRecycleBin;
+use crate::utils::datetime::now_local;
+use uuid::Uuid;
 use crate::model::db::qa::Answer;
+use crate::model::db::qa::AnswerChanges;
 use crate::model::db::qa::Page;
+use crate::model::db::qa::CursorPage;
+use crate::model::db::qa::QuestionCursor;
+use crate::model::db::qa::SearchHit;
+use crate::model::db::qa::QuestionRevision;
+use crate::model::db::qa::QuestionSnapshot;
+use crate::model::db::qa::RevisionAction;
+use crate::model::db::qa::push_effective_sort;
+use crate::model::db::qa::Vote;
+use crate::model::db::qa::QuestionTranslation;
+use crate::model::db::qa::AnswerTranslation;
+use crate::model::db::qa::Reply;
+use crate::model::db::qa::AnswerLatencyStat;
+use crate::model::request::operation::qa_report::{FindUnansweredQuestion as RequestFindUnansweredQuestion, FindAnswerLatency as RequestFindAnswerLatency};
+use crate::utils::view_counter::QuestionView;
+use crate::utils::locale::Locale;
 use crate::utils::error::BusinessError;
 use sqlx::MySqlPool;
 use sqlx::QueryBuilder;
+use std::collections::HashMap;
+
+/// 建有ngram全文索引的列，见migrations/20241103000000_qa_fulltext.sql
+const FULLTEXT_COLUMNS: [&str; 2] = ["question_content", "answer_content"];
 pub struct QuestionDao;
 impl QuestionDao{
 
@@ -25,9 +55,10 @@ impl QuestionDao{
             `create_time`,
             `update_time`,
             `sort`,
-            `rank`)
+            `rank`,
+            `review_status`)
             values (
-            ?,?,?,?,?,?,?,?,?,?
+            ?,?,?,?,?,?,?,?,?,?,?
             )",
             question.sku_code.clone().unwrap_or_else(|| "".to_string()),
             question.product_code,
@@ -38,7 +69,8 @@ impl QuestionDao{
             question.create_time,
             question.update_time,
             question.sort,
-            question.rank
+            question.rank,
+            question.review_status
         );
         
         let result = query.execute(&mut **transaction)
@@ -57,12 +89,14 @@ impl QuestionDao{
         let query = sqlx::query!(
        "insert into rc_qa_answer (
         `answer_content`,
+        `answer_html`,
         `create_user_id`,
         `creator_name`,
         `question_code`,
         `status`,
         `create_time`,
-        `update_time`
+        `update_time`,
+        `review_status`
          )
          values (
          ?,
@@ -71,34 +105,107 @@ impl QuestionDao{
          ?,
          ?,
          ?,
+         ?,
+         ?,
          ?
          )",
          answer.answer_content,
+         answer.answer_html,
          answer.create_user_id,
          answer.creator_name,
          answer.question_code,
          answer.status,
          answer.create_time,
-         answer.update_time
+         answer.update_time,
+         answer.review_status
         );
         
         query.execute(&mut **transaction)
         .await?;
+        //只记录问题第一次有回答的时间，用于回答时效统计
+        sqlx::query("update rc_qa_question set `first_answer_time` = ? where `question_code` = ? and `first_answer_time` is null")
+            .bind(answer.create_time)
+            .bind(&answer.question_code)
+            .execute(&mut **transaction)
+            .await?;
 
         Ok(())
     }
 
-    pub async fn delete_answer(
+    /// 在原记录上更新回答的内容、状态和审核信息
+    pub async fn update_answer(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        answer: &Answer,
+    )->Result<(), BusinessError>{
+        sqlx::query("update rc_qa_answer set `answer_content` = ?,`answer_html` = ?,`status` = ?,`review_status` = ?,`reject_reason` = ?,`reviewer_id` = ?,`review_time` = ?,`update_time` = ? where `id` = ? and `question_code` = ?")
+            .bind(&answer.answer_content)
+            .bind(&answer.answer_html)
+            .bind(answer.status)
+            .bind(answer.review_status)
+            .bind(&answer.reject_reason)
+            .bind(&answer.reviewer_id)
+            .bind(answer.review_time)
+            .bind(answer.update_time)
+            .bind(answer.id)
+            .bind(&answer.question_code)
+            .execute(&mut **transaction)
+            .await?;
+        Ok(())
+    }
+
+    /// 依据id删除问题下的回答及其投票，回答下的追问回复设为失效
+    pub async fn delete_answer_by_ids(
         transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
         question_code: &String,
+        answer_ids: &[i64],
     )->Result<(), BusinessError>{
+        if answer_ids.is_empty(){
+            return Ok(());
+        }
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("delete from rc_qa_answer where `question_code` = ");
+        builder.push_bind(question_code);
+        builder.push(" and `id` in (");
+        let mut separated = builder.separated(",");
+        for answer_id in answer_ids{
+            separated.push_bind(answer_id);
+        }
+        separated.push_unseparated(")");
+        builder.build().execute(&mut **transaction).await?;
 
-        // 执行插入操作，并忽略返回的结果
-        let query = sqlx::query!("delete from rc_qa_answer where `question_code` = ?",question_code);
-        
-        query.execute(&mut **transaction)
-        .await?;
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("delete from rc_qa_answer_vote where `answer_id` in (");
+        let mut separated = builder.separated(",");
+        for answer_id in answer_ids{
+            separated.push_bind(answer_id);
+        }
+        separated.push_unseparated(")");
+        builder.build().execute(&mut **transaction).await?;
 
+        //被删除回答下的追问回复不再展示
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("update rc_qa_reply set `status`='0' where `answer_id` in (");
+        let mut separated = builder.separated(",");
+        for answer_id in answer_ids{
+            separated.push_bind(answer_id);
+        }
+        separated.push_unseparated(") and `status`='1'");
+        builder.build().execute(&mut **transaction).await?;
+        Ok(())
+    }
+
+    /// 保存问题回答列表的变更：新增回答、在原记录上更新有变化的回答、删除被移除的回答
+    pub async fn save_answer_changes(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        question_code: &String,
+        changes: &AnswerChanges,
+    )->Result<(), BusinessError>{
+        QuestionDao::delete_answer_by_ids(transaction, question_code, &changes.deleted_ids).await?;
+        for answer in &changes.updated{
+            QuestionDao::update_answer(transaction, answer).await?;
+        }
+        for answer in &changes.inserted{
+            QuestionDao::insert_answer(transaction, answer).await?;
+        }
+        //只有被删除或内容有变化的回答的翻译不再有效
+        QuestionDao::delete_answer_translation_by_answer_ids(transaction, &changes.stale_translation_ids()).await?;
         Ok(())
     }
 
@@ -136,6 +243,20 @@ impl QuestionDao{
         Ok(question)
     }
 
+    /// 依据question_code查询有效且审核通过的问题记录（前台展示用）
+    pub async fn find_approved_question_by_question_code(
+        pool: &MySqlPool,
+        question_code: &String,
+    )->Result<Option<Question>, BusinessError>{
+        let question = sqlx::query_as::<_,Question>(
+            "select * from rc_qa_question where `question_code` = ? and status='1' and review_status = ?",
+        ).bind(question_code)
+        .bind::<i8>(ReviewStatus::Approved.into())
+        .fetch_optional(pool)
+        .await?;
+        Ok(question)
+    }
+
     /// 依据question_code删除问题记录
     pub async fn delete_question_by_question_code(
         transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
@@ -181,11 +302,13 @@ impl QuestionDao{
     pub async fn disabled_question_by_question_code(
         transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
         question_code: &String,
+        disable_batch: &String,
+        disable_time: chrono::NaiveDateTime,
     )->Result<(), BusinessError>{
 
         // 执行插入操作，并忽略返回的结果
-        let query = sqlx::query!("update rc_qa_question set `status`='0' 
-        where `question_code` = ? and `status`='1'",question_code);
+        let query = sqlx::query!("update rc_qa_question set `status`='0',`disable_batch` = ?,`disable_time` = ? 
+        where `question_code` = ? and `status`='1'",disable_batch,disable_time,question_code);
         
         query.execute(&mut **transaction)
         .await?;
@@ -197,10 +320,11 @@ impl QuestionDao{
     pub async fn disable_answer_by_question_code(
         transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
         question_code: &String,
+        disable_batch: &String,
     )->Result<(), BusinessError>{
     
         // 执行插入操作，并忽略返回的结果
-        let query = sqlx::query!("update rc_qa_answer set `status`='0' where `question_code` = ? and `status`='1'",question_code);
+        let query = sqlx::query!("update rc_qa_answer set `status`='0',`disable_batch` = ? where `question_code` = ? and `status`='1'",disable_batch,question_code);
         
         query.execute(&mut **transaction)
         .await?;
@@ -208,33 +332,128 @@ impl QuestionDao{
         Ok(())
     }
 
+    /// 软删除问题及其有效的回答，两者记录同一个软删除批次
     pub async fn disabled_question_and_answer_by_question_code(
         transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
         question_code: &String,
     )->Result<(), BusinessError>{
-        let _ = QuestionDao::disable_answer_by_question_code(transaction, question_code).await?;
-        let _ = QuestionDao::disabled_question_by_question_code(transaction, question_code).await?;
+        let disable_batch = Uuid::new_v4().to_string();
+        let _ = QuestionDao::disable_answer_by_question_code(transaction, question_code, &disable_batch).await?;
+        let _ = QuestionDao::disabled_question_by_question_code(transaction, question_code, &disable_batch, now_local()).await?;
         Ok(())
     }
 
+    /// 从回收站恢复问题，以及与问题同一批次软删除的回答，返回恢复前的问题记录
+    /// 在删除问题之前单独失效的回答不属于该批次，保持失效
+    pub async fn restore_disabled_question(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        question_code: &String,
+        update_time: chrono::NaiveDateTime,
+    )->Result<Question, BusinessError>{
+        let question = sqlx::query_as::<_,Question>(
+            "select * from rc_qa_question where `question_code` = ? and status='0' for update",
+        ).bind(question_code)
+        .fetch_optional(&mut **transaction)
+        .await?;
+        let question = match question {
+            Some(question) => question,
+            None => {
+                let mut parameters= HashMap::new();
+                parameters.insert("question_code".to_string(), question_code.clone());
+                return Err(BusinessError::QuestionNotFound(
+                    (Some("回收站中找不到对应的问题记录".to_string()),Some(parameters))
+                ));
+            }
+        };
+        if let Some(disable_batch) = &question.disable_batch {
+            let query = sqlx::query!("update rc_qa_answer set `status`='1',`disable_batch` = null,`update_time` = ? where `question_code` = ? and `status`='0' and `disable_batch` = ?",
+                update_time,question_code,disable_batch);
+            query.execute(&mut **transaction)
+            .await?;
+        }
+        let query = sqlx::query!("update rc_qa_question set `status`='1',`disable_batch` = null,`disable_time` = null,`update_time` = ? where `question_code` = ?",
+            update_time,question_code);
+        query.execute(&mut **transaction)
+        .await?;
+        Ok(question)
+    }
+
+    /// 分页查询回收站（已软删除的问题），按删除时间倒序
+    pub async fn query_recycle_bin(
+        pool: &MySqlPool,
+        request_find_recycle_bin: &RequestFindRecycleBin,
+    )->Result<Page<Question>, BusinessError>{
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select count(1) as total_count from rc_qa_question where status=0 ");
+        if let Some(product_code) = &request_find_recycle_bin.product_code {
+            builder.push(" and product_code = ").push_bind(product_code);
+        }
+        let total_records = builder.build_query_as::<(i64,)>().fetch_one(pool).await?.0;
+
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select * from rc_qa_question where status=0 ");
+        if let Some(product_code) = &request_find_recycle_bin.product_code {
+            builder.push(" and product_code = ").push_bind(product_code);
+        }
+        let current_pageno = request_find_recycle_bin.current_pageno;
+        let page_size = request_find_recycle_bin.page_size;
+        let offset = (current_pageno-1)*page_size;
+        builder.push(format!(" order by `disable_time` desc,`id` desc limit {} offset {}",page_size,offset));
+        let questions = builder.build_query_as::<Question>().fetch_all(pool).await?;
+        Ok(Page::new(total_records, current_pageno, page_size, questions))
+    }
+
+    /// 依据软删除批次查询随问题一起失效的回答，按question_code分组
+    pub async fn query_answer_by_disable_batches(
+        pool: &MySqlPool,
+        disable_batches: &[String],
+    )->Result<HashMap<String, Vec<Answer>>, BusinessError>{
+        if disable_batches.is_empty(){
+            return Ok(HashMap::new());
+        }
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select * from rc_qa_answer where status='0' and `disable_batch` in (");
+        let mut separated = builder.separated(",");
+        for disable_batch in disable_batches{
+            separated.push_bind(disable_batch);
+        }
+        separated.push_unseparated(") order by `id`");
+        let answers = builder.build_query_as::<Answer>().fetch_all(pool).await?;
+        Ok(group_answers_by_question_code(answers))
+    }
+
     //构造查询条件
     pub fn query_question_list_condition<'a>(
         builder:&mut QueryBuilder<'a, sqlx::MySql>,
         request_find_question_list_for_trad: &'a RequestFindQuestionListForTrad
     ){
+        let match_mode = request_find_question_list_for_trad.match_mode;
+        //未指定状态时只查询有效的问题
+        let status = request_find_question_list_for_trad.status.unwrap_or(QuestionStatus::Active.into());
+        builder.push(" and status = ").push_bind(status);
+
+        let id_list = request_find_question_list_for_trad.id_list();
+        if !id_list.is_empty() {
+            builder.push(" and id in (");
+            let mut separated = builder.separated(",");
+            for id in id_list{
+                separated.push_bind(id);
+            }
+            separated.push_unseparated(")");
+        }
+        if let Some(sku_code) = &request_find_question_list_for_trad.sku_code {
+            Self::push_match(builder, "sku_code", sku_code, match_mode);
+        }
         if let Some(product_code) = &request_find_question_list_for_trad.product_code {
-            builder.push(" and product_code like ").push_bind(product_code);
+            Self::push_match(builder, "product_code", product_code, match_mode);
         }
         if let Some(question_content) = &request_find_question_list_for_trad.question_content {
-            builder.push(" and question_content like ").push_bind(question_content);
+            Self::push_match(builder, "question_content", question_content, match_mode);
         }
 
         if let Some(question_code) = &request_find_question_list_for_trad.question_code {
-            builder.push(" and question_code like ").push_bind(question_code);
+            Self::push_match(builder, "question_code", question_code, match_mode);
         }
 
         if let Some(create_name) = &request_find_question_list_for_trad.create_name {
-            builder.push(" and create_name like ").push_bind(create_name);
+            Self::push_match(builder, "creator_name", create_name, match_mode);
         }
 
         if let Some(start_time) = &request_find_question_list_for_trad.start_time {
@@ -243,8 +462,49 @@ impl QuestionDao{
         if let Some(end_time) = &request_find_question_list_for_trad.end_time {
             builder.push(" and create_time <= ").push_bind(end_time);
         }
+        if let Some(answer_code) = &request_find_question_list_for_trad.answer_code {
+            builder.push(" and question_code in (select question_code from rc_qa_answer where status=1 and id = ").push_bind(answer_code).push(")");
+        }
         if let Some(answer_content) = &request_find_question_list_for_trad.answer_content {
-            builder.push(" and question_code  in (select question_code from rc_qa_answer where status=1 and answer_content like ").push_bind(answer_content).push(")");
+            builder.push(" and question_code in (select question_code from rc_qa_answer where status=1");
+            Self::push_match(builder, "answer_content", answer_content, match_mode);
+            builder.push(")");
+        }
+        if let Some(tag) = &request_find_question_list_for_trad.tag {
+            Self::push_tag(builder, tag);
+        }
+    }
+
+    /// 拼接标签条件，只查询带有该标签的问题
+    fn push_tag<'a>(
+        builder:&mut QueryBuilder<'a, sqlx::MySql>,
+        tag:&'a String,
+    ){
+        builder.push(" and question_code in (select qt.question_code from rc_qa_question_tag qt join rc_qa_tag t on t.id = qt.tag_id where t.tag_name = ")
+            .push_bind(tag)
+            .push(")");
+    }
+
+    /// 按匹配方式拼接文本条件，match_mode为空时沿用直接like调用方传入的值
+    fn push_match<'a>(
+        builder:&mut QueryBuilder<'a, sqlx::MySql>,
+        column:&str,
+        value:&'a String,
+        match_mode:Option<MatchMode>,
+    ){
+        match match_mode {
+            None => {
+                builder.push(format!(" and {} like ",column)).push_bind(value);
+            },
+            Some(MatchMode::Exact) => {
+                builder.push(format!(" and {} = ",column)).push_bind(value);
+            },
+            Some(MatchMode::FullText) if FULLTEXT_COLUMNS.contains(&column) => {
+                builder.push(format!(" and match({}) against(",column)).push_bind(value).push(" in natural language mode)");
+            },
+            Some(match_mode) => {
+                builder.push(format!(" and {} like ",column)).push_bind(match_mode.like_pattern(value));
+            },
         }
     }
     
@@ -261,7 +521,7 @@ impl QuestionDao{
     }
 
     /// 依据查询条件查询问题列表（分页）
-    async fn query_question_list_by_page(
+    pub async fn query_question_list_by_page(
         pool: &MySqlPool,
         request_find_question_list_for_trad: &RequestFindQuestionListForTrad, 
         current_pageno:i64,
@@ -271,7 +531,8 @@ impl QuestionDao{
         let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select * from rc_qa_question where 1=1 ");
         Self::query_question_list_condition(&mut builder,request_find_question_list_for_trad);
         let offset = (current_pageno-1)*page_size;
-        builder.push(format!(" order by `sort` desc,`rank`,`create_time` desc,`id` limit {} offset {}",page_size,offset));
+        request_find_question_list_for_trad.push_order_by(&mut builder, now_local());
+        builder.push(format!(" limit {} offset {}",page_size,offset));
         let sql = builder.sql();
         tracing::info!("sql: {:?}", sql);
         let query = builder.build_query_as::<Question>();
@@ -280,6 +541,167 @@ impl QuestionDao{
         Ok(result)
     }
 
+    /// 依据查询条件按游标查询问题列表（keyset分页），不使用offset
+    /// cursor为None时查询第一页；with_total为true时额外查询总记录数
+    pub async fn query_question_list_by_cursor(
+        pool: &MySqlPool,
+        request_find_question_list_for_trad: &RequestFindQuestionListForTrad,
+        cursor: Option<&QuestionCursor>,
+        page_size:i64,
+        with_total:bool,
+    )->Result<CursorPage<Question>, BusinessError>{
+        let total_records = if with_total {
+            Some(Self::query_question_count(pool,request_find_question_list_for_trad).await?)
+        }else{
+            None
+        };
+        let now = now_local();
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select * from rc_qa_question where 1=1 ");
+        Self::query_question_list_condition(&mut builder,request_find_question_list_for_trad);
+        if let Some(cursor) = cursor {
+            //排序为sort desc,`rank`,`create_time` desc,`id`，sort为未到期的置顶值，取排在游标之后的记录
+            builder.push(" and (");
+            push_effective_sort(&mut builder, now);
+            builder.push(" < ").push_bind(cursor.sort).push(" or (");
+            push_effective_sort(&mut builder, now);
+            builder.push(" = ").push_bind(cursor.sort).push(" and `rank` > ").push_bind(cursor.rank).push(") or (");
+            push_effective_sort(&mut builder, now);
+            builder.push(" = ").push_bind(cursor.sort).push(" and `rank` = ").push_bind(cursor.rank)
+                .push(" and `create_time` < ").push_bind(cursor.create_time).push(") or (");
+            push_effective_sort(&mut builder, now);
+            builder.push(" = ").push_bind(cursor.sort).push(" and `rank` = ").push_bind(cursor.rank)
+                .push(" and `create_time` = ").push_bind(cursor.create_time).push(" and `id` > ").push_bind(cursor.id).push("))");
+        }
+        //多查一条，用于判断是否还有下一页
+        builder.push(" order by ");
+        push_effective_sort(&mut builder, now);
+        builder.push(format!(" desc,`rank`,`create_time` desc,`id` limit {}",page_size+1));
+        tracing::info!("sql: {:?}", builder.sql());
+        let query = builder.build_query_as::<Question>();
+        let mut questions = query.fetch_all(pool)
+            .await?;
+        let next_cursor = if questions.len() as i64 > page_size {
+            questions.truncate(page_size as usize);
+            questions.last().map(|question|QuestionCursor::from_question(question, now).encode())
+        }else{
+            None
+        };
+        Ok(CursorPage{
+            total_records,
+            page_size,
+            next_cursor,
+            data:questions,
+        })
+    }
+
+    /// 前台按商品（可选按SKU）分页查询有效且审核通过的问题，按置顶顺序排序
+    pub async fn query_approved_question_list_by_product(
+        pool: &MySqlPool,
+        product_code: &String,
+        sku_code: Option<&String>,
+        tag: Option<&String>,
+        current_pageno:i64,
+        page_size:i64,
+    )->Result<Page<Question>, BusinessError>{
+        let approved: i8 = ReviewStatus::Approved.into();
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select count(1) as total_count from rc_qa_question where status=1 ");
+        builder.push(" and review_status = ").push_bind(approved);
+        builder.push(" and product_code = ").push_bind(product_code);
+        if let Some(sku_code) = sku_code {
+            builder.push(" and sku_code = ").push_bind(sku_code);
+        }
+        if let Some(tag) = tag {
+            Self::push_tag(&mut builder, tag);
+        }
+        let total_records = builder.build_query_as::<(i64,)>().fetch_one(pool).await?.0;
+
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select * from rc_qa_question where status=1 ");
+        builder.push(" and review_status = ").push_bind(approved);
+        builder.push(" and product_code = ").push_bind(product_code);
+        if let Some(sku_code) = sku_code {
+            builder.push(" and sku_code = ").push_bind(sku_code);
+        }
+        if let Some(tag) = tag {
+            Self::push_tag(&mut builder, tag);
+        }
+        let offset = (current_pageno-1)*page_size;
+        builder.push(" order by ");
+        push_effective_sort(&mut builder, now_local());
+        builder.push(format!(" desc,`rank`,`create_time` desc,`id` limit {} offset {}",page_size,offset));
+        let questions = builder.build_query_as::<Question>().fetch_all(pool).await?;
+        Ok(Page::new(total_records, current_pageno, page_size, questions))
+    }
+
+    /// 查询商品下全部有效且审核通过的问题（生成FAQ快照用），排序与前台问题列表一致
+    pub async fn query_all_approved_question_by_product(
+        pool: &MySqlPool,
+        product_code: &String,
+    )->Result<Vec<Question>, BusinessError>{
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select * from rc_qa_question where status=1 ");
+        builder.push(" and review_status = ").push_bind::<i8>(ReviewStatus::Approved.into());
+        builder.push(" and product_code = ").push_bind(product_code);
+        builder.push(" order by ");
+        push_effective_sort(&mut builder, now_local());
+        builder.push(" desc,`rank`,`create_time` desc,`id`");
+        let questions = builder.build_query_as::<Question>().fetch_all(pool).await?;
+        Ok(questions)
+    }
+
+    /// 查询有前台展示问题的商品编号
+    pub async fn query_product_code_with_approved_question(
+        pool: &MySqlPool,
+    )->Result<Vec<String>, BusinessError>{
+        let product_codes = sqlx::query_as::<_,(String,)>(
+            "select distinct product_code from rc_qa_question where status=1 and review_status = ? order by product_code",
+        ).bind::<i8>(ReviewStatus::Approved.into())
+        .fetch_all(pool)
+        .await?;
+        Ok(product_codes.into_iter().map(|row|row.0).collect())
+    }
+
+    /// 在商品范围内全文检索有效且审核通过的问题，问题内容与回答内容任一命中即返回
+    /// 按相关度（问题内容相关度加上回答内容的最高相关度）降序排列
+    pub async fn search_approved_question(
+        pool: &MySqlPool,
+        product_code: &String,
+        keyword: &String,
+        current_pageno:i64,
+        page_size:i64,
+    )->Result<Page<SearchHit>, BusinessError>{
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select count(1) as total_count ");
+        Self::search_approved_question_condition(&mut builder, product_code, keyword);
+        let total_records = builder.build_query_as::<(i64,)>().fetch_one(pool).await?.0;
+
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select q.*, match(q.question_content) against(");
+        builder.push_bind(keyword);
+        builder.push(" in natural language mode) + coalesce(a.score, 0) as score ");
+        Self::search_approved_question_condition(&mut builder, product_code, keyword);
+        let offset = (current_pageno-1)*page_size;
+        builder.push(format!(" order by score desc, q.id limit {} offset {}",page_size,offset));
+        let hits = builder.build_query_as::<SearchHit>().fetch_all(pool).await?;
+        Ok(Page::new(total_records, current_pageno, page_size, hits))
+    }
+
+    fn search_approved_question_condition<'a>(
+        builder:&mut QueryBuilder<'a, sqlx::MySql>,
+        product_code: &'a String,
+        keyword: &'a String,
+    ){
+        let approved: i8 = ReviewStatus::Approved.into();
+        builder.push(" from rc_qa_question q left join (select question_code, max(match(answer_content) against(");
+        builder.push_bind(keyword);
+        builder.push(" in natural language mode)) as score from rc_qa_answer where status=1 and review_status = ");
+        builder.push_bind(approved);
+        builder.push(" and match(answer_content) against(");
+        builder.push_bind(keyword);
+        builder.push(" in natural language mode) group by question_code) a on a.question_code = q.question_code");
+        builder.push(" where q.status=1 and q.review_status = ").push_bind(approved);
+        builder.push(" and q.product_code = ").push_bind(product_code);
+        builder.push(" and (match(q.question_content) against(");
+        builder.push_bind(keyword);
+        builder.push(" in natural language mode) or a.question_code is not null)");
+    }
+
     /// 依据查询条件查询问题列表
     async fn query_question_count(
         pool: &MySqlPool,
@@ -295,6 +717,20 @@ impl QuestionDao{
         Ok(result.0)
     }
 
+    /// 符合查询条件的问题中有效回答最多的数量（导出CSV时确定回答列的数量）
+    pub async fn query_max_active_answer_count(
+        pool: &MySqlPool,
+        request_find_question_list_for_trad: &RequestFindQuestionListForTrad,
+    )->Result<i64, BusinessError>{
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new(
+            "select coalesce(max(t.answer_count),0) from (select (select count(1) from rc_qa_answer a
+            where a.question_code = rc_qa_question.question_code and a.status='1') as answer_count from rc_qa_question where 1=1 ");
+        Self::query_question_list_condition(&mut builder,request_find_question_list_for_trad);
+        builder.push(") t");
+        let result = builder.build_query_as::<(i64,)>().fetch_one(pool).await?;
+        Ok(result.0)
+    }
+
     /// 依据question_code查询回答记录
     pub async fn query_answer_by_question_code(
         pool: &MySqlPool,
@@ -308,6 +744,90 @@ impl QuestionDao{
         Ok(answer)
     }
 
+    /// 依据question_code查询有效且审核通过的回答记录（前台展示用）
+    pub async fn query_approved_answer_by_question_code(
+        pool: &MySqlPool,
+        question_code: &String,
+    )->Result<Vec<Answer>, BusinessError>{
+        let answer = sqlx::query_as::<_,Answer>(
+            "select * from rc_qa_answer where `question_code` = ? and status='1' and review_status = ? order by `id`",
+        ).bind(question_code)
+        .bind::<i8>(ReviewStatus::Approved.into())
+        .fetch_all(pool)
+        .await?;
+        Ok(answer)
+    }
+
+    /// 依据一批question_code批量查询回答记录，按question_code分组返回
+    /// 一页问题只执行一次查询（question_code in (...)），避免逐条查询回答
+    pub async fn query_answer_by_question_codes(
+        pool: &MySqlPool,
+        question_codes: &[String],
+    )->Result<HashMap<String, Vec<Answer>>, BusinessError>{
+        Self::query_answer_by_question_codes_with_condition(pool, question_codes, false).await
+    }
+
+    /// 依据一批question_code批量查询有效且审核通过的回答记录（前台展示用）
+    pub async fn query_approved_answer_by_question_codes(
+        pool: &MySqlPool,
+        question_codes: &[String],
+    )->Result<HashMap<String, Vec<Answer>>, BusinessError>{
+        Self::query_answer_by_question_codes_with_condition(pool, question_codes, true).await
+    }
+
+    async fn query_answer_by_question_codes_with_condition(
+        pool: &MySqlPool,
+        question_codes: &[String],
+        approved_only: bool,
+    )->Result<HashMap<String, Vec<Answer>>, BusinessError>{
+        if question_codes.is_empty(){
+            return Ok(HashMap::new());
+        }
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select * from rc_qa_answer where 1=1 ");
+        if approved_only {
+            builder.push(" and status='1' and review_status = ").push_bind::<i8>(ReviewStatus::Approved.into());
+        }
+        builder.push(" and `question_code` in (");
+        let mut separated = builder.separated(",");
+        for question_code in question_codes{
+            separated.push_bind(question_code);
+        }
+        separated.push_unseparated(") order by `id`");
+        let query = builder.build_query_as::<Answer>();
+        let answers = query.fetch_all(pool)
+            .await?;
+        Ok(group_answers_by_question_code(answers))
+    }
+
+    /// 置顶或取消置顶，同时设置置顶到期时间（取消置顶时为None）
+    pub async fn update_top_by_question_code(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        question_code:&String,
+        sort:i32,
+        top_expire_time:Option<chrono::NaiveDateTime>,
+    )->Result<(), BusinessError>{
+        let query = sqlx::query!("update rc_qa_question set sort=?,top_expire_time=? where question_code = ? and status=1",sort,top_expire_time,question_code);
+        query.execute(&mut **transaction)
+        .await?;
+        Ok(())
+    }
+
+    /// 将置顶已到期的问题重置为未置顶，返回被重置问题的(question_code, product_code)
+    pub async fn reset_expired_top_question(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        now:chrono::NaiveDateTime,
+    )->Result<Vec<(String, String)>, BusinessError>{
+        let expired = sqlx::query_as::<_,(String, String)>(
+            "select question_code, product_code from rc_qa_question where status=1 and sort > 0 and top_expire_time <= ? for update",
+        ).bind(now)
+        .fetch_all(&mut **transaction)
+        .await?;
+        for (question_code, _) in &expired{
+            QuestionDao::update_top_by_question_code(transaction, question_code, 0, None).await?;
+        }
+        Ok(expired)
+    }
+
     pub async fn update_sort_by_question_code(
         transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
         question_code:&String,
@@ -322,17 +842,995 @@ impl QuestionDao{
         Ok(())
     }
 
-    /// 查询下一个sort值
-    /// 逻辑：查询数据库中当前最大sort值+1
+    /// 查询审核队列（分页）
+    pub async fn query_review_queue(
+        pool: &MySqlPool,
+        request_find_review_queue: &RequestFindReviewQueue,
+    )->Result<Page<ReviewItem>, BusinessError>{
+        let review_status = request_find_review_queue.review_status.unwrap_or(ReviewStatus::Pending.into());
+        let current_pageno = request_find_review_queue.current_pageno;
+        let page_size = request_find_review_queue.page_size;
+        let (select_sql, count_sql) = match request_find_review_queue.target_type {
+            ReviewTargetType::Question => (
+                "select q.id,q.question_code,q.product_code,q.question_content as content,q.create_user_id,q.creator_name,
+                q.create_time,q.review_status,q.reject_reason from rc_qa_question q where q.status=1 ",
+                "select count(1) as total_count from rc_qa_question q where q.status=1 ",
+            ),
+            ReviewTargetType::Answer => (
+                "select a.id,a.question_code,q.product_code,a.answer_content as content,a.create_user_id,a.creator_name,
+                a.create_time,a.review_status,a.reject_reason from rc_qa_answer a
+                join rc_qa_question q on q.question_code = a.question_code where a.status=1 ",
+                "select count(1) as total_count from rc_qa_answer a
+                join rc_qa_question q on q.question_code = a.question_code where a.status=1 ",
+            ),
+            ReviewTargetType::Reply => (
+                "select r.id,r.question_code,q.product_code,r.reply_content as content,r.create_user_id,r.creator_name,
+                r.create_time,r.review_status,r.reject_reason from rc_qa_reply r
+                join rc_qa_question q on q.question_code = r.question_code where r.status=1 ",
+                "select count(1) as total_count from rc_qa_reply r
+                join rc_qa_question q on q.question_code = r.question_code where r.status=1 ",
+            ),
+        };
+        let alias = match request_find_review_queue.target_type {
+            ReviewTargetType::Question => "q",
+            ReviewTargetType::Answer => "a",
+            ReviewTargetType::Reply => "r",
+        };
+
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new(count_sql);
+        builder.push(format!(" and {}.review_status = ",alias)).push_bind(review_status);
+        if let Some(product_code) = &request_find_review_queue.product_code {
+            builder.push(" and q.product_code = ").push_bind(product_code);
+        }
+        let total_records = builder.build_query_as::<(i64,)>().fetch_one(pool).await?.0;
+
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new(select_sql);
+        builder.push(format!(" and {}.review_status = ",alias)).push_bind(review_status);
+        if let Some(product_code) = &request_find_review_queue.product_code {
+            builder.push(" and q.product_code = ").push_bind(product_code);
+        }
+        //先提交的先审核
+        let offset = (current_pageno-1)*page_size;
+        builder.push(format!(" order by {}.create_time,{}.id limit {} offset {}",alias,alias,page_size,offset));
+        let items = builder.build_query_as::<ReviewItem>().fetch_all(pool).await?;
+        Ok(Page::new(total_records, current_pageno, page_size, items))
+    }
+
+    /// 问题重新进入待审核状态，清除之前的审核结果
+    pub async fn reset_question_review(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        question_code: &String,
+    )->Result<(), BusinessError>{
+        let pending: i8 = ReviewStatus::Pending.into();
+        let query = sqlx::query!("update rc_qa_question set `review_status` = ?,`reject_reason` = null,`reviewer_id` = null,`review_time` = null where `question_code` = ?",
+            pending,
+            question_code
+        );
+        query.execute(&mut **transaction)
+        .await?;
+        Ok(())
+    }
+
+    /// 设置问题的审核结果，返回(问题编号,商品编号)；问题不存在或已失效时返回None
+    pub async fn review_question(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        id: i64,
+        review_status: i8,
+        reject_reason: &Option<String>,
+        reviewer_id: &String,
+        review_time: chrono::NaiveDateTime,
+    )->Result<Option<(String,String)>, BusinessError>{
+        let question_code = sqlx::query_as::<_,(String,String)>(
+            "select question_code,product_code from rc_qa_question where `id` = ? and status='1' for update",
+        ).bind(id)
+        .fetch_optional(&mut **transaction)
+        .await?;
+        if question_code.is_none(){
+            return Ok(None);
+        }
+        let query = sqlx::query!("update rc_qa_question set `review_status` = ?,`reject_reason` = ?,`reviewer_id` = ?,`review_time` = ? where `id` = ?",
+            review_status,
+            reject_reason,
+            reviewer_id,
+            review_time,
+            id
+        );
+        query.execute(&mut **transaction)
+        .await?;
+        Ok(question_code)
+    }
+
+    /// 设置回答的审核结果，返回回答所属的(问题编号,商品编号)；回答不存在或已失效时返回None
+    pub async fn review_answer(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        id: i64,
+        review_status: i8,
+        reject_reason: &Option<String>,
+        reviewer_id: &String,
+        review_time: chrono::NaiveDateTime,
+    )->Result<Option<(String,String)>, BusinessError>{
+        let question_code = sqlx::query_as::<_,(String,String)>(
+            "select a.question_code,q.product_code from rc_qa_answer a
+            join rc_qa_question q on q.question_code = a.question_code
+            where a.`id` = ? and a.status='1' for update",
+        ).bind(id)
+        .fetch_optional(&mut **transaction)
+        .await?;
+        if question_code.is_none(){
+            return Ok(None);
+        }
+        let query = sqlx::query!("update rc_qa_answer set `review_status` = ?,`reject_reason` = ?,`reviewer_id` = ?,`review_time` = ? where `id` = ?",
+            review_status,
+            reject_reason,
+            reviewer_id,
+            review_time,
+            id
+        );
+        query.execute(&mut **transaction)
+        .await?;
+        Ok(question_code)
+    }
+
+    /// 设置追问回复的审核结果，返回回复所属的(问题编号,商品编号)；回复不存在或已失效时返回None
+    pub async fn review_reply(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        id: i64,
+        review_status: i8,
+        reject_reason: &Option<String>,
+        reviewer_id: &String,
+        review_time: chrono::NaiveDateTime,
+    )->Result<Option<(String,String)>, BusinessError>{
+        let question_code = sqlx::query_as::<_,(String,String)>(
+            "select r.question_code,q.product_code from rc_qa_reply r
+            join rc_qa_question q on q.question_code = r.question_code
+            where r.`id` = ? and r.status='1' for update",
+        ).bind(id)
+        .fetch_optional(&mut **transaction)
+        .await?;
+        if question_code.is_none(){
+            return Ok(None);
+        }
+        let query = sqlx::query!("update rc_qa_reply set `review_status` = ?,`reject_reason` = ?,`reviewer_id` = ?,`review_time` = ? where `id` = ?",
+            review_status,
+            reject_reason,
+            reviewer_id,
+            review_time,
+            id
+        );
+        query.execute(&mut **transaction)
+        .await?;
+        Ok(question_code)
+    }
+
+    /// 查询前台展示的回答所属的问题编号，回答不存在、已失效或未审核通过时返回None
+    pub async fn find_approved_answer_question_code(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        answer_id: i64,
+    )->Result<Option<String>, BusinessError>{
+        let question_code = sqlx::query_as::<_,(String,)>(
+            "select question_code from rc_qa_answer where `id` = ? and status='1' and review_status = ?",
+        ).bind(answer_id)
+        .bind::<i8>(ReviewStatus::Approved.into())
+        .fetch_optional(&mut **transaction)
+        .await?;
+        Ok(question_code.map(|row|row.0))
+    }
+
+    /// 依据id查询有效且审核通过的回答（前台展示用）
+    pub async fn find_approved_answer(
+        pool: &MySqlPool,
+        answer_id: i64,
+    )->Result<Option<Answer>, BusinessError>{
+        let answer = sqlx::query_as::<_,Answer>(
+            "select * from rc_qa_answer where `id` = ? and status='1' and review_status = ?",
+        ).bind(answer_id)
+        .bind::<i8>(ReviewStatus::Approved.into())
+        .fetch_optional(pool)
+        .await?;
+        Ok(answer)
+    }
+
+    /// 依据id查询追问回复
+    pub async fn find_reply(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        id: i64,
+    )->Result<Option<Reply>, BusinessError>{
+        let reply = sqlx::query_as::<_,Reply>(
+            "select * from rc_qa_reply where `id` = ?",
+        ).bind(id)
+        .fetch_optional(&mut **transaction)
+        .await?;
+        Ok(reply)
+    }
+
+    /// 插入追问回复，返回回复的id
+    pub async fn insert_reply(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        reply: &Reply,
+    )->Result<u64, BusinessError>{
+        let query = sqlx::query!("insert into rc_qa_reply (`answer_id`,`parent_id`,`question_code`,`depth`,`reply_content`,`create_user_id`,`creator_name`,`status`,`review_status`,`create_time`,`update_time`)
+            values (?,?,?,?,?,?,?,?,?,?,?)",
+            reply.answer_id,
+            reply.parent_id,
+            reply.question_code,
+            reply.depth,
+            reply.reply_content,
+            reply.create_user_id,
+            reply.creator_name,
+            reply.status,
+            reply.review_status,
+            reply.create_time,
+            reply.update_time
+        );
+        let result = query.execute(&mut **transaction)
+        .await?;
+        Ok(result.last_insert_id())
+    }
+
+    /// 依据一批回答id批量查询有效且审核通过的追问回复（前台展示用），按回答id分组，组内按id排序
+    pub async fn query_approved_reply_by_answer_ids(
+        pool: &MySqlPool,
+        answer_ids: &[i64],
+    )->Result<HashMap<i64, Vec<Reply>>, BusinessError>{
+        let mut replies_by_answer_id: HashMap<i64, Vec<Reply>> = HashMap::new();
+        if answer_ids.is_empty(){
+            return Ok(replies_by_answer_id);
+        }
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select * from rc_qa_reply where status='1' and review_status = ");
+        builder.push_bind::<i8>(ReviewStatus::Approved.into());
+        builder.push(" and `answer_id` in (");
+        let mut separated = builder.separated(",");
+        for answer_id in answer_ids{
+            separated.push_bind(answer_id);
+        }
+        separated.push_unseparated(") order by `id`");
+        let replies = builder.build_query_as::<Reply>().fetch_all(pool).await?;
+        for reply in replies{
+            replies_by_answer_id.entry(reply.answer_id).or_default().push(reply);
+        }
+        Ok(replies_by_answer_id)
+    }
+
+    /// 分页查询回答下有效且审核通过的一级追问回复，按id排序
+    pub async fn query_approved_root_reply_page(
+        pool: &MySqlPool,
+        answer_id: i64,
+        current_pageno: i64,
+        page_size: i64,
+    )->Result<Page<Reply>, BusinessError>{
+        let approved: i8 = ReviewStatus::Approved.into();
+        let total_records = sqlx::query_as::<_,(i64,)>(
+            "select count(1) from rc_qa_reply where `answer_id` = ? and `parent_id` is null and status='1' and review_status = ?",
+        ).bind(answer_id)
+        .bind(approved)
+        .fetch_one(pool)
+        .await?.0;
+        let offset = (current_pageno-1)*page_size;
+        let replies = sqlx::query_as::<_,Reply>(
+            "select * from rc_qa_reply where `answer_id` = ? and `parent_id` is null and status='1' and review_status = ? order by `id` limit ? offset ?",
+        ).bind(answer_id)
+        .bind(approved)
+        .bind(page_size)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+        Ok(Page::new(total_records, current_pageno, page_size, replies))
+    }
+
+    /// 查询一批回复下全部有效且审核通过的下级回复，上级回复未审核通过时其下级回复也不查询，按id排序
+    pub async fn query_approved_reply_descendants(
+        pool: &MySqlPool,
+        parent_ids: &[i64],
+    )->Result<Vec<Reply>, BusinessError>{
+        if parent_ids.is_empty(){
+            return Ok(vec![]);
+        }
+        let approved: i8 = ReviewStatus::Approved.into();
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new(
+            "with recursive reply_tree as (select * from rc_qa_reply where status='1' and review_status = ");
+        builder.push_bind(approved);
+        builder.push(" and `parent_id` in (");
+        let mut separated = builder.separated(",");
+        for parent_id in parent_ids{
+            separated.push_bind(parent_id);
+        }
+        separated.push_unseparated(")");
+        builder.push(" union all select r.* from rc_qa_reply r join reply_tree t on r.`parent_id` = t.`id` where r.status='1' and r.review_status = ");
+        builder.push_bind(approved);
+        builder.push(") select * from reply_tree order by `id`");
+        let replies = builder.build_query_as::<Reply>().fetch_all(pool).await?;
+        Ok(replies)
+    }
+
+    /// 批量累加问题的每日浏览量
+    pub async fn add_question_views(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        views: &[QuestionView],
+        now: chrono::NaiveDateTime,
+    )->Result<(), BusinessError>{
+        if views.is_empty(){
+            return Ok(());
+        }
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new(
+            "insert into rc_qa_question_view_daily (`question_code`,`view_date`,`view_count`,`create_time`,`update_time`) ");
+        builder.push_values(views, |mut row, (question_code, view_date, view_count)|{
+            row.push_bind(question_code)
+                .push_bind(view_date)
+                .push_bind(view_count)
+                .push_bind(now)
+                .push_bind(now);
+        });
+        builder.push(" on duplicate key update `view_count` = `view_count` + values(`view_count`),`update_time` = values(`update_time`)");
+        builder.build().execute(&mut **transaction).await?;
+        Ok(())
+    }
+
+    /// 查询商品下浏览日期范围内浏览量最高的有效问题，返回(问题编号,问题内容,浏览量)
+    pub async fn query_top_viewed_question(
+        pool: &MySqlPool,
+        product_code: &String,
+        start_date: Option<chrono::NaiveDate>,
+        end_date: Option<chrono::NaiveDate>,
+        limit: i64,
+    )->Result<Vec<(String, String, i64)>, BusinessError>{
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new(
+            "select q.question_code,q.question_content,cast(sum(v.view_count) as signed) as view_count from rc_qa_question_view_daily v
+            join rc_qa_question q on q.question_code = v.question_code where q.status=1 and q.product_code = ");
+        builder.push_bind(product_code);
+        if let Some(start_date) = start_date {
+            builder.push(" and v.view_date >= ").push_bind(start_date);
+        }
+        if let Some(end_date) = end_date {
+            builder.push(" and v.view_date <= ").push_bind(end_date);
+        }
+        builder.push(format!(" group by q.question_code,q.question_content order by view_count desc,q.question_code limit {}",limit));
+        let questions = builder.build_query_as::<(String, String, i64)>().fetch_all(pool).await?;
+        Ok(questions)
+    }
+
+    /// 分页查询没有有效回答的有效问题，先创建的排在前面
+    pub async fn query_unanswered_question(
+        pool: &MySqlPool,
+        request_find_unanswered_question: &RequestFindUnansweredQuestion,
+    )->Result<Page<Question>, BusinessError>{
+        let (start_time, end_time) = request_find_unanswered_question.create_time_range();
+        let condition = |builder: &mut QueryBuilder<'_, sqlx::MySql>|{
+            //待审核或审核拒绝的回答前台看不到，仍算作没有回答
+            builder.push(" and not exists (select 1 from rc_qa_answer a where a.question_code = q.question_code and a.status=1 and a.review_status = ");
+            builder.push_bind::<i8>(ReviewStatus::Approved.into());
+            builder.push(")");
+            if let Some(product_code) = &request_find_unanswered_question.product_code {
+                builder.push(" and q.product_code = ").push_bind(product_code.clone());
+            }
+            if let Some(start_time) = start_time {
+                builder.push(" and q.create_time >= ").push_bind(start_time);
+            }
+            if let Some(end_time) = end_time {
+                builder.push(" and q.create_time < ").push_bind(end_time);
+            }
+        };
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select count(1) as total_count from rc_qa_question q where q.status=1 ");
+        condition(&mut builder);
+        let total_records = builder.build_query_as::<(i64,)>().fetch_one(pool).await?.0;
+
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select q.* from rc_qa_question q where q.status=1 ");
+        condition(&mut builder);
+        let current_pageno = request_find_unanswered_question.current_pageno;
+        let page_size = request_find_unanswered_question.page_size;
+        let offset = (current_pageno-1)*page_size;
+        builder.push(format!(" order by q.create_time,q.id limit {} offset {}",page_size,offset));
+        let questions = builder.build_query_as::<Question>().fetch_all(pool).await?;
+        Ok(Page::new(total_records, current_pageno, page_size, questions))
+    }
+
+    /// 按商品统计有效问题的回答时效（问题创建到第一次有回答的秒数），回答之后被修改或删除不影响统计
+    pub async fn query_answer_latency(
+        pool: &MySqlPool,
+        request_find_answer_latency: &RequestFindAnswerLatency,
+    )->Result<Vec<AnswerLatencyStat>, BusinessError>{
+        let (start_time, end_time) = request_find_answer_latency.create_time_range();
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new(
+            "select q.product_code,count(1) as question_count,count(q.first_answer_time) as answered_count,
+            cast(sum(timestampdiff(second,q.create_time,q.first_answer_time)) as signed) as total_latency_seconds,
+            min(timestampdiff(second,q.create_time,q.first_answer_time)) as min_latency_seconds,
+            max(timestampdiff(second,q.create_time,q.first_answer_time)) as max_latency_seconds
+            from rc_qa_question q where q.status=1 ");
+        if let Some(product_code) = &request_find_answer_latency.product_code {
+            builder.push(" and q.product_code = ").push_bind(product_code);
+        }
+        if let Some(start_time) = start_time {
+            builder.push(" and q.create_time >= ").push_bind(start_time);
+        }
+        if let Some(end_time) = end_time {
+            builder.push(" and q.create_time < ").push_bind(end_time);
+        }
+        builder.push(" group by q.product_code order by q.product_code");
+        let stats = builder.build_query_as::<AnswerLatencyStat>().fetch_all(pool).await?;
+        Ok(stats)
+    }
+
+    /// 记录用户对回答的投票并更新回答的票数，返回回答所属的(问题编号,商品编号)
+    /// 只能对前台展示的回答投票，回答不存在、已失效或未审核通过时返回None
+    /// 先锁定回答记录，同一回答的投票依次执行，同一用户不会重复计票
+    pub async fn vote_answer(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        answer_id: i64,
+        user_id: &String,
+        vote: Vote,
+        vote_time: chrono::NaiveDateTime,
+    )->Result<Option<(String,String)>, BusinessError>{
+        let question_code = sqlx::query_as::<_,(String,String)>(
+            "select a.question_code,q.product_code from rc_qa_answer a
+            join rc_qa_question q on q.question_code = a.question_code
+            where a.`id` = ? and a.status='1' and a.review_status = ? for update",
+        ).bind(answer_id)
+        .bind::<i8>(ReviewStatus::Approved.into())
+        .fetch_optional(&mut **transaction)
+        .await?;
+        if question_code.is_none(){
+            return Ok(None);
+        }
+        let old_vote = sqlx::query_as::<_,(i8,)>(
+            "select vote from rc_qa_answer_vote where `answer_id` = ? and `user_id` = ?",
+        ).bind(answer_id)
+        .bind(user_id)
+        .fetch_optional(&mut **transaction)
+        .await?
+        .map(|row|Vote::from(&row.0));
+        if old_vote == Some(vote){
+            return Ok(question_code);
+        }
+
+        let vote_value: i8 = vote.into();
+        if old_vote.is_some(){
+            let query = sqlx::query!("update rc_qa_answer_vote set `vote` = ?,`update_time` = ? where `answer_id` = ? and `user_id` = ?",
+                vote_value,vote_time,answer_id,user_id);
+            query.execute(&mut **transaction)
+            .await?;
+        }else{
+            let query = sqlx::query!("insert into rc_qa_answer_vote (`answer_id`,`user_id`,`vote`,`create_time`,`update_time`) values (?,?,?,?,?)",
+                answer_id,user_id,vote_value,vote_time,vote_time);
+            query.execute(&mut **transaction)
+            .await?;
+        }
+        let (helpful_delta, unhelpful_delta) = vote.counter_delta(old_vote);
+        let query = sqlx::query!("update rc_qa_answer set `helpful_count` = `helpful_count` + ?,`unhelpful_count` = `unhelpful_count` + ? where `id` = ?",
+            helpful_delta,unhelpful_delta,answer_id);
+        query.execute(&mut **transaction)
+        .await?;
+        Ok(question_code)
+    }
+
+    /// 插入审核记录
+    pub async fn insert_review_log(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        review_log: &ReviewLog,
+    )->Result<(), BusinessError>{
+        let query = sqlx::query!(
+            "insert into rc_qa_review_log (
+            `target_type`,
+            `target_id`,
+            `question_code`,
+            `review_status`,
+            `reject_reason`,
+            `reviewer_id`,
+            `reviewer_name`,
+            `create_time`)
+            values (
+            ?,?,?,?,?,?,?,?
+            )",
+            review_log.target_type,
+            review_log.target_id,
+            review_log.question_code,
+            review_log.review_status,
+            review_log.reject_reason,
+            review_log.reviewer_id,
+            review_log.reviewer_name,
+            review_log.create_time
+        );
+        query.execute(&mut **transaction)
+        .await?;
+        Ok(())
+    }
+
+    /// 在事务中依据question_code查询问题记录（不限状态）并加锁，问题不存在时返回QuestionNotFound
+    pub async fn find_question_for_update(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        question_code: &String,
+    )->Result<Question, BusinessError>{
+        let question = sqlx::query_as::<_,Question>(
+            "select * from rc_qa_question where `question_code` = ? for update",
+        ).bind(question_code)
+        .fetch_optional(&mut **transaction)
+        .await?;
+        question.ok_or_else(||{
+            let mut parameters= HashMap::new();
+            parameters.insert("question_code".to_string(), question_code.clone());
+            BusinessError::QuestionNotFound((None,Some(parameters)))
+        })
+    }
+
+    /// 查询同一商品下与question_content全文相关度最高的其它有效问题的(question_code, question_content)，用于查找重复的问题
+    /// 使用问题内容的ngram全文索引限制候选数量，不加载商品下的全部问题
+    pub async fn query_duplicate_candidates(
+        pool: &MySqlPool,
+        product_code: &String,
+        question_content: &String,
+        exclude_question_code: &String,
+        limit: i64,
+    )->Result<Vec<(String, String)>, BusinessError>{
+        let questions = sqlx::query_as::<_,(String, String)>(
+            "select question_code, question_content from rc_qa_question where `product_code` = ? and status='1' and `question_code` != ? \
+            and match(question_content) against(? in natural language mode) \
+            order by match(question_content) against(? in natural language mode) desc, id limit ?",
+        ).bind(product_code)
+        .bind(exclude_question_code)
+        .bind(question_content)
+        .bind(question_content)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+        Ok(questions)
+    }
+
+    /// 合并问题：来源问题的有效回答移到目标问题，来源问题失效，并记录重定向
+    /// 返回(来源问题, 目标问题)合并前的记录
+    pub async fn merge_question(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        source_question_code: &String,
+        target_question_code: &String,
+        operator_id: &Option<String>,
+        merge_time: chrono::NaiveDateTime,
+    )->Result<(Question, Question), BusinessError>{
+        //按问题编号顺序加锁，避免两个方向相反的合并互相等待
+        let (source_question, target_question) = if source_question_code < target_question_code {
+            let source_question = QuestionDao::find_question_for_update(transaction, source_question_code).await?;
+            let target_question = QuestionDao::find_question_for_update(transaction, target_question_code).await?;
+            (source_question, target_question)
+        }else{
+            let target_question = QuestionDao::find_question_for_update(transaction, target_question_code).await?;
+            let source_question = QuestionDao::find_question_for_update(transaction, source_question_code).await?;
+            (source_question, target_question)
+        };
+        let active: i8 = QuestionStatus::Active.into();
+        for question in [&source_question, &target_question]{
+            if question.status != active {
+                let mut parameters= HashMap::new();
+                parameters.insert("question_code".to_string(), question.question_code.clone());
+                return Err(BusinessError::QuestionNotFound((None,Some(parameters))));
+            }
+        }
+        if source_question.product_code != target_question.product_code {
+            let mut parameters = HashMap::new();
+            parameters.insert("field".to_string(), "targetQuestionCode".to_string());
+            parameters.insert("message".to_string(), "只能合并同一商品下的问题".to_string());
+            return Err(BusinessError::InvalidParameter((None,Some(vec![parameters]))));
+        }
+
+        let query = sqlx::query!("update rc_qa_answer set `question_code` = ?,`update_time` = ? where `question_code` = ? and `status`='1'",
+            target_question_code,merge_time,source_question_code);
+        query.execute(&mut **transaction)
+        .await?;
+        //回答的翻译随回答移到目标问题
+        let query = sqlx::query!("update rc_qa_answer_translation t join rc_qa_answer a on a.id = t.answer_id set t.`question_code` = a.`question_code` where t.`question_code` = ?",
+            source_question_code);
+        query.execute(&mut **transaction)
+        .await?;
+        QuestionDao::disabled_question_and_answer_by_question_code(transaction, source_question_code).await?;
+
+        //之前重定向到来源问题的记录改为重定向到目标问题，查询时只需要跳转一次
+        let query = sqlx::query!("update rc_qa_question_redirect set `target_question_code` = ? where `target_question_code` = ?",
+            target_question_code,source_question_code);
+        query.execute(&mut **transaction)
+        .await?;
+        let query = sqlx::query!("insert into rc_qa_question_redirect (`source_question_code`,`target_question_code`,`operator_id`,`create_time`) values (?,?,?,?)
+            on duplicate key update `target_question_code` = values(`target_question_code`),`operator_id` = values(`operator_id`),`create_time` = values(`create_time`)",
+            source_question_code,target_question_code,operator_id,merge_time);
+        query.execute(&mut **transaction)
+        .await?;
+        Ok((source_question, target_question))
+    }
+
+    /// 保存问题的标签：不存在的标签先创建，再用传入的标签替换问题原有的标签
+    pub async fn save_question_tags(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        question_code: &String,
+        tags: &[String],
+    )->Result<(), BusinessError>{
+        let query = sqlx::query!("delete from rc_qa_question_tag where `question_code` = ?", question_code);
+        query.execute(&mut **transaction)
+        .await?;
+        if tags.is_empty(){
+            return Ok(());
+        }
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("insert ignore into rc_qa_tag (`tag_name`) ");
+        builder.push_values(tags, |mut separated, tag|{
+            separated.push_bind(tag);
+        });
+        builder.build().execute(&mut **transaction).await?;
+
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("insert into rc_qa_question_tag (`question_code`,`tag_id`) select ");
+        builder.push_bind(question_code).push(", id from rc_qa_tag where `tag_name` in (");
+        let mut separated = builder.separated(",");
+        for tag in tags{
+            separated.push_bind(tag);
+        }
+        separated.push_unseparated(")");
+        builder.build().execute(&mut **transaction).await?;
+        Ok(())
+    }
+
+    /// 依据一批question_code批量查询问题的标签，按question_code分组返回
+    pub async fn query_tags_by_question_codes(
+        pool: &MySqlPool,
+        question_codes: &[String],
+    )->Result<HashMap<String, Vec<String>>, BusinessError>{
+        if question_codes.is_empty(){
+            return Ok(HashMap::new());
+        }
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select qt.question_code, t.tag_name from rc_qa_question_tag qt join rc_qa_tag t on t.id = qt.tag_id where qt.question_code in (");
+        let mut separated = builder.separated(",");
+        for question_code in question_codes{
+            separated.push_bind(question_code);
+        }
+        separated.push_unseparated(") order by t.tag_name");
+        let rows = builder.build_query_as::<(String, String)>().fetch_all(pool).await?;
+        let mut tags_by_question_code: HashMap<String, Vec<String>> = HashMap::new();
+        for (question_code, tag_name) in rows{
+            tags_by_question_code.entry(question_code).or_default().push(tag_name);
+        }
+        Ok(tags_by_question_code)
+    }
+
+    /// 统计商品下各标签关联的有效问题数量，按数量从高到低排序
+    pub async fn query_tag_usage_by_product(
+        pool: &MySqlPool,
+        product_code: &String,
+    )->Result<Vec<(String, i64)>, BusinessError>{
+        let usages = sqlx::query_as::<_,(String, i64)>(
+            "select t.tag_name, count(1) as question_count from rc_qa_question_tag qt
+            join rc_qa_tag t on t.id = qt.tag_id
+            join rc_qa_question q on q.question_code = qt.question_code
+            where q.`product_code` = ? and q.status='1'
+            group by t.tag_name order by question_count desc, t.tag_name",
+        ).bind(product_code)
+        .fetch_all(pool)
+        .await?;
+        Ok(usages)
+    }
+
+    /// 保存问题某个语言的翻译，已存在时覆盖
+    pub async fn save_question_translation(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        question_code: &String,
+        locale: Locale,
+        question_content: &String,
+        update_time: chrono::NaiveDateTime,
+    )->Result<(), BusinessError>{
+        let query = sqlx::query!("insert into rc_qa_question_translation (`question_code`,`locale`,`question_content`,`update_time`) values (?,?,?,?)
+            on duplicate key update `question_content` = values(`question_content`),`update_time` = values(`update_time`)",
+            question_code,locale.as_str(),question_content,update_time);
+        query.execute(&mut **transaction)
+        .await?;
+        Ok(())
+    }
+
+    /// 保存回答某个语言的翻译，已存在时覆盖
+    pub async fn save_answer_translation(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        answer_id: i64,
+        question_code: &String,
+        locale: Locale,
+        answer_content: &String,
+        update_time: chrono::NaiveDateTime,
+    )->Result<(), BusinessError>{
+        let query = sqlx::query!("insert into rc_qa_answer_translation (`answer_id`,`question_code`,`locale`,`answer_content`,`update_time`) values (?,?,?,?,?)
+            on duplicate key update `answer_content` = values(`answer_content`),`update_time` = values(`update_time`)",
+            answer_id,question_code,locale.as_str(),answer_content,update_time);
+        query.execute(&mut **transaction)
+        .await?;
+        Ok(())
+    }
+
+    /// 删除问题及其回答某个语言的翻译，返回是否删除了翻译
+    pub async fn delete_question_translation(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        question_code: &String,
+        locale: Locale,
+    )->Result<bool, BusinessError>{
+        let query = sqlx::query!("delete from rc_qa_question_translation where `question_code` = ? and `locale` = ?",
+            question_code,locale.as_str());
+        let question_rows = query.execute(&mut **transaction)
+        .await?.rows_affected();
+        let query = sqlx::query!("delete from rc_qa_answer_translation where `question_code` = ? and `locale` = ?",
+            question_code,locale.as_str());
+        let answer_rows = query.execute(&mut **transaction)
+        .await?.rows_affected();
+        Ok(question_rows + answer_rows > 0)
+    }
+
+    /// 删除一批回答的翻译
+    pub async fn delete_answer_translation_by_answer_ids(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        answer_ids: &[i64],
+    )->Result<(), BusinessError>{
+        if answer_ids.is_empty(){
+            return Ok(());
+        }
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("delete from rc_qa_answer_translation where `answer_id` in (");
+        let mut separated = builder.separated(",");
+        for answer_id in answer_ids{
+            separated.push_bind(answer_id);
+        }
+        separated.push_unseparated(")");
+        builder.build().execute(&mut **transaction).await?;
+        Ok(())
+    }
+
+    /// 依据一批question_code查询问题的翻译，locales为空时查询全部语言
+    pub async fn query_question_translations(
+        pool: &MySqlPool,
+        question_codes: &[String],
+        locales: &[Locale],
+    )->Result<Vec<QuestionTranslation>, BusinessError>{
+        if question_codes.is_empty(){
+            return Ok(vec![]);
+        }
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select * from rc_qa_question_translation where 1=1 ");
+        Self::push_translation_condition(&mut builder, question_codes, locales);
+        let translations = builder.build_query_as::<QuestionTranslation>().fetch_all(pool).await?;
+        Ok(translations)
+    }
+
+    /// 依据一批question_code查询问题下回答的翻译，locales为空时查询全部语言
+    pub async fn query_answer_translations(
+        pool: &MySqlPool,
+        question_codes: &[String],
+        locales: &[Locale],
+    )->Result<Vec<AnswerTranslation>, BusinessError>{
+        if question_codes.is_empty(){
+            return Ok(vec![]);
+        }
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select * from rc_qa_answer_translation where 1=1 ");
+        Self::push_translation_condition(&mut builder, question_codes, locales);
+        builder.push(" order by `answer_id`");
+        let translations = builder.build_query_as::<AnswerTranslation>().fetch_all(pool).await?;
+        Ok(translations)
+    }
+
+    fn push_translation_condition<'a>(
+        builder:&mut QueryBuilder<'a, sqlx::MySql>,
+        question_codes: &'a [String],
+        locales: &[Locale],
+    ){
+        builder.push(" and `question_code` in (");
+        let mut separated = builder.separated(",");
+        for question_code in question_codes{
+            separated.push_bind(question_code);
+        }
+        separated.push_unseparated(")");
+        if !locales.is_empty(){
+            builder.push(" and `locale` in (");
+            let mut separated = builder.separated(",");
+            for locale in locales{
+                separated.push_bind(locale.as_str());
+            }
+            separated.push_unseparated(")");
+        }
+    }
+
+    /// 查询被合并问题重定向到的问题编号
+    pub async fn find_question_redirect(
+        pool: &MySqlPool,
+        question_code: &String,
+    )->Result<Option<String>, BusinessError>{
+        let target_question_code = sqlx::query_as::<_,(String,)>(
+            "select target_question_code from rc_qa_question_redirect where `source_question_code` = ?",
+        ).bind(question_code)
+        .fetch_optional(pool)
+        .await?
+        .map(|row|row.0);
+        Ok(target_question_code)
+    }
+
+    /// 保存问题当前状态（含全部回答）的快照，返回新的修订版本号
+    /// 在变更问题的事务中调用，问题记录加锁后再计算版本号，同一问题的修订不会并发生成相同的版本号
+    pub async fn insert_revision(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        question_code: &String,
+        action: RevisionAction,
+        operator_id: Option<String>,
+        create_time: chrono::NaiveDateTime,
+    )->Result<i32, BusinessError>{
+        let question = QuestionDao::find_question_for_update(transaction, question_code).await?;
+        let answers = sqlx::query_as::<_,Answer>(
+            "select * from rc_qa_answer where `question_code` = ? order by `id`",
+        ).bind(question_code)
+        .fetch_all(&mut **transaction)
+        .await?;
+        let snapshot = serde_json::to_string(&QuestionSnapshot::from_db(&question, &answers))
+            .map_err(|error|BusinessError::InternalServerError((Some(error.to_string()),None)))?;
+
+        let revision = sqlx::query_as::<_,(i64,)>(
+            "select cast(coalesce(max(`revision`),0)+1 as signed) from rc_qa_question_revision where `question_code` = ?",
+        ).bind(question_code)
+        .fetch_one(&mut **transaction)
+        .await?.0 as i32;
+
+        let query = sqlx::query!(
+            "insert into rc_qa_question_revision (
+            `question_code`,
+            `revision`,
+            `action`,
+            `snapshot`,
+            `operator_id`,
+            `create_time`)
+            values (
+            ?,?,?,?,?,?
+            )",
+            question_code,
+            revision,
+            action.as_str(),
+            snapshot,
+            operator_id,
+            create_time
+        );
+        query.execute(&mut **transaction)
+        .await?;
+        Ok(revision)
+    }
+
+    /// 分页查询问题的修订记录，按版本号倒序
+    pub async fn query_revision_list(
+        pool: &MySqlPool,
+        question_code: &String,
+        current_pageno:i64,
+        page_size:i64,
+    )->Result<Page<QuestionRevision>, BusinessError>{
+        let total_records = sqlx::query_as::<_,(i64,)>(
+            "select count(1) as total_count from rc_qa_question_revision where `question_code` = ?",
+        ).bind(question_code)
+        .fetch_one(pool)
+        .await?.0;
+        let offset = (current_pageno-1)*page_size;
+        let revisions = sqlx::query_as::<_,QuestionRevision>(
+            "select * from rc_qa_question_revision where `question_code` = ? order by `revision` desc limit ? offset ?",
+        ).bind(question_code)
+        .bind(page_size)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+        Ok(Page::new(total_records, current_pageno, page_size, revisions))
+    }
+
+    /// 依据question_code和版本号查询修订记录
+    pub async fn find_revision(
+        pool: &MySqlPool,
+        question_code: &String,
+        revision: i32,
+    )->Result<Option<QuestionRevision>, BusinessError>{
+        let revision = sqlx::query_as::<_,QuestionRevision>(
+            "select * from rc_qa_question_revision where `question_code` = ? and `revision` = ?",
+        ).bind(question_code)
+        .bind(revision)
+        .fetch_optional(pool)
+        .await?;
+        Ok(revision)
+    }
+
+    /// 按快照恢复问题及其回答，回答列表恢复为快照中的回答
+    pub async fn restore_question(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        question_code: &String,
+        snapshot: &QuestionSnapshot,
+        update_time: chrono::NaiveDateTime,
+    )->Result<(), BusinessError>{
+        let query = sqlx::query!("update rc_qa_question set `sku_code` = ?,`product_code` = ?,`question_content` = ?,`status` = ?,`sort` = ?,`rank` = ?,`review_status` = ?,`update_time` = ? where `question_code` = ?",
+            snapshot.sku_code.clone().unwrap_or_else(|| "".to_string()),
+            snapshot.product_code,
+            snapshot.question_content,
+            snapshot.status,
+            snapshot.sort,
+            snapshot.rank,
+            snapshot.review_status,
+            update_time,
+            question_code
+        );
+        query.execute(&mut **transaction)
+        .await?;
+        //快照中仍存在的回答在原记录上恢复，保留id和投票计数
+        let current_answers = sqlx::query_as::<_,Answer>("select * from rc_qa_answer where `question_code` = ? for update")
+            .bind(question_code)
+            .fetch_all(&mut **transaction)
+            .await?;
+        let answers = snapshot.answers.iter()
+            .map(|answer|answer.into_db_answer(question_code.clone(), &current_answers, update_time))
+            .collect();
+        let changes = AnswerChanges::diff(&current_answers, answers);
+        QuestionDao::save_answer_changes(transaction, question_code, &changes).await?;
+        Ok(())
+    }
+
+    /// 查询商品下一个置顶的sort值
+    /// 逻辑：查询该商品当前最大sort值+1，查询时锁定该商品的有效问题，同一商品的并发置顶依次执行，不会得到相同的sort值
     pub async fn have_next_sort(
-        pool: &MySqlPool,      
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        product_code: &String,
     )->Result<i32, BusinessError>{
-        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select max(sort)+1 as max_sort from rc_qa_question where 1=1 and status=1 ");
-        let query = builder.build_query_as::<(i32,)>();
-        let result = query.fetch_one(pool)
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select cast(coalesce(max(sort),0)+1 as signed) as max_sort from rc_qa_question where status=1 and product_code = ");
+        builder.push_bind(product_code);
+        builder.push(" for update");
+        let query = builder.build_query_as::<(i64,)>();
+        let result = query.fetch_one(&mut **transaction)
             .await?;
-        Ok(result.0)
+        Ok(result.0 as i32)
+    }
+
+    /// 按给定顺序重写商品下置顶问题的sort值，返回sort值有变化的问题编号
+    /// question_codes中的问题依次排在最前，其它已置顶的问题保持原有相对顺序排在后面
+    pub async fn reorder_top_question(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        product_code: &String,
+        question_codes: &[String],
+    )->Result<Vec<String>, BusinessError>{
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select question_code, sort from rc_qa_question where status=1 and product_code = ");
+        builder.push_bind(product_code);
+        //已到期但还没有被重置的置顶不再保留
+        builder.push(" and ((sort > 0 and (top_expire_time is null or top_expire_time > ").push_bind(now_local()).push(")) or question_code in (");
+        let mut separated = builder.separated(",");
+        for question_code in question_codes{
+            separated.push_bind(question_code);
+        }
+        separated.push_unseparated(")) order by `sort` desc,`rank`,`create_time` desc,`id` for update");
+        let current_sorts: Vec<(String, i32)> = builder.build_query_as::<(String, i32)>()
+            .fetch_all(&mut **transaction)
+            .await?;
+        let current_question_codes: Vec<String> = current_sorts.iter()
+            .map(|(question_code, _)|question_code.clone())
+            .collect();
+
+        let missing_question_codes: Vec<&str> = question_codes.iter()
+            .filter(|question_code|!current_question_codes.contains(question_code))
+            .map(|question_code|question_code.as_str())
+            .collect();
+        if !missing_question_codes.is_empty(){
+            let mut parameters= HashMap::new();
+            parameters.insert("product_code".to_string(), product_code.clone());
+            parameters.insert("question_code".to_string(), missing_question_codes.join(","));
+            return Err(BusinessError::QuestionNotFound((None,Some(parameters))));
+        }
+
+        let mut changed_question_codes = vec![];
+        for (question_code, sort) in reorder_sorts(question_codes, &current_question_codes){
+            if current_sorts.contains(&(question_code.clone(), sort)){
+                continue;
+            }
+            QuestionDao::update_sort_by_question_code(transaction, &question_code, sort).await?;
+            changed_question_codes.push(question_code);
+        }
+        Ok(changed_question_codes)
+    }
+}
+
+/// 计算调整顺序后的sort值，排在最前的问题sort值最大，最后一个为1
+pub fn reorder_sorts(question_codes: &[String], current_question_codes: &[String])->Vec<(String, i32)>{
+    let ordered: Vec<&String> = question_codes.iter()
+        .chain(current_question_codes.iter().filter(|question_code|!question_codes.contains(question_code)))
+        .collect();
+    let total = ordered.len() as i32;
+    ordered.into_iter()
+        .enumerate()
+        .map(|(index, question_code)|(question_code.clone(), total - index as i32))
+        .collect()
+}
+
+/// 将回答记录按question_code分组，组内保持查询返回的顺序
+pub fn group_answers_by_question_code(answers: Vec<Answer>)->HashMap<String, Vec<Answer>>{
+    let mut grouped: HashMap<String, Vec<Answer>> = HashMap::new();
+    for answer in answers{
+        grouped.entry(answer.question_code.clone()).or_default().push(answer);
     }
+    grouped
 }
 
 #[cfg(test)]
@@ -578,4 +2076,154 @@ mod test{
             },
         }
     }
+
+    #[test]
+    fn test_reorder_sorts(){
+        let to_codes = |codes:&[&str]|codes.iter().map(|code|code.to_string()).collect::<Vec<String>>();
+        //c原本未置顶，a和b已置顶，d已置顶但不在列表中
+        let question_codes = to_codes(&["c","b","a"]);
+        let current_question_codes = to_codes(&["a","d","b","c"]);
+        let sorts = reorder_sorts(&question_codes, &current_question_codes);
+        assert_eq!(sorts, vec![
+            ("c".to_string(),4),
+            ("b".to_string(),3),
+            ("a".to_string(),2),
+            ("d".to_string(),1),
+        ]);
+    }
+
+    #[test]
+    fn test_group_answers_by_question_code(){
+        let answers = vec![
+            Answer::new("question_code_1".to_string(),"answer_1".to_string(),None,None),
+            Answer::new("question_code_2".to_string(),"answer_2".to_string(),None,None),
+            Answer::new("question_code_1".to_string(),"answer_3".to_string(),None,None),
+        ];
+        let grouped = group_answers_by_question_code(answers);
+        assert_eq!(grouped.len(),2);
+        let answers_1: Vec<&str> = grouped.get("question_code_1").unwrap().iter().map(|x|x.answer_content.as_str()).collect();
+        assert_eq!(answers_1,vec!["answer_1","answer_3"]);
+        assert_eq!(grouped.get("question_code_2").unwrap().len(),1);
+    }
+
+    //查询当前会话已执行的语句数
+    async fn session_questions(pool: &MySqlPool)->i64{
+        let row = sqlx::query_as::<_,(String,String)>("show session status like 'Questions'")
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        row.1.parse::<i64>().unwrap()
+    }
+
+    //查询一页问题及其回答所执行的语句数
+    async fn count_queries_for_page(pool: &MySqlPool,request: &RequestFindQuestionListForTrad,page_size:i64)->(i64,usize){
+        let before = session_questions(pool).await;
+        let page = QuestionDao::query_question_list(pool,request,1,page_size).await.unwrap();
+        let question_codes: Vec<String> = page.data.iter().map(|x|x.question_code.clone()).collect();
+        let answers = QuestionDao::query_answer_by_question_codes(pool,&question_codes).await.unwrap();
+        let after = session_questions(pool).await;
+        (after-before,answers.len())
+    }
+
+    //每页的查询次数应固定，与页内问题数量无关
+    #[tokio::test]
+    async fn test_query_answer_by_question_codes_constant_queries(){
+        init_log().await; // 日志初始化
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set in .env file");
+        //只使用一个连接，保证会话统计覆盖所有语句
+        let pool = sqlx::mysql::MySqlPoolOptions::new()
+            .max_connections(1)
+            .connect(&database_url)
+            .await
+            .expect("Cannot init the database pool");
+        let expected_value = prepare_data1(&pool).await.expect("prepare data error");
+        let expected_questions = expected_value.0;
+
+        let mut request = RequestFindQuestionListForTrad::new();
+        request.question_code=Some("question_code_%".to_string());
+        let (queries_small_page,answers_small_page) = count_queries_for_page(&pool,&request,5).await;
+        let (queries_large_page,answers_large_page) = count_queries_for_page(&pool,&request,50).await;
+
+        let question_codes = expected_questions.iter().map(|x|x.question_code.clone()).collect();
+        delete_test_data1(&pool, &question_codes).await;
+
+        assert_eq!(answers_small_page,5);
+        assert_eq!(answers_large_page,50);
+        assert_eq!(queries_small_page,queries_large_page);
+    }
+
+    //游标分页逐页读取的结果应与offset分页一致
+    #[tokio::test]
+    async fn test_query_question_list_by_cursor(){
+        init_log().await; // 日志初始化
+        let pool: sqlx::Pool<sqlx::MySql> = init_pool().await.expect("Cannot init the database pool");
+        let expected_value = prepare_data1(&pool).await.expect("prepare data error");
+        let expected_questions = expected_value.0;
+
+        let mut request = RequestFindQuestionListForTrad::new();
+        request.question_code=Some("question_code_%".to_string());
+        let page_size = 10;
+        let mut actual_questions = vec![];
+        let mut cursor: Option<QuestionCursor> = None;
+        let mut total_records = None;
+        loop{
+            let page = QuestionDao::query_question_list_by_cursor(&pool,&request,cursor.as_ref(),page_size,cursor.is_none()).await.unwrap();
+            if cursor.is_none(){
+                total_records = page.total_records;
+            }else{
+                assert_eq!(page.total_records,None);
+            }
+            actual_questions.extend(page.data);
+            match page.next_cursor{
+                Some(next_cursor) => cursor = Some(QuestionCursor::decode(&next_cursor).unwrap()),
+                None => break,
+            }
+        }
+        let offset_page = QuestionDao::query_question_list(&pool,&request,1,100).await.unwrap();
+
+        let question_codes = expected_questions.iter().map(|x|x.question_code.clone()).collect();
+        delete_test_data1(&pool, &question_codes).await;
+
+        assert_eq!(total_records,Some(55));
+        compare_question(&actual_questions,&offset_page.data);
+    }
+
+    //从回收站恢复时，删除问题之前单独失效的回答应保持失效
+    #[tokio::test]
+    async fn test_restore_disabled_question(){
+        init_log().await; // 日志初始化
+        let pool: sqlx::Pool<sqlx::MySql> = init_pool().await.expect("Cannot init the database pool");
+        let expected_value = prepare_data(&pool).await.expect("prepare data error");
+        let expected_question = expected_value.0;
+        let question_code = expected_question.question_code.clone();
+
+        let mut transaction = pool.begin().await.unwrap();
+        let answer = Answer::new(question_code.clone(),"disabled_answer_content".to_string(),None,None);
+        QuestionDao::insert_answer(&mut transaction, &answer).await.unwrap();
+        //单独失效的回答没有软删除批次
+        sqlx::query("update rc_qa_answer set `status`='0' where `question_code` = ? and `answer_content` = ?")
+            .bind(&question_code)
+            .bind("disabled_answer_content")
+            .execute(&mut *transaction)
+            .await
+            .unwrap();
+        QuestionDao::disabled_question_and_answer_by_question_code(&mut transaction, &question_code).await.unwrap();
+        transaction.commit().await.unwrap();
+
+        let mut transaction = pool.begin().await.unwrap();
+        let restore_result = QuestionDao::restore_disabled_question(&mut transaction, &question_code, now_local()).await;
+        transaction.commit().await.unwrap();
+        let question = QuestionDao::find_question_by_question_code(&pool, &question_code).await;
+        let answers = QuestionDao::query_answer_by_question_code(&pool, &question_code).await;
+
+        delete_test_data(&pool, &question_code).await;
+
+        assert_eq!(restore_result.unwrap().status, 0);
+        assert_eq!(question.unwrap().map(|question|question.status), Some(1));
+        let answer_status = |answer_content:&str|answers.as_ref().unwrap().iter()
+            .find(|answer|answer.answer_content == answer_content)
+            .map(|answer|(answer.status, answer.disable_batch.clone()));
+        assert_eq!(answer_status("answer_content"), Some((1, None)));
+        assert_eq!(answer_status("disabled_answer_content"), Some((0, None)));
+    }
 }
@@ -0,0 +1,160 @@
+use crate::model::db::qa::{DeliveryStatus, Notification, NotificationEvent, Page};
+use crate::model::request::operation::find_notification_list::FindNotificationList as RequestFindNotificationList;
+use crate::utils::error::BusinessError;
+use sqlx::MySqlPool;
+use sqlx::QueryBuilder;
+
+pub struct SubscriptionDao;
+impl SubscriptionDao{
+
+    /// 订阅问题，已取消的订阅重新生效
+    pub async fn subscribe(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        question_code: &String,
+        subscriber_id: &String,
+        now: chrono::NaiveDateTime,
+    )->Result<(), BusinessError>{
+        let query = sqlx::query!("insert into rc_qa_subscription (`question_code`,`subscriber_id`,`status`,`create_time`,`update_time`) values (?,?,1,?,?)
+            on duplicate key update `status` = 1,`update_time` = values(`update_time`)",
+            question_code,subscriber_id,now,now);
+        query.execute(&mut **transaction)
+        .await?;
+        Ok(())
+    }
+
+    /// 取消订阅，返回是否存在有效的订阅
+    pub async fn unsubscribe(
+        pool: &MySqlPool,
+        question_code: &String,
+        subscriber_id: &String,
+        now: chrono::NaiveDateTime,
+    )->Result<bool, BusinessError>{
+        let query = sqlx::query!("update rc_qa_subscription set `status` = 0,`update_time` = ? where `question_code` = ? and `subscriber_id` = ? and `status` = 1",
+            now,question_code,subscriber_id);
+        let result = query.execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 为问题的每个有效订阅生成一条待发送的通知，返回生成的通知数量
+    pub async fn enqueue_notification(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        question_code: &String,
+        event: NotificationEvent,
+        payload: &String,
+        now: chrono::NaiveDateTime,
+    )->Result<u64, BusinessError>{
+        let pending: i8 = DeliveryStatus::Pending.into();
+        let result = sqlx::query(
+            "insert into rc_qa_notification (`subscription_id`,`question_code`,`subscriber_id`,`event_type`,`payload`,`status`,`attempt_count`,`next_retry_time`,`create_time`,`update_time`)
+            select `id`,`question_code`,`subscriber_id`,?,?,?,0,?,?,? from rc_qa_subscription where `question_code` = ? and `status` = 1",
+        ).bind(event.as_str())
+        .bind(payload)
+        .bind(pending)
+        .bind(now)
+        .bind(now)
+        .bind(now)
+        .bind(question_code)
+        .execute(&mut **transaction)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 领取到期待发送的通知，以及领取已到期仍未记录结果的通知（发送中的实例可能已退出），先生成的先发送
+    /// 领取时锁定通知，已被其它实例锁定的跳过；领取后状态改为发送中，next_retry_time为领取的到期时间，并累加尝试次数
+    /// 领取后即可提交事务，发送在事务之外进行
+    pub async fn claim_due_notifications(
+        transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+        now: chrono::NaiveDateTime,
+        lease_until: chrono::NaiveDateTime,
+        limit: i64,
+    )->Result<Vec<Notification>, BusinessError>{
+        let sending: i8 = DeliveryStatus::Sending.into();
+        let mut notifications = sqlx::query_as::<_,Notification>(
+            "select * from rc_qa_notification where `status` in (?,?) and `next_retry_time` <= ? order by `id` limit ? for update skip locked",
+        ).bind::<i8>(DeliveryStatus::Pending.into())
+        .bind(sending)
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&mut **transaction)
+        .await?;
+        if notifications.is_empty(){
+            return Ok(notifications);
+        }
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("update rc_qa_notification set `status` = ");
+        builder.push_bind(sending);
+        builder.push(",`attempt_count` = `attempt_count` + 1,`next_retry_time` = ").push_bind(lease_until);
+        builder.push(",`update_time` = ").push_bind(now);
+        builder.push(" where `id` in (");
+        let mut separated = builder.separated(",");
+        for notification in &notifications{
+            separated.push_bind(notification.id);
+        }
+        separated.push_unseparated(")");
+        builder.build().execute(&mut **transaction).await?;
+        for notification in notifications.iter_mut(){
+            notification.status = sending;
+            notification.attempt_count += 1;
+            notification.next_retry_time = lease_until;
+        }
+        Ok(notifications)
+    }
+
+    /// 记录一次发送的结果：status为发送后的状态，发送失败时记录原因和下次发送时间
+    /// 只更新仍处于发送中的通知，每条通知单独更新，不持有其它通知的锁
+    pub async fn update_delivery(
+        pool: &MySqlPool,
+        id: i64,
+        status: DeliveryStatus,
+        next_retry_time: chrono::NaiveDateTime,
+        last_error: Option<&str>,
+        now: chrono::NaiveDateTime,
+    )->Result<(), BusinessError>{
+        let status: i8 = status.into();
+        let sending: i8 = DeliveryStatus::Sending.into();
+        sqlx::query("update rc_qa_notification set `status` = ?,`next_retry_time` = ?,`last_error` = ?,`update_time` = ? where `id` = ? and `status` = ?")
+            .bind(status)
+            .bind(next_retry_time)
+            .bind(last_error)
+            .bind(now)
+            .bind(id)
+            .bind(sending)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// 分页查询通知及其发送状态，最新的在前
+    pub async fn query_notification_list(
+        pool: &MySqlPool,
+        request_find_notification_list: &RequestFindNotificationList,
+    )->Result<Page<Notification>, BusinessError>{
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select count(1) as total_count from rc_qa_notification where 1=1 ");
+        Self::query_notification_list_condition(&mut builder, request_find_notification_list);
+        let total_records = builder.build_query_as::<(i64,)>().fetch_one(pool).await?.0;
+
+        let mut builder: QueryBuilder<'_, sqlx::MySql> = QueryBuilder::<sqlx::MySql>::new("select * from rc_qa_notification where 1=1 ");
+        Self::query_notification_list_condition(&mut builder, request_find_notification_list);
+        let current_pageno = request_find_notification_list.current_pageno;
+        let page_size = request_find_notification_list.page_size;
+        let offset = (current_pageno-1)*page_size;
+        builder.push(format!(" order by `id` desc limit {} offset {}",page_size,offset));
+        let notifications = builder.build_query_as::<Notification>().fetch_all(pool).await?;
+        Ok(Page::new(total_records, current_pageno, page_size, notifications))
+    }
+
+    fn query_notification_list_condition<'a>(
+        builder: &mut QueryBuilder<'a, sqlx::MySql>,
+        request_find_notification_list: &'a RequestFindNotificationList,
+    ){
+        if let Some(question_code) = &request_find_notification_list.question_code {
+            builder.push(" and `question_code` = ").push_bind(question_code);
+        }
+        if let Some(subscriber_id) = &request_find_notification_list.subscriber_id {
+            builder.push(" and `subscriber_id` = ").push_bind(subscriber_id);
+        }
+        if let Some(status) = request_find_notification_list.status {
+            builder.push(" and `status` = ").push_bind(status);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use tracing::{info, instrument};
+use axum::extract::Query;
+use axum::Json;
+use uuid::Uuid;
+use validator::Validate;
+use std::collections::HashMap;
+use crate::model::request::admin::cache::{
+    FindCacheKeyList as RequestFindCacheKeyList,
+    GetCacheEntry as RequestGetCacheEntry,
+    EvictCacheKey as RequestEvictCacheKey,
+    EvictCachePrefix as RequestEvictCachePrefix,
+    FlushCache as RequestFlushCache,
+    DEFAULT_CACHE_KEY_LIMIT,
+};
+use crate::model::response::admin::cache::{
+    CacheNamespace as ResponseCacheNamespace,
+    CacheKeyList as ResponseCacheKeyList,
+    CacheEntry as ResponseCacheEntry,
+};
+use crate::models::response_models::AppResponse;
+use crate::utils::cache::{self, CacheNamespace};
+use crate::utils::error::BusinessError;
+
+/// 查询全部缓存命名空间的配置和当前缓存项数量
+#[instrument(name = "find_cache_namespace_list", fields(request_id = %Uuid::new_v4()))]
+pub async fn find_cache_namespace_list()
+    -> Result<Json<AppResponse<Vec<ResponseCacheNamespace>>>, BusinessError> {
+    let response = cache::namespaces().into_iter()
+        .map(ResponseCacheNamespace::from_namespace)
+        .collect();
+    Ok(Json(AppResponse::success(response)))
+}
+
+/// 按前缀查询缓存命名空间中的key及其剩余有效时间
+#[instrument(name = "find_cache_key_list", skip(params),fields(request_id = %Uuid::new_v4()))]
+pub async fn find_cache_key_list(
+    Query(params): Query<RequestFindCacheKeyList>,
+    )
+    -> Result<Json<AppResponse<ResponseCacheKeyList>>, BusinessError> {
+    params.validate()?;
+    let namespace = find_namespace(&params.namespace)?;
+    let entries = namespace.entries(&params.prefix);
+    let matched_count = entries.len();
+    let keys = entries.into_iter()
+        .take(params.limit.unwrap_or(DEFAULT_CACHE_KEY_LIMIT))
+        .map(Into::into)
+        .collect();
+    Ok(Json(AppResponse::success(ResponseCacheKeyList{
+        namespace: namespace.name().to_string(),
+        entry_count: namespace.entry_count(),
+        matched_count,
+        keys,
+    })))
+}
+
+/// 查询单个缓存项的剩余有效时间和内容
+#[instrument(name = "get_cache_entry", skip(params),fields(request_id = %Uuid::new_v4()))]
+pub async fn get_cache_entry(
+    Query(params): Query<RequestGetCacheEntry>,
+    )
+    -> Result<Json<AppResponse<Option<ResponseCacheEntry>>>, BusinessError> {
+    params.validate()?;
+    let namespace = find_namespace(&params.namespace)?;
+    let entry = namespace.entry(&params.key).map(Into::into);
+    Ok(Json(AppResponse::success(entry)))
+}
+
+/// 使单个缓存项失效，返回缓存项是否存在
+#[instrument(name = "evict_cache_key", fields(request_id = %Uuid::new_v4()))]
+pub async fn evict_cache_key(
+    Json(request): Json<RequestEvictCacheKey>,
+)-> Result<Json<AppResponse<bool>>,BusinessError> {
+    request.validate()?;
+    let namespace = find_namespace(&request.namespace)?;
+    let evicted = namespace.invalidate_key(&request.key);
+    info!("清除缓存{}的key {}: {}", namespace.name(), request.key, evicted);
+    Ok(Json(AppResponse::success(evicted)))
+}
+
+/// 使key以前缀开头的缓存项失效，返回失效的数量
+#[instrument(name = "evict_cache_prefix", fields(request_id = %Uuid::new_v4()))]
+pub async fn evict_cache_prefix(
+    Json(request): Json<RequestEvictCachePrefix>,
+)-> Result<Json<AppResponse<usize>>,BusinessError> {
+    request.validate()?;
+    let namespace = find_namespace(&request.namespace)?;
+    let evicted = namespace.invalidate_prefix(&request.prefix);
+    info!("清除缓存{}中前缀为{}的{}个key", namespace.name(), request.prefix, evicted);
+    Ok(Json(AppResponse::success(evicted)))
+}
+
+/// 清空缓存命名空间，返回清空前的缓存项数量
+#[instrument(name = "flush_cache", fields(request_id = %Uuid::new_v4()))]
+pub async fn flush_cache(
+    Json(request): Json<RequestFlushCache>,
+)-> Result<Json<AppResponse<u64>>,BusinessError> {
+    request.validate()?;
+    let namespace = find_namespace(&request.namespace)?;
+    let entry_count = namespace.entry_count();
+    namespace.invalidate_all();
+    info!("清空缓存{}，共{}个key", namespace.name(), entry_count);
+    Ok(Json(AppResponse::success(entry_count)))
+}
+
+fn find_namespace(name: &str)->Result<&'static dyn CacheNamespace, BusinessError>{
+    cache::namespace(name).ok_or_else(||{
+        let names: Vec<&str> = cache::namespaces().iter().map(|namespace|namespace.name()).collect();
+        let mut parameters = HashMap::new();
+        parameters.insert("field".to_string(), "namespace".to_string());
+        parameters.insert("message".to_string(), format!("namespace应该是{}之一", names.join("、")));
+        BusinessError::InvalidParameter((None,Some(vec![parameters])))
+    })
+}
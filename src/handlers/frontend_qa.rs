@@ -1,48 +1,612 @@
 use tracing::instrument;
 use axum::Extension;
 use axum::extract::Query;
+use axum::http::{HeaderMap, header::ACCEPT_LANGUAGE};
 use axum::Json;
 
 use sqlx::mysql::MySqlPool;
 use uuid::Uuid;
-use crate::model::request::frontend::get_question_by_code::GetQuestionByCode as RequestGetQuestionByCode;
+use crate::model::request::frontend::get_question_by_code::{GetQuestionByCode as RequestGetQuestionByCode, AnswerSort, DEFAULT_REPLY_PAGE_SIZE};
+use crate::model::request::frontend::reply_answer::ReplyAnswer as RequestReplyAnswer;
+use crate::model::request::frontend::find_reply_list::FindReplyList as RequestFindReplyList;
+use crate::model::request::frontend::get_faq_snapshot::GetFaqSnapshot as RequestGetFaqSnapshot;
+use crate::model::request::frontend::subscribe_question::{SubscribeQuestion as RequestSubscribeQuestion, UnsubscribeQuestion as RequestUnsubscribeQuestion};
+use crate::utils::faq_snapshot::{self, SNAPSHOT_DIR};
+use axum::http::{StatusCode, header::{CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_NONE_MATCH}};
+use axum::response::{IntoResponse, Response};
+use crate::model::response::frontend::reply::ReplyNode;
+use crate::model::request::frontend::vote_answer::VoteAnswer as RequestVoteAnswer;
+use crate::handlers::operation_qa::{insert_new_question, invalidate_question_cache};
+use crate::utils::datetime::now_local;
+use crate::model::request::frontend::ask_question::AskQuestion as RequestAskQuestion;
+use crate::model::request::frontend::find_question_list::FindQuestionList as RequestFindQuestionList;
+use crate::model::request::frontend::search_question::SearchQuestion as RequestSearchQuestion;
+use crate::model::cache::qa::QuestionPage as CacheQuestionPage;
+use crate::models::response_models::PageResponse;
+use validator::Validate;
 use crate::model::response::frontend::get_question_by_code::GetQuestionByCode as ResponseGetQuestionByCode;
+use crate::model::response::frontend::search_question::SearchQuestion as ResponseSearchQuestion;
 use crate::model::cache::qa::Question as CacheQuestion;
+use crate::model::cache::qa::Reply as CacheReply;
+use crate::utils::highlight::split_keywords;
 
 use crate::dao::qa_dao::QuestionDao;
+use crate::dao::subscription_dao::SubscriptionDao;
 use crate::models::response_models::AppResponse;
-use crate::utils::cache::{CACHE,CacheType,Expiration};
+use crate::utils::cache::{QUESTION_CACHE, QUESTION_PAGE_CACHE, QUESTION_REDIRECT_CACHE, Expiration, key::{QuestionKey, QuestionListKey}};
 use crate::utils::error::BusinessError;
+use crate::utils::rate_limit::RateLimiter;
+use crate::utils::client_identity::ClientIdentity;
+use crate::utils::unsubscribe_token;
+use crate::model::db::qa::{AnswerStatus, Reply, ReviewStatus, Translations};
+use crate::utils::locale::Locale;
+use crate::utils::view_counter::QUESTION_VIEW_COUNTER;
+use crate::utils::single_flight::SingleFlight;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::env;
+use std::time::Duration;
 
+// 前台用户提问的限流：每个调用方（认证用户或客户端IP）在一个时间窗口内最多提问的次数
+static ASK_QUESTION_RATE_LIMITER: Lazy<RateLimiter> = Lazy::new(|| {
+    let max_requests = env::var("ASK_QUESTION_RATE_LIMIT").ok()
+        .and_then(|value|value.parse::<u32>().ok())
+        .unwrap_or(5);
+    let window_seconds = env::var("ASK_QUESTION_RATE_WINDOW_SECONDS").ok()
+        .and_then(|value|value.parse::<u64>().ok())
+        .unwrap_or(3600);
+    RateLimiter::new(max_requests, Duration::from_secs(window_seconds))
+});
 
-#[instrument(name = "get_question_by_code", skip(params),fields(request_id = %Uuid::new_v4()))]
+// 前台用户追问回复的限流：每个调用方（认证用户或客户端IP）在一个时间窗口内最多回复的次数
+static REPLY_RATE_LIMITER: Lazy<RateLimiter> = Lazy::new(|| {
+    let max_requests = env::var("REPLY_RATE_LIMIT").ok()
+        .and_then(|value|value.parse::<u32>().ok())
+        .unwrap_or(10);
+    let window_seconds = env::var("REPLY_RATE_WINDOW_SECONDS").ok()
+        .and_then(|value|value.parse::<u64>().ok())
+        .unwrap_or(3600);
+    RateLimiter::new(max_requests, Duration::from_secs(window_seconds))
+});
+
+// 追问回复允许的最大层级，直接回复回答的为第1层
+static REPLY_MAX_DEPTH: Lazy<i32> = Lazy::new(|| {
+    env::var("QA_REPLY_MAX_DEPTH").ok()
+        .and_then(|value|value.parse::<i32>().ok())
+        .filter(|value|*value >= 1)
+        .unwrap_or(3)
+});
+
+/// 合并同一个问题（同一个语言）缓存未命中时的并发查询
+static QUESTION_LOADER: Lazy<SingleFlight<QuestionKey, Result<Option<CacheQuestion>, BusinessError>>> = Lazy::new(SingleFlight::new);
+
+// 前台回答的默认排序方式
+static DEFAULT_ANSWER_SORT: Lazy<AnswerSort> = Lazy::new(|| {
+    env::var("FRONTEND_ANSWER_SORT").ok()
+        .and_then(|value|AnswerSort::from_env_value(&value))
+        .unwrap_or(AnswerSort::Default)
+});
+
+/// 前台依据问题编号查询问题及审核通过的有效回答，问题不存在、未审核通过或已删除时返回QuestionNotFound
+#[instrument(name = "get_question_by_code", skip(params, headers),fields(request_id = %Uuid::new_v4()))]
 pub async fn get_question_by_code(
     Extension(pool): Extension<MySqlPool>,
+    headers: HeaderMap,
     Query(params): Query<RequestGetQuestionByCode>,
     ) 
-    -> Result<Json<AppResponse<Option<ResponseGetQuestionByCode>>>, BusinessError> {
+    -> Result<Json<AppResponse<ResponseGetQuestionByCode>>, BusinessError> {
+    params.validate()?;
     let question_code = &params.question_code;
-    let key = format!("question_code:{}",question_code);
+    let answer_sort = params.answer_sort.unwrap_or(*DEFAULT_ANSWER_SORT);
+    let locale = request_locale(&headers);
+    let reply_page_size = params.reply_page_size.unwrap_or(DEFAULT_REPLY_PAGE_SIZE);
+    let mut cache_question_option = load_question(&pool, question_code, locale).await?;
+    //问题已被合并时返回保留的问题
+    if cache_question_option.is_none(){
+        if let Some(target_question_code) = find_question_redirect(&pool, question_code).await?{
+            cache_question_option = load_question(&pool, &target_question_code, locale).await?;
+        }
+    }
+    let Some(mut question) = ResponseGetQuestionByCode::from_cache(cache_question_option, reply_page_size) else {
+        let mut parameters= HashMap::new();
+        parameters.insert("questionCode".to_string(), question_code.clone());
+        return Err(BusinessError::QuestionNotFound((None,Some(parameters))));
+    };
+    question.sort_answers(answer_sort);
+    //浏览量只在内存中累加，由后台任务批量写入
+    QUESTION_VIEW_COUNTER.record(&question.question_code, now_local().date());
+    Ok(Json(AppResponse::success(question)))
+}
+
+/// 依据Accept-Language选择前台展示内容的首选语言
+fn request_locale(headers:&HeaderMap)->Locale{
+    Locale::from_accept_language(headers.get(ACCEPT_LANGUAGE).and_then(|value|value.to_str().ok()))
+}
+
+/// 查询一批问题及其回答在语言回退链上的翻译，首选语言为默认语言时不需要查询
+async fn load_translations(pool:&MySqlPool, question_codes:&[String], chain:&[Locale])->Result<Translations, BusinessError>{
+    let locales: Vec<Locale> = chain.iter().copied().filter(|locale|!locale.is_default()).collect();
+    if locales.is_empty(){
+        return Ok(Translations::default());
+    }
+    let question_translations = QuestionDao::query_question_translations(pool, question_codes, &locales).await?;
+    let answer_translations = QuestionDao::query_answer_translations(pool, question_codes, &locales).await?;
+    Ok(Translations::new(question_translations, answer_translations))
+}
+
+/// 依据question_code查询前台展示的问题及回答（按首选语言翻译，附带回答下的追问回复），优先从缓存中读取
+/// 问题不存在时同样缓存一小段时间，避免不存在的问题编号反复查询数据库；同一个问题的并发未命中只查询一次
+async fn load_question(pool:&MySqlPool, question_code:&String, locale:Locale)->Result<Option<CacheQuestion>, BusinessError>{
+    let key = QuestionKey::new(question_code, locale);
     //检查缓存是否存在
-    if let Some((_,CacheType::Question(question_option))) = CACHE.get(&key){
-        let question_option = ResponseGetQuestionByCode::from_cache(question_option);
+    if let Some(question_option) = QUESTION_CACHE.get(&key){
         tracing::trace!("Cache hit");
-        return Ok(Json(AppResponse::success(question_option)));
-    }else{
+        return Ok(question_option);
+    }
+    QUESTION_LOADER.run(&key, || async {
+        //等待期间其它请求可能已经写入缓存
+        if let Some(question_option) = QUESTION_CACHE.get(&key){
+            return Ok(question_option);
+        }
         //缓存不存在，从数据库中查询，并将查询结果存入缓存
         tracing::trace!("Cache miss");
-        let db_review_list = QuestionDao::query_answer_by_question_code(&pool, question_code).await?;
-        
-        if let Ok(db_question_option) = QuestionDao::find_question_by_question_code(&pool, question_code).await{
-            let db_question = db_question_option.unwrap();
-            let question_response = ResponseGetQuestionByCode::from_db(db_question.clone(),db_review_list.clone());
-            let cache_question = CacheQuestion::from_db(db_question, db_review_list);
-            let cache_type_with_question: CacheType = CacheType::Question(Some(cache_question));
-            CACHE.insert(key.clone(), (Expiration::AfterShortTime,cache_type_with_question));
-            return Ok(Json(AppResponse::success(Some(question_response))));
-        }else{
-            CACHE.insert(key.clone(), (Expiration::AfterShortTime,CacheType::Question(None)));            
-            return Ok(Json(AppResponse::success(None)));
-        }
-    }
-}
\ No newline at end of file
+        let question_option = load_question_from_db(pool, question_code, locale).await?;
+        QUESTION_CACHE.insert(key.clone(), question_option.clone());
+        Ok(question_option)
+    }).await
+}
+
+/// 从数据库查询前台展示的问题及回答，前台只展示审核通过且有效的问题和回答
+async fn load_question_from_db(pool:&MySqlPool, question_code:&String, locale:Locale)->Result<Option<CacheQuestion>, BusinessError>{
+    let Some(mut db_question) = QuestionDao::find_approved_question_by_question_code(pool, question_code).await? else {
+        return Ok(None);
+    };
+    let mut db_review_list = QuestionDao::query_approved_answer_by_question_code(pool, question_code).await?;
+    let chain = locale.chain();
+    load_translations(pool, &[question_code.clone()], &chain).await?
+        .apply(&mut db_question, &mut db_review_list, &chain);
+    let answer_ids: Vec<i64> = db_review_list.iter().filter_map(|answer|answer.id).collect();
+    let mut replies_by_answer_id = QuestionDao::query_approved_reply_by_answer_ids(pool, &answer_ids).await?;
+    let mut cache_question = CacheQuestion::from_db(db_question, db_review_list);
+    for answer in cache_question.answer_list.iter_mut(){
+        let replies = replies_by_answer_id.remove(&answer.id).unwrap_or_default();
+        answer.replies = Some(replies.into_iter().map(CacheReply::from_db).collect());
+    }
+    Ok(Some(cache_question))
+}
+
+/// 查询被合并问题重定向到的问题编号，重定向不会变化，存在时长期缓存
+async fn find_question_redirect(pool:&MySqlPool, question_code:&String)->Result<Option<String>, BusinessError>{
+    if let Some(target_question_code) = QUESTION_REDIRECT_CACHE.get(question_code){
+        return Ok(target_question_code);
+    }
+    let target_question_code = QuestionDao::find_question_redirect(pool, question_code).await?;
+    let expiration = if target_question_code.is_some() { Expiration::AfterLongTime } else { Expiration::AfterShortTime };
+    QUESTION_REDIRECT_CACHE.insert_with_expiration(question_code.clone(), target_question_code.clone(), expiration);
+    Ok(target_question_code)
+}
+
+/// 前台用户提问，提交后进入待审核状态，审核通过后才会在前台展示
+#[instrument(name = "ask_question", fields(request_id = %Uuid::new_v4()))]
+pub async fn ask_question(
+    Extension(pool): Extension<MySqlPool>,
+    identity: ClientIdentity,
+    Json(request): Json<RequestAskQuestion>,
+)-> Result<Json<AppResponse<String>>,BusinessError> {
+    request.custom_validate().await?;
+    //请求体中的用户id可以随意填写，限流按认证用户或客户端IP
+    let rate_limit_key = identity.rate_limit_key();
+    if !ASK_QUESTION_RATE_LIMITER.try_acquire(&rate_limit_key) {
+        let mut parameters= HashMap::new();
+        parameters.insert("client".to_string(), rate_limit_key);
+        return Err(BusinessError::TooManyRequests((None,Some(parameters))));
+    }
+
+    //与运营端新建问题共用入库逻辑（敏感词处理、标签和修订记录），新问题为待审核状态
+    let mut create_question = request.into_create_question();
+    // 开始一个事务
+    let mut transaction = pool.begin().await?;
+    let (_, question) = insert_new_question(&mut transaction, &mut create_question).await?;
+    //登录的提问人自动订阅自己的问题，问题有回答时收到通知；请求体中的用户id可以随意填写，不用于订阅
+    if let Some(user_id) = &identity.user_id {
+        SubscriptionDao::subscribe(&mut transaction, &question.question_code, user_id, now_local()).await?;
+    }
+    // 提交事务
+    transaction.commit().await?;
+
+    Ok(Json(AppResponse::success(question.question_code)))
+}
+
+/// 前台登录用户订阅问题，问题有新回答或被更新时收到通知，重复订阅不报错
+#[instrument(name = "subscribe_question", fields(request_id = %Uuid::new_v4()))]
+pub async fn subscribe_question(
+    Extension(pool): Extension<MySqlPool>,
+    identity: ClientIdentity,
+    Json(request): Json<RequestSubscribeQuestion>,
+)-> Result<Json<AppResponse<bool>>,BusinessError> {
+    request.validate()?;
+    let Some(subscriber_id) = identity.user_id else {
+        let mut parameters= HashMap::new();
+        parameters.insert("message".to_string(), "订阅问题需要登录".to_string());
+        return Err(BusinessError::Forbidden((None,Some(parameters))));
+    };
+    if QuestionDao::find_question_by_question_code(&pool, &request.question_code).await?.is_none() {
+        let mut parameters= HashMap::new();
+        parameters.insert("questionCode".to_string(), request.question_code.clone());
+        return Err(BusinessError::QuestionNotFound((None,Some(parameters))));
+    }
+    // 开始一个事务
+    let mut transaction = pool.begin().await?;
+    SubscriptionDao::subscribe(&mut transaction, &request.question_code, &subscriber_id, now_local()).await?;
+    // 提交事务
+    transaction.commit().await?;
+
+    Ok(Json(AppResponse::success(true)))
+}
+
+/// 前台用户取消订阅问题，返回是否取消了有效的订阅
+/// 带有通知中的取消订阅令牌时取消令牌对应用户的订阅，否则取消登录用户自己的订阅
+#[instrument(name = "unsubscribe_question", fields(request_id = %Uuid::new_v4()))]
+pub async fn unsubscribe_question(
+    Extension(pool): Extension<MySqlPool>,
+    identity: ClientIdentity,
+    Json(request): Json<RequestUnsubscribeQuestion>,
+)-> Result<Json<AppResponse<bool>>,BusinessError> {
+    request.validate()?;
+    let subscriber_id = match (&request.subscriber_id, &request.unsubscribe_token) {
+        (Some(subscriber_id), Some(token)) if unsubscribe_token::verify(&request.question_code, subscriber_id, token) => Some(subscriber_id.clone()),
+        (_, Some(_)) => None,
+        (_, None) => identity.user_id,
+    };
+    let Some(subscriber_id) = subscriber_id else {
+        let mut parameters= HashMap::new();
+        parameters.insert("message".to_string(), "取消订阅需要登录或提供有效的取消订阅令牌".to_string());
+        return Err(BusinessError::Forbidden((None,Some(parameters))));
+    };
+    let unsubscribed = SubscriptionDao::unsubscribe(&pool, &request.question_code, &subscriber_id, now_local()).await?;
+    Ok(Json(AppResponse::success(unsubscribed)))
+}
+
+/// 前台用户针对回答追问或回复其它追问，提交后进入待审核状态，审核通过后才会在前台展示，返回回复的id
+#[instrument(name = "reply_answer", fields(request_id = %Uuid::new_v4()))]
+pub async fn reply_answer(
+    Extension(pool): Extension<MySqlPool>,
+    identity: ClientIdentity,
+    Json(request): Json<RequestReplyAnswer>,
+)-> Result<Json<AppResponse<u64>>,BusinessError> {
+    request.custom_validate()?;
+    //请求体中的用户id可以随意填写，限流按认证用户或客户端IP
+    let rate_limit_key = identity.rate_limit_key();
+    if !REPLY_RATE_LIMITER.try_acquire(&rate_limit_key) {
+        let mut parameters= HashMap::new();
+        parameters.insert("client".to_string(), rate_limit_key);
+        return Err(BusinessError::TooManyRequests((None,Some(parameters))));
+    }
+
+    // 开始一个事务
+    let mut transaction = pool.begin().await?;
+    let Some(question_code) = QuestionDao::find_approved_answer_question_code(&mut transaction, request.answer_id).await? else {
+        let mut parameters= HashMap::new();
+        parameters.insert("answerId".to_string(), request.answer_id.to_string());
+        return Err(BusinessError::AnswerNotFound((None,Some(parameters))));
+    };
+    //只能回复同一回答下前台展示的回复
+    let parent = match request.parent_id {
+        Some(parent_id) => {
+            let active: i8 = AnswerStatus::Active.into();
+            let approved: i8 = ReviewStatus::Approved.into();
+            let parent = QuestionDao::find_reply(&mut transaction, parent_id).await?
+                .filter(|parent|parent.answer_id == request.answer_id && parent.status == active && parent.review_status == approved);
+            match parent {
+                Some(parent) => Some(parent),
+                None => {
+                    let mut parameters= HashMap::new();
+                    parameters.insert("parentId".to_string(), parent_id.to_string());
+                    return Err(BusinessError::ReplyNotFound((None,Some(parameters))));
+                }
+            }
+        },
+        None => None,
+    };
+    let reply = Reply::new(
+        request.answer_id,
+        question_code,
+        parent.as_ref(),
+        request.normalized_reply_content(),
+        Some(request.create_user_id.clone()),
+        Some(request.creator_name.clone()).filter(|creator_name|!creator_name.is_empty()),
+    );
+    if reply.depth > *REPLY_MAX_DEPTH {
+        let mut parameters= HashMap::new();
+        parameters.insert("field".to_string(), "parentId".to_string());
+        parameters.insert("message".to_string(), format!("回复层级不能超过{}层", *REPLY_MAX_DEPTH));
+        return Err(BusinessError::InvalidParameter((None,Some(vec![parameters]))));
+    }
+    let id = QuestionDao::insert_reply(&mut transaction, &reply).await?;
+    // 提交事务
+    transaction.commit().await?;
+
+    Ok(Json(AppResponse::success(id)))
+}
+
+/// 前台分页查询回答下审核通过的追问回复，按一级回复分页
+#[instrument(name = "find_reply_list", skip(params),fields(request_id = %Uuid::new_v4()))]
+pub async fn find_reply_list(
+    Extension(pool): Extension<MySqlPool>,
+    Query(params): Query<RequestFindReplyList>,
+    )
+    -> Result<Json<AppResponse<PageResponse<ReplyNode>>>, BusinessError> {
+    params.validate()?;
+    if QuestionDao::find_approved_answer(&pool, params.answer_id).await?.is_none() {
+        let mut parameters= HashMap::new();
+        parameters.insert("answerId".to_string(), params.answer_id.to_string());
+        return Err(BusinessError::AnswerNotFound((None,Some(parameters))));
+    }
+    //一级回复在数据库中分页，只查询这一页一级回复的下级回复
+    let root_page = QuestionDao::query_approved_root_reply_page(&pool, params.answer_id, params.current_pageno, params.page_size).await?;
+    let root_ids: Vec<i64> = root_page.data.iter().filter_map(|reply|reply.id).collect();
+    let descendants = QuestionDao::query_approved_reply_descendants(&pool, &root_ids).await?;
+    let replies = root_page.data.into_iter()
+        .chain(descendants)
+        .map(CacheReply::from_db)
+        .collect();
+    Ok(Json(AppResponse::success(ReplyNode::page_of_roots(replies, root_page.total_records, params.current_pageno, params.page_size))))
+}
+
+/// 获取商品的FAQ静态快照（由后台任务或运营接口生成），ETag为快照内容的哈希值
+/// 请求头If-None-Match与ETag相同时返回304，客户端可以继续使用已有的快照
+#[instrument(name = "get_faq_snapshot", skip(params, headers),fields(request_id = %Uuid::new_v4()))]
+pub async fn get_faq_snapshot(
+    headers: HeaderMap,
+    Query(params): Query<RequestGetFaqSnapshot>,
+    )
+    -> Result<Response, BusinessError> {
+    params.validate()?;
+    let snapshot_not_found = ||{
+        let mut parameters= HashMap::new();
+        parameters.insert("productCode".to_string(), params.product_code.clone());
+        BusinessError::SnapshotNotFound((None,Some(parameters)))
+    };
+    let Some(file) = faq_snapshot::snapshot_file(&SNAPSHOT_DIR, &params.product_code) else {
+        return Err(snapshot_not_found());
+    };
+    //ETag取自版本信息文件，快照未变化时不读取快照文件
+    let Some(meta) = faq_snapshot::read_meta(&file).await? else {
+        return Err(snapshot_not_found());
+    };
+    let etag = faq_snapshot::etag(&meta.content_hash);
+    let not_modified = headers.get(IF_NONE_MATCH)
+        .and_then(|value|value.to_str().ok())
+        .map(|value|faq_snapshot::if_none_match(value, &etag))
+        .unwrap_or(false);
+    //快照随时可能重新生成，客户端每次使用前都需要验证ETag
+    let response_headers = [
+        (ETAG, etag),
+        (CACHE_CONTROL, "no-cache".to_string()),
+    ];
+    if not_modified {
+        return Ok((StatusCode::NOT_MODIFIED, response_headers).into_response());
+    }
+    let Some(snapshot) = faq_snapshot::read(&file).await? else {
+        return Err(snapshot_not_found());
+    };
+    Ok((response_headers, [(CONTENT_TYPE, "application/json".to_string())], snapshot).into_response())
+}
+
+/// 前台用户对回答投票，票数变化后清除回答所属问题的缓存
+#[instrument(name = "vote_answer", fields(request_id = %Uuid::new_v4()))]
+pub async fn vote_answer(
+    Extension(pool): Extension<MySqlPool>,
+    identity: ClientIdentity,
+    Json(request): Json<RequestVoteAnswer>,
+)-> Result<Json<AppResponse<bool>>,BusinessError> {
+    request.validate()?;
+    //按认证用户或客户端IP去重，请求体中的用户id可以随意填写
+    let Some(voter) = identity.principal() else {
+        let mut parameters= HashMap::new();
+        parameters.insert("message".to_string(), "无法识别投票用户".to_string());
+        return Err(BusinessError::Forbidden((None,Some(parameters))));
+    };
+    // 开始一个事务
+    let mut transaction = pool.begin().await?;
+    let question_code = QuestionDao::vote_answer(&mut transaction, request.answer_id, &voter, request.vote, now_local()).await?;
+    // 提交事务
+    transaction.commit().await?;
+
+    match question_code {
+        Some((question_code, product_code)) => {
+            invalidate_question_cache(&question_code, &product_code);
+            Ok(Json(AppResponse::success(true)))
+        },
+        None => {
+            let mut parameters= HashMap::new();
+            parameters.insert("answerId".to_string(), request.answer_id.to_string());
+            Err(BusinessError::AnswerNotFound((None,Some(parameters))))
+        }
+    }
+}
+
+/// 前台按商品（可选按SKU、标签）分页查询问题列表，只展示审核通过的问题和回答
+/// 列表按页和语言缓存，问题变更时按商品清除
+#[instrument(name = "find_question_list", skip(params, headers),fields(request_id = %Uuid::new_v4()))]
+pub async fn find_question_list(
+    Extension(pool): Extension<MySqlPool>,
+    headers: HeaderMap,
+    Query(params): Query<RequestFindQuestionList>,
+    )
+    -> Result<Json<AppResponse<PageResponse<ResponseGetQuestionByCode>>>, BusinessError> {
+    params.validate()?;
+    let locale = request_locale(&headers);
+    let key = QuestionListKey{
+        product_code: params.product_code.clone(),
+        sku_code: params.sku_code.clone(),
+        tag: params.tag.clone(),
+        locale,
+        current_pageno: params.current_pageno,
+        page_size: params.page_size,
+    };
+    if let Some(question_page) = QUESTION_PAGE_CACHE.get(&key){
+        tracing::trace!("Cache hit");
+        return Ok(Json(AppResponse::success(page_response_from_cache(question_page))));
+    }
+    tracing::trace!("Cache miss");
+    let page = QuestionDao::query_approved_question_list_by_product(&pool, &params.product_code, params.sku_code.as_ref(), params.tag.as_ref(), params.current_pageno, params.page_size).await?;
+    let question_codes: Vec<String> = page.data.iter()
+        .map(|question|question.question_code.clone())
+        .collect();
+    let mut answers_by_question_code = QuestionDao::query_approved_answer_by_question_codes(&pool, &question_codes).await?;
+    let chain = locale.chain();
+    let translations = load_translations(&pool, &question_codes, &chain).await?;
+    let list = page.data.into_iter()
+        .map(|mut question|{
+            let mut answers = answers_by_question_code.remove(&question.question_code).unwrap_or_default();
+            translations.apply(&mut question, &mut answers, &chain);
+            CacheQuestion::from_db(question, answers)
+        })
+        .collect();
+    let question_page = CacheQuestionPage{
+        total_records:page.total_records,
+        current_pageno:page.current_pageno,
+        page_size:page.page_size,
+        total_pages:page.total_pages,
+        list,
+    };
+    QUESTION_PAGE_CACHE.insert(key, question_page.clone());
+    Ok(Json(AppResponse::success(page_response_from_cache(question_page))))
+}
+
+/// 前台在商品范围内全文检索问题，关键词组合不可枚举，检索结果不做缓存
+#[instrument(name = "search_question", skip(params),fields(request_id = %Uuid::new_v4()))]
+pub async fn search_question(
+    Extension(pool): Extension<MySqlPool>,
+    Query(params): Query<RequestSearchQuestion>,
+    )
+    -> Result<Json<AppResponse<PageResponse<ResponseSearchQuestion>>>, BusinessError> {
+    params.validate()?;
+    let keyword = params.keyword.trim().to_string();
+    let page = QuestionDao::search_approved_question(&pool, &params.product_code, &keyword, params.current_pageno, params.page_size).await?;
+    let question_codes: Vec<String> = page.data.iter()
+        .map(|hit|hit.question.question_code.clone())
+        .collect();
+    let mut answers_by_question_code = QuestionDao::query_approved_answer_by_question_codes(&pool, &question_codes).await?;
+    let keywords = split_keywords(&keyword);
+    let list = page.data.into_iter()
+        .map(|hit|{
+            let answers = answers_by_question_code.remove(&hit.question.question_code).unwrap_or_default();
+            ResponseSearchQuestion::from_db(hit, answers, &keywords)
+        })
+        .collect();
+    Ok(Json(AppResponse::success(PageResponse::new(
+        page.total_records,
+        page.current_pageno,
+        page.page_size,
+        page.total_pages,
+        Some(list),
+    ))))
+}
+
+fn page_response_from_cache(question_page:CacheQuestionPage)->PageResponse<ResponseGetQuestionByCode>{
+    let list = question_page.list.into_iter()
+        .map(|question|ResponseGetQuestionByCode::from_cache_question(question, DEFAULT_REPLY_PAGE_SIZE))
+        .collect();
+    PageResponse::new(
+        question_page.total_records,
+        question_page.current_pageno,
+        question_page.page_size,
+        question_page.total_pages,
+        Some(list),
+    )
+}
+
+/// 启动后台任务，定期将内存中累加的问题浏览量批量写入数据库
+/// 间隔由环境变量QA_VIEW_FLUSH_INTERVAL_SECONDS指定，默认60秒
+pub fn start_question_view_flusher(pool: MySqlPool){
+    let interval_seconds = env::var("QA_VIEW_FLUSH_INTERVAL_SECONDS").ok()
+        .and_then(|value|value.parse::<u64>().ok())
+        .unwrap_or(60);
+    tokio::spawn(
+        async move{
+            loop{
+                tokio::time::sleep(Duration::from_secs(interval_seconds)).await;
+                if let Err(error) = flush_question_views(&pool).await {
+                    tracing::error!("写入问题浏览量失败: {:?}", error);
+                }
+            }
+        }
+    );
+}
+
+// 每条insert语句写入的浏览量条数上限
+const VIEW_FLUSH_BATCH_SIZE: usize = 500;
+
+/// 将内存中累加的问题浏览量写入数据库，写入失败时放回缓冲区等待下次写入，返回写入的条数
+pub async fn flush_question_views(pool: &MySqlPool)->Result<usize, BusinessError>{
+    let views = QUESTION_VIEW_COUNTER.take();
+    if views.is_empty(){
+        return Ok(0);
+    }
+    let result = async {
+        // 开始一个事务
+        let mut transaction = pool.begin().await?;
+        for batch in views.chunks(VIEW_FLUSH_BATCH_SIZE){
+            QuestionDao::add_question_views(&mut transaction, batch, now_local()).await?;
+        }
+        // 提交事务
+        transaction.commit().await?;
+        Ok::<(), BusinessError>(())
+    }.await;
+    match result {
+        Ok(()) => {
+            tracing::debug!("已写入{}条问题浏览量", views.len());
+            Ok(views.len())
+        },
+        Err(error) => {
+            QUESTION_VIEW_COUNTER.restore(views);
+            Err(error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    //连接在第一次使用时才建立，缓存命中时不会访问数据库
+    fn lazy_pool()->MySqlPool{
+        MySqlPool::connect_lazy("mysql://root@127.0.0.1:3306/unused").unwrap()
+    }
+
+    fn get_question_params(question_code:&str)->RequestGetQuestionByCode{
+        RequestGetQuestionByCode{
+            question_code: question_code.to_string(),
+            answer_sort: None,
+            reply_page_size: None,
+        }
+    }
+
+    //缓存中的不存在记录直接返回，不查询数据库
+    #[tokio::test]
+    async fn test_load_question_cached_negative_entry(){
+        let question_code = "test_load_question_cached_negative_entry".to_string();
+        let locale = request_locale(&HeaderMap::new());
+        QUESTION_CACHE.insert(QuestionKey::new(&question_code, locale), None);
+        let question = load_question(&lazy_pool(), &question_code, locale).await.unwrap();
+        assert!(question.is_none());
+    }
+
+    //问题不存在且没有被合并时返回QuestionNotFound
+    #[tokio::test]
+    async fn test_get_question_by_code_not_found(){
+        let question_code = "test_get_question_by_code_not_found".to_string();
+        let locale = request_locale(&HeaderMap::new());
+        QUESTION_CACHE.insert(QuestionKey::new(&question_code, locale), None);
+        QUESTION_REDIRECT_CACHE.insert_with_expiration(question_code.clone(), None, Expiration::AfterShortTime);
+        let result = get_question_by_code(Extension(lazy_pool()), HeaderMap::new(), Query(get_question_params(&question_code))).await;
+        match result {
+            Err(BusinessError::QuestionNotFound((_, Some(parameters)))) => {
+                assert_eq!(parameters.get("questionCode"), Some(&question_code));
+            },
+            _ => panic!("错误类型不匹配"),
+        }
+    }
+}
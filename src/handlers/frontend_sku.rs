@@ -8,7 +8,7 @@ use crate::model::response::frontend::find_sku::FindSku as ResponseFrontendFindS
 
 use crate::models::response_models::AppResponse;
 use crate::dao::sku_dao::SkuDao;
-use crate::utils::cache::{CACHE,CacheType,Expiration};
+use crate::utils::cache::SKU_CACHE;
 use crate::utils::error::BusinessError;
 
 #[instrument(name = "find_sku", fields(request_id = %Uuid::new_v4()))]
@@ -17,9 +17,8 @@ pub async fn find_sku(
     Json(request): Json<RequestFrontendFindSku>,
 )-> Result<Json<AppResponse<Option<ResponseFrontendFindSku>>>,BusinessError> {
     let sku_code = &request.sku_code;
-    let key = format!("sku:{}",sku_code);
 
-    if let Some((_,CacheType::Sku(sku_option))) = CACHE.get(&key){
+    if let Some(sku_option) = SKU_CACHE.get(sku_code){
         let sku_option = ResponseFrontendFindSku::from_db_sku(sku_option);
         tracing::trace!("Cache hit");
         return Ok(Json(AppResponse::success(sku_option)));        
@@ -27,11 +26,10 @@ pub async fn find_sku(
         tracing::trace!("Cache miss");
         if let Ok(sku_option) = SkuDao::find_sku(&pool, sku_code).await{
             let sku_response: Option<ResponseFrontendFindSku> = ResponseFrontendFindSku::from_db_sku(sku_option.clone());
-            let cache_type_with_sku: CacheType = CacheType::Sku(sku_option);
-            CACHE.insert(key.clone(), (Expiration::AfterShortTime,cache_type_with_sku));
+            SKU_CACHE.insert(sku_code.clone(), sku_option);
             return Ok(Json(AppResponse::success(sku_response)));                    
         }else{
-            CACHE.insert(key.clone(), (Expiration::AfterShortTime,CacheType::Sku(None)));   
+            SKU_CACHE.insert(sku_code.clone(), None);
             return Ok(Json(AppResponse::success(None)));
         }
     }
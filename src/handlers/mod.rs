@@ -6,4 +6,5 @@ pub mod frontend_sku;
 pub mod client_sku;
 pub mod operation_qa;
 pub mod frontend_qa;
-pub mod divide0;
\ No newline at end of file
+pub mod divide0;
+pub mod admin_cache;
\ No newline at end of file
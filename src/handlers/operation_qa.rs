@@ -5,66 +5,277 @@ use axum_extra::TypedHeader;
 use headers::UserAgent;
 use sqlx::mysql::MySqlPool;
 use validator::Validate;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::env;
+use std::time::Duration;
 use axum::{
     extract::Query,
     Json
 };
 use uuid::Uuid;
 use crate::model::request::operation::{
-    create_question::CreateQuestion as RequestCreateQuestion,
+    create_question::{CreateQuestion as RequestCreateQuestion, normalize_tags},
     update_question::UpdateQuestion as RequestUpdateQuestion,
     find_question_list_for_trad::FindQuestionListForTrad as RequestFindQuestionListForTrad,
+    find_tag_usage::FindTagUsage as RequestFindTagUsage,
+    save_question_translation::SaveQuestionTranslation as RequestSaveQuestionTranslation,
+    find_question_translation::FindQuestionTranslation as RequestFindQuestionTranslation,
+    delete_question_translation::DeleteQuestionTranslation as RequestDeleteQuestionTranslation,
     delete_question::DeleteQuestion as RequestDeleteQuestion,
     top_question::TopQuestion as RequestTopQuestion,
-    cancel_top_question::CancelTopQuestion as RequestCancelTopQuestion
+    cancel_top_question::CancelTopQuestion as RequestCancelTopQuestion,
+    find_review_queue::FindReviewQueue as RequestFindReviewQueue,
+    review_qa::{ReviewQa as RequestReviewQa, ReviewTargetType},
+    find_question_revision_list::FindQuestionRevisionList as RequestFindQuestionRevisionList,
+    diff_question_revision::DiffQuestionRevision as RequestDiffQuestionRevision,
+    restore_question_revision::RestoreQuestionRevision as RequestRestoreQuestionRevision,
+    find_recycle_bin::FindRecycleBin as RequestFindRecycleBin,
+    reorder_top_question::ReorderTopQuestion as RequestReorderTopQuestion,
+    merge_question::MergeQuestion as RequestMergeQuestion,
+    restore_question::RestoreQuestion as RequestRestoreQuestion,
+    build_faq_snapshot::BuildFaqSnapshot as RequestBuildFaqSnapshot,
+    find_notification_list::FindNotificationList as RequestFindNotificationList,
+    import_question::{parse_question_csv, UTF8_BOM},
+    qa_report::{
+        FindTopViewedQuestion as RequestFindTopViewedQuestion,
+        FindUnansweredQuestion as RequestFindUnansweredQuestion,
+        FindAnswerLatency as RequestFindAnswerLatency,
+        DEFAULT_TOP_VIEWED_LIMIT,
+    },
+};
+use crate::model::response::operation::recycle_bin::RecycleBinQuestion as ResponseRecycleBinQuestion;
+use crate::model::response::operation::notification::Notification as ResponseNotification;
+use crate::utils::notifier::{self, NotificationMessage};
+use crate::utils::unsubscribe_token;
+use crate::model::response::operation::tag_usage::TagUsage as ResponseTagUsage;
+use crate::model::response::operation::import_question::{ImportQuestion as ResponseImportQuestion, ImportRowError};
+use crate::model::response::operation::export_question::{csv_header, csv_row, write_csv};
+use axum::body::{Body, Bytes};
+use axum::extract::multipart::{Multipart, MultipartError};
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::response::{IntoResponse, Response};
+use futures_util::{stream, StreamExt, TryStreamExt};
+use crate::model::response::operation::faq_snapshot::{FaqSnapshot, BuildFaqSnapshot as ResponseBuildFaqSnapshot, FAQ_SNAPSHOT_FORMAT_VERSION};
+use crate::model::response::frontend::get_question_by_code::GetQuestionByCode as ResponseGetQuestionByCode;
+use crate::utils::faq_snapshot::{self, SNAPSHOT_DIR};
+use crate::model::response::operation::qa_report::{TopViewedQuestion as ResponseTopViewedQuestion, AnswerLatency as ResponseAnswerLatency};
+use crate::model::response::operation::question_translation::QuestionTranslation as ResponseQuestionTranslation;
+use crate::model::response::operation::create_question::{CreateQuestion as ResponseCreateQuestion, DuplicateQuestion, DUPLICATE_CANDIDATE_LIMIT};
+use crate::model::response::operation::question_revision::{
+    QuestionRevision as ResponseQuestionRevision,
+    DiffQuestionRevision as ResponseDiffQuestionRevision,
 };
 use crate::model::response::operation::find_question_list_for_trad::Question;
+use crate::model::db::qa::{Question as DbQuestion, AnswerChanges, NotificationEvent, DeliveryStatus, QuestionCursor, ReviewLog, ReviewStatus, RevisionAction, QuestionRevision as DbQuestionRevision};
+use crate::model::response::operation::find_review_queue::ReviewItem as ResponseReviewItem;
 use crate::model::response::operation:: find_question_list_for_trad::FindQuestionListForTrad as ResponseFindQuestionListForTrad;
 
 use crate::utils::datetime::now_local;
 use crate::utils::error::BusinessError;
 use crate::dao::qa_dao::QuestionDao;
-use crate::models::response_models::AppResponse;
+use crate::dao::subscription_dao::SubscriptionDao;
+use crate::models::response_models::{AppResponse, PageResponse};
+use crate::utils::cache::{QUESTION_CACHE, QUESTION_PAGE_CACHE, QUESTION_REDIRECT_CACHE, CacheNamespace, key::QuestionKey};
+use crate::utils::locale::Locale;
 
 #[instrument(name = "create_question", fields(request_id = %Uuid::new_v4()))]
 pub async fn create_question(
     Extension(pool): Extension<MySqlPool>,
     TypedHeader(headers): TypedHeader<UserAgent>,
-    Json(request): Json<RequestCreateQuestion>,
-)-> Result<Json<AppResponse<String>>,BusinessError> {
+    Json(mut request): Json<RequestCreateQuestion>,
+)-> Result<Json<AppResponse<ResponseCreateQuestion>>,BusinessError> {
     request.custom_validate().await?;
-
-    info!("创建Question : {:?}", request.clone());
-    let question = request.into_db_question();
     // 开始一个事务
     let mut transaction = pool.begin().await?;
+    let (new_question_id, question) = insert_new_question(&mut transaction, &mut request).await?;
+    // 提交事务
+    transaction.commit().await?;
+    invalidate_question_cache(&question.question_code, &question.product_code);
 
-    let new_question_id = QuestionDao::insert_question(&mut transaction, &question).await?;
+    //同一商品下可能重复的问题，提示运营人员合并
+    let candidates = QuestionDao::query_duplicate_candidates(&pool, &question.product_code, &question.question_content, &question.question_code, DUPLICATE_CANDIDATE_LIMIT).await?;
+    let response = ResponseCreateQuestion{
+        id: new_question_id.to_string(),
+        question_code: question.question_code.clone(),
+        duplicates: DuplicateQuestion::find(&question.question_content, candidates),
+    };
+    Ok(Json(AppResponse::success(response)))
+}
 
-    let answer_list = request.answer_list;
-    for answer in &answer_list{
+/// 保存已校验过的新问题及其回答、标签，并记录修订，返回问题的id和问题记录
+/// 新问题为待审核状态，前台用户提问也通过这里入库
+pub async fn insert_new_question(
+    transaction: &mut sqlx::Transaction<'_, sqlx::MySql>,
+    request: &mut RequestCreateQuestion,
+)->Result<(u64, DbQuestion), BusinessError>{
+    //新建的问题本身就需要审核，命中需要审核的敏感词时只记录日志
+    let flagged_words = request.mask_sensitive_words();
+    if !flagged_words.is_empty(){
+        info!("新建问题命中需要审核的敏感词: {:?}", flagged_words);
+    }
+
+    info!("创建Question : {:?}", request.clone());
+    let question = request.into_db_question();
+    let new_question_id = QuestionDao::insert_question(transaction, &question).await?;
+    for answer in &request.answer_list{
         let db_answer = answer.into_db_answer(question.question_code.clone());
-        QuestionDao::insert_answer(&mut transaction, &db_answer).await?;
+        QuestionDao::insert_answer(transaction, &db_answer).await?;
+    }
+    QuestionDao::save_question_tags(transaction, &question.question_code, &normalize_tags(&request.tags)).await?;
+    QuestionDao::insert_revision(transaction, &question.question_code, RevisionAction::Create, question.create_user_id.clone(), question.create_time).await?;
+    Ok((new_question_id, question))
+}
+
+/// 从CSV文件（multipart的file字段）批量导入问题，每行按新建问题的规则校验
+/// 有任何一行校验失败时不导入任何问题，返回每行的错误，修正后可以直接重新上传整个文件
+#[instrument(name = "import_question", skip(multipart),fields(request_id = %Uuid::new_v4()))]
+pub async fn import_question(
+    Extension(pool): Extension<MySqlPool>,
+    mut multipart: Multipart,
+)-> Result<Json<AppResponse<ResponseImportQuestion>>,BusinessError> {
+    let mut content = None;
+    while let Some(field) = multipart.next_field().await.map_err(multipart_error)? {
+        if field.name() == Some("file") {
+            content = Some(field.bytes().await.map_err(multipart_error)?);
+            break;
+        }
     }
+    let Some(content) = content else {
+        let mut parameters = HashMap::new();
+        parameters.insert("field".to_string(), "file".to_string());
+        parameters.insert("message".to_string(), "请上传CSV文件".to_string());
+        return Err(BusinessError::InvalidParameter((None,Some(vec![parameters]))));
+    };
 
+    let rows = parse_question_csv(&content)?;
+    let total_rows = rows.len();
+    let mut questions = vec![];
+    let mut errors = vec![];
+    for row in rows {
+        let result = match row.question {
+            Ok(question) => question.custom_validate().await.map(|_|question),
+            Err(error) => Err(error),
+        };
+        match result {
+            Ok(question) => questions.push(question),
+            Err(error) => errors.push(ImportRowError::new(row.row_number, error)),
+        }
+    }
+    if !errors.is_empty() || questions.is_empty() {
+        return Ok(Json(AppResponse::success(ResponseImportQuestion{
+            total_rows,
+            imported_count: 0,
+            question_codes: vec![],
+            errors,
+        })));
+    }
+
+    //导入的都是新问题，还没有订阅用户；导入的回答待审核，审核通过时再通知
+    // 开始一个事务
+    let mut transaction = pool.begin().await?;
+    let mut imported = vec![];
+    for mut question in questions {
+        let (_, question) = insert_new_question(&mut transaction, &mut question).await?;
+        imported.push(question);
+    }
     // 提交事务
     transaction.commit().await?;
-    
-    Ok(Json(AppResponse::success(new_question_id.to_string())))
+    //导入的问题编号都是新生成的，没有详情缓存，只需按商品清除一次列表缓存
+    let product_codes: BTreeSet<&str> = imported.iter().map(|question|question.product_code.as_str()).collect();
+    for product_code in product_codes {
+        invalidate_question_list_cache(product_code);
+    }
+    Ok(Json(AppResponse::success(ResponseImportQuestion{
+        total_rows,
+        imported_count: imported.len(),
+        question_codes: imported.into_iter().map(|question|question.question_code).collect(),
+        errors,
+    })))
+}
+
+fn multipart_error(error: MultipartError)->BusinessError{
+    let mut parameters = HashMap::new();
+    parameters.insert("field".to_string(), "file".to_string());
+    parameters.insert("message".to_string(), error.body_text());
+    BusinessError::InvalidParameter((None,Some(vec![parameters])))
+}
+
+// 导出CSV时每次查询的问题数量
+const EXPORT_PAGE_SIZE: i64 = 200;
+
+/// 按运营端问题列表的查询条件导出CSV（格式与导入相同），忽略分页参数，与游标分页模式一样只支持默认排序
+/// 按游标逐页查询并边查询边输出，不在内存中缓存全部结果
+#[instrument(name = "export_question", fields(request_id = %Uuid::new_v4()))]
+pub async fn export_question(
+    Extension(pool): Extension<MySqlPool>,
+    Json(request): Json<RequestFindQuestionListForTrad>,
+)-> Result<Response,BusinessError> {
+    request.validate()?;
+    if !request.is_default_sort() {
+        let mut parameters = HashMap::new();
+        parameters.insert("field".to_string(), "sortField".to_string());
+        parameters.insert("message".to_string(), "导出只支持默认排序".to_string());
+        return Err(BusinessError::InvalidParameter((None,Some(vec![parameters]))));
+    }
+    let answer_columns = QuestionDao::query_max_active_answer_count(&pool, &request).await?.max(1) as usize;
+    let mut header = UTF8_BOM.to_vec();
+    header.extend(write_csv(&[csv_header(answer_columns)]).map_err(csv_error)?);
+
+    //状态为None表示已导出完毕，Some(None)表示第一页
+    let pages = stream::try_unfold(Some(None::<QuestionCursor>), move |cursor| {
+        let pool = pool.clone();
+        let request = request.clone();
+        async move {
+            let Some(cursor) = cursor else {
+                return Ok(None);
+            };
+            let page = QuestionDao::query_question_list_by_cursor(&pool, &request, cursor.as_ref(), EXPORT_PAGE_SIZE, false).await?;
+            if page.data.is_empty() {
+                return Ok(None);
+            }
+            let next_cursor = match page.next_cursor {
+                Some(next_cursor) => Some(Some(QuestionCursor::decode(&next_cursor)?)),
+                None => None,
+            };
+            let rows: Vec<Vec<String>> = with_answers(&pool, &page.data).await?.iter()
+                .map(|question|csv_row(question, answer_columns))
+                .collect();
+            let content = write_csv(&rows).map_err(csv_error)?;
+            Ok(Some((Bytes::from(content), next_cursor)))
+        }
+    })
+    //响应已经开始输出，无法再返回业务错误，只能中断输出
+    .map_err(|error: BusinessError|{
+        tracing::error!("导出问题CSV失败: {:?}", error);
+        std::io::Error::new(std::io::ErrorKind::Other, error.msg())
+    });
+    let body = Body::from_stream(stream::once(async move { Ok::<_, std::io::Error>(Bytes::from(header)) }).chain(pages));
+    let headers = [
+        (CONTENT_TYPE, "text/csv; charset=utf-8"),
+        (CONTENT_DISPOSITION, "attachment; filename=\"questions.csv\""),
+    ];
+    Ok((headers, body).into_response())
+}
+
+fn csv_error(error: csv::Error)->BusinessError{
+    BusinessError::InternalServerError((Some(error.to_string()),None))
 }
 
 #[instrument(name = "update_question", fields(request_id = %Uuid::new_v4()))]
 pub async fn update_question(
     Extension(pool): Extension<MySqlPool>,
     TypedHeader(headers): TypedHeader<UserAgent>,
-    Json(request): Json<RequestUpdateQuestion>,
+    Json(mut request): Json<RequestUpdateQuestion>,
 )-> Result<Json<AppResponse<bool>>,BusinessError> {
     request.custom_validate().await?;    
+    let flagged_words = request.mask_sensitive_words();
 
     //先依据question_code查询数据库，确保问题记录是存在的
+    let old_question;
     if let Ok(question_option) = QuestionDao::find_question_by_question_code(&pool, &request.question_code).await{
-        if question_option.is_none(){
+        if let Some(question) = question_option{
+            old_question = question;
+        }else{
             let mut parameters= HashMap::new();
             parameters.insert("question_code".to_string(), request.question_code.clone());
             return Err(BusinessError::QuestionNotFound(
@@ -77,6 +288,7 @@ pub async fn update_question(
         ))
     }
     info!("更新Question : {:?}", request.clone());
+    let old_answers = QuestionDao::query_answer_by_question_code(&pool, &request.question_code).await?;
     let mut question = request.into_db_question();
 
     let now = now_local();
@@ -87,16 +299,39 @@ pub async fn update_question(
     let mut transaction = pool.begin().await?;
 
     let _ = QuestionDao::update_question(&mut transaction, &question).await;
-    let _ = QuestionDao::delete_answer(&mut transaction, &question.question_code).await;
-
-    let answer_list = request.answer_list;
-    for answer in &answer_list{
-        let db_answer = answer.into_db_answer(question.question_code.clone());
-        let _ = QuestionDao::insert_answer(&mut transaction, &db_answer).await;
+    //回答在原记录上更新，保留id、创建时间和投票，只删除被移除的回答
+    let answers = request.into_db_answers(&old_answers, now)?;
+    let visible_answer_count = answers.iter().filter(|answer|answer.is_visible()).count();
+    let answer_changes = AnswerChanges::diff(&old_answers, answers);
+    QuestionDao::save_answer_changes(&mut transaction, &question.question_code, &answer_changes).await?;
+    //命中需要审核的敏感词时，已审核通过的问题重新进入待审核状态
+    if !flagged_words.is_empty(){
+        info!("更新问题命中需要审核的敏感词: {:?}", flagged_words);
+        QuestionDao::reset_question_review(&mut transaction, &question.question_code).await?;
+    }
+    //未传入标签时保留原有标签
+    if let Some(tags) = &request.tags {
+        QuestionDao::save_question_tags(&mut transaction, &question.question_code, &normalize_tags(tags)).await?;
+    }
+    QuestionDao::insert_revision(&mut transaction, &question.question_code, RevisionAction::Update, None, now).await?;
+    //只有前台可以看到的变化才通知订阅用户，新增的回答在审核通过时通知
+    let approved: i8 = ReviewStatus::Approved.into();
+    let visible_changed = old_question.question_content != question.question_content
+        || answer_changes.affects_visible_answers(&old_answers);
+    if old_question.review_status == approved && flagged_words.is_empty() && visible_changed {
+        let payload = serde_json::json!({
+            "questionCode": question.question_code,
+            "questionContent": question.question_content,
+            "answerCount": visible_answer_count,
+        }).to_string();
+        SubscriptionDao::enqueue_notification(&mut transaction, &question.question_code, NotificationEvent::QuestionUpdated, &payload, now).await?;
     }
 
     // 提交事务
     transaction.commit().await?;
+    //商品编号可能被修改，新旧商品的问题列表缓存都需要清除
+    invalidate_question_cache(&question.question_code, &old_question.product_code);
+    invalidate_question_cache(&question.question_code, &question.product_code);
 
     let response = true;
     Ok(Json(AppResponse::success(response)))
@@ -110,22 +345,31 @@ pub async fn find_question_list_for_trad(
     request.validate()?;
     let current_pageno = request.current_pageno;
     let page_size = request.page_size;
-    
-    if let Ok(page_questions) = QuestionDao::query_question_list(&pool, &request, current_pageno, page_size).await{        
-        let mut response_questions = vec![];
-        for question in &page_questions.data {
-            let question_code = &question.question_code;
-            match QuestionDao::query_answer_by_question_code(&pool, question_code).await {
-                Ok(answers) => {
-                    let question_response = Question::from_db_questions(question.clone(),answers);
-                    response_questions.push(question_response);
-                },
-                Err(_) => {
-                    let question_response = Question::from_db_questions(question.clone(),vec![]);
-                    response_questions.push(question_response);
-                }
-            }
+
+    //游标分页模式
+    if request.cursor_mode {
+        if !request.is_default_sort() {
+            let mut parameters = HashMap::new();
+            parameters.insert("field".to_string(), "sortField".to_string());
+            parameters.insert("message".to_string(), "游标分页模式只支持默认排序".to_string());
+            return Err(BusinessError::InvalidParameter((None,Some(vec![parameters]))));
         }
+        let cursor = match &request.cursor {
+            Some(cursor) => Some(QuestionCursor::decode(cursor)?),
+            None => None,
+        };
+        let cursor_page = QuestionDao::query_question_list_by_cursor(&pool, &request, cursor.as_ref(), page_size, request.with_total).await?;
+        let response_questions = with_answers(&pool, &cursor_page.data).await?;
+        let response_find_question_list_for_trad = ResponseFindQuestionListForTrad::with_cursor(
+            cursor_page.total_records,
+            cursor_page.next_cursor,
+            response_questions,
+        );
+        return Ok(Json(AppResponse::success(response_find_question_list_for_trad)));
+    }
+
+    if let Ok(page_questions) = QuestionDao::query_question_list(&pool, &request, current_pageno, page_size).await{        
+        let response_questions = with_answers(&pool, &page_questions.data).await?;
         let response_find_question_list_for_trad = ResponseFindQuestionListForTrad::new(
             page_questions.total_records,
             page_questions.current_pageno,
@@ -141,6 +385,23 @@ pub async fn find_question_list_for_trad(
     }
 }
 
+/// 为一页问题加载回答，一页问题的回答只查询一次，再按question_code分组
+async fn with_answers(pool: &MySqlPool, questions: &[DbQuestion])->Result<Vec<Question>, BusinessError>{
+    let question_codes: Vec<String> = questions.iter()
+        .map(|question|question.question_code.clone())
+        .collect();
+    let mut answers_by_question_code = QuestionDao::query_answer_by_question_codes(pool, &question_codes).await?;
+    let mut tags_by_question_code = QuestionDao::query_tags_by_question_codes(pool, &question_codes).await?;
+    let mut response_questions = vec![];
+    for question in questions {
+        let answers = answers_by_question_code.remove(&question.question_code).unwrap_or_default();
+        let mut question_response = Question::from_db_questions(question.clone(),answers);
+        question_response.tags = tags_by_question_code.remove(&question.question_code).unwrap_or_default();
+        response_questions.push(question_response);
+    }
+    Ok(response_questions)
+}
+
 /// 将问题记录置顶
 #[instrument(name = "top_question", skip(params),fields(request_id = %Uuid::new_v4()))]
 pub async fn top_question(
@@ -148,6 +409,8 @@ pub async fn top_question(
     Query(params): Query<RequestTopQuestion>,
     ) 
     -> Result<Json<AppResponse<bool>>,BusinessError>  {
+    let now = now_local();
+    params.validate_expire_time(now)?;
     let question_code = &params.question_code;
     if let Ok(question_option) = QuestionDao::find_question_by_question_code(&pool, &question_code).await{
         if question_option.is_none(){
@@ -157,13 +420,17 @@ pub async fn top_question(
                 (None,Some(parameters))
             ));     
         }else{
+            let product_code = question_option.as_ref().map(|question|question.product_code.clone()).unwrap_or_default();
             // 开始一个事务
             let mut transaction = pool.begin().await?;
-            let next_sort = QuestionDao::have_next_sort(&pool).await?;
-            // let sort:i32 = 1;
-            let _ = QuestionDao::update_sort_by_question_code(&mut transaction, question_code, next_sort).await;
+            let next_sort = QuestionDao::have_next_sort(&mut transaction, &product_code).await?;
+            QuestionDao::update_top_by_question_code(&mut transaction, question_code, next_sort, params.expire_time).await?;
+            QuestionDao::insert_revision(&mut transaction, question_code, RevisionAction::Top, None, now).await?;
             // 提交事务
             transaction.commit().await?;
+            if let Some(question) = &question_option{
+                invalidate_question_cache(&question.question_code, &question.product_code);
+            }
         }
     }else {
         return Err(BusinessError::InternalServerError(
@@ -193,10 +460,14 @@ pub async fn cancel_top_question(
             // 开始一个事务
             let mut transaction = pool.begin().await?;
             let reset_sort = 0;
-            QuestionDao::update_sort_by_question_code(&mut transaction, question_code, reset_sort).await?;
+            QuestionDao::update_top_by_question_code(&mut transaction, question_code, reset_sort, None).await?;
+            QuestionDao::insert_revision(&mut transaction, question_code, RevisionAction::CancelTop, None, now_local()).await?;
 
             // 提交事务
             transaction.commit().await?;
+            if let Some(question) = &question_option{
+                invalidate_question_cache(&question.question_code, &question.product_code);
+            }
         }
     }else {
         return Err(BusinessError::InternalServerError(
@@ -207,6 +478,30 @@ pub async fn cancel_top_question(
 
 }
 
+/// 调整同一商品下置顶问题的顺序，在一个事务中重写sort值
+#[instrument(name = "reorder_top_question", fields(request_id = %Uuid::new_v4()))]
+pub async fn reorder_top_question(
+    Extension(pool): Extension<MySqlPool>,
+    Json(request): Json<RequestReorderTopQuestion>,
+)-> Result<Json<AppResponse<bool>>,BusinessError> {
+    request.validate()?;
+    let now = now_local();
+
+    // 开始一个事务
+    let mut transaction = pool.begin().await?;
+    let changed_question_codes = QuestionDao::reorder_top_question(&mut transaction, &request.product_code, &request.question_codes).await?;
+    for question_code in &changed_question_codes{
+        QuestionDao::insert_revision(&mut transaction, question_code, RevisionAction::Reorder, None, now).await?;
+    }
+    // 提交事务
+    transaction.commit().await?;
+
+    for question_code in &changed_question_codes{
+        invalidate_question_cache(question_code, &request.product_code);
+    }
+    Ok(Json(AppResponse::success(true)))
+}
+
 /// 软删除问题记录（设置问题状态为失效）
 #[instrument(name = "disabled_question", fields(request_id = %Uuid::new_v4()))]
 pub async fn disabled_question(
@@ -228,8 +523,12 @@ pub async fn disabled_question(
             // 开始一个事务
             let mut transaction = pool.begin().await?;
             QuestionDao::disabled_question_and_answer_by_question_code(&mut transaction, &request.question_code).await?;
+            QuestionDao::insert_revision(&mut transaction, &request.question_code, RevisionAction::Disable, None, now_local()).await?;
             // 提交事务
             transaction.commit().await?;
+            if let Some(question) = &question_option{
+                invalidate_question_cache(&question.question_code, &question.product_code);
+            }
         }
     }else {
         return Err(BusinessError::InternalServerError(
@@ -239,5 +538,641 @@ pub async fn disabled_question(
     Ok(Json(AppResponse::success(true)))
 }
 
+/// 查询审核队列
+#[instrument(name = "find_review_queue", fields(request_id = %Uuid::new_v4()))]
+pub async fn find_review_queue(
+    Extension(pool): Extension<MySqlPool>,
+    Json(request): Json<RequestFindReviewQueue>,
+)-> Result<Json<AppResponse<PageResponse<ResponseReviewItem>>>,BusinessError> {
+    request.validate()?;
+    let page = QuestionDao::query_review_queue(&pool, &request).await?;
+    let target_type = request.target_type.as_str();
+    let list = page.data.into_iter()
+        .map(|item|ResponseReviewItem::from_db(target_type, item))
+        .collect();
+    let response = PageResponse::new(
+        page.total_records,
+        page.current_pageno,
+        page.page_size,
+        page.total_pages,
+        Some(list),
+    );
+    Ok(Json(AppResponse::success(response)))
+}
+
+/// 批量审核问题、答案和追问回复，每个审核决定都记录审核人和审核时间
+#[instrument(name = "review_qa", fields(request_id = %Uuid::new_v4()))]
+pub async fn review_qa(
+    Extension(pool): Extension<MySqlPool>,
+    Json(request): Json<RequestReviewQa>,
+)-> Result<Json<AppResponse<bool>>,BusinessError> {
+    request.custom_validate().await?;
+    let now = now_local();
+    let mut question_codes = vec![];
+
+    // 开始一个事务
+    let mut transaction = pool.begin().await?;
+    for item in &request.item_list{
+        let review_status: i8 = ReviewStatus::from(item.decision).into();
+        let question_code = match item.target_type {
+            ReviewTargetType::Question => QuestionDao::review_question(&mut transaction, item.id, review_status, &item.reject_reason, &request.reviewer_id, now).await?,
+            ReviewTargetType::Answer => QuestionDao::review_answer(&mut transaction, item.id, review_status, &item.reject_reason, &request.reviewer_id, now).await?,
+            ReviewTargetType::Reply => QuestionDao::review_reply(&mut transaction, item.id, review_status, &item.reject_reason, &request.reviewer_id, now).await?,
+        };
+        let (question_code, product_code) = match question_code {
+            Some(question_code) => question_code,
+            None => {
+                let mut parameters= HashMap::new();
+                parameters.insert("id".to_string(), item.id.to_string());
+                return match item.target_type {
+                    ReviewTargetType::Question => Err(BusinessError::QuestionNotFound((None,Some(parameters)))),
+                    ReviewTargetType::Answer => Err(BusinessError::AnswerNotFound((None,Some(parameters)))),
+                    ReviewTargetType::Reply => Err(BusinessError::ReplyNotFound((None,Some(parameters)))),
+                };
+            }
+        };
+        let review_log = ReviewLog{
+            id:None,
+            target_type:item.target_type.as_str().to_string(),
+            target_id:item.id,
+            question_code:question_code.clone(),
+            review_status,
+            reject_reason:item.reject_reason.clone(),
+            reviewer_id:request.reviewer_id.clone(),
+            reviewer_name:request.reviewer_name.clone(),
+            create_time:now,
+        };
+        QuestionDao::insert_review_log(&mut transaction, &review_log).await?;
+        //回答审核通过后前台才能看到，此时通知订阅用户新增了回答
+        let approved: i8 = ReviewStatus::Approved.into();
+        if item.target_type == ReviewTargetType::Answer && review_status == approved {
+            let payload = serde_json::json!({
+                "questionCode": question_code,
+                "answerId": item.id,
+            }).to_string();
+            SubscriptionDao::enqueue_notification(&mut transaction, &question_code, NotificationEvent::AnswerAdded, &payload, now).await?;
+        }
+        question_codes.push((question_code, product_code));
+    }
+    // 提交事务
+    transaction.commit().await?;
+
+    //审核结果影响前台展示，清除相关问题的缓存
+    for (question_code, product_code) in question_codes{
+        invalidate_question_cache(&question_code, &product_code);
+    }
+    Ok(Json(AppResponse::success(true)))
+}
+
+/// 查询商品下各标签关联的有效问题数量，按数量从高到低排序
+#[instrument(name = "find_tag_usage", skip(params),fields(request_id = %Uuid::new_v4()))]
+pub async fn find_tag_usage(
+    Extension(pool): Extension<MySqlPool>,
+    Query(params): Query<RequestFindTagUsage>,
+    )
+    -> Result<Json<AppResponse<Vec<ResponseTagUsage>>>,BusinessError> {
+    params.validate()?;
+    let usages = QuestionDao::query_tag_usage_by_product(&pool, &params.product_code).await?;
+    let response = usages.into_iter()
+        .map(|(tag_name, question_count)|ResponseTagUsage{tag_name, question_count})
+        .collect();
+    Ok(Json(AppResponse::success(response)))
+}
+
+/// 报表：商品下浏览量最高的问题，按浏览日期筛选（尚未写入数据库的浏览量不计入）
+#[instrument(name = "find_top_viewed_question", skip(params),fields(request_id = %Uuid::new_v4()))]
+pub async fn find_top_viewed_question(
+    Extension(pool): Extension<MySqlPool>,
+    Query(params): Query<RequestFindTopViewedQuestion>,
+    )
+    -> Result<Json<AppResponse<Vec<ResponseTopViewedQuestion>>>,BusinessError> {
+    params.custom_validate()?;
+    let limit = params.limit.unwrap_or(DEFAULT_TOP_VIEWED_LIMIT);
+    let questions = QuestionDao::query_top_viewed_question(&pool, &params.product_code, params.start_date, params.end_date, limit).await?;
+    let response = questions.into_iter()
+        .map(|(question_code, question_content, view_count)|ResponseTopViewedQuestion{question_code, question_content, view_count})
+        .collect();
+    Ok(Json(AppResponse::success(response)))
+}
+
+/// 报表：没有有效回答的问题（分页），按问题的创建日期筛选
+#[instrument(name = "find_unanswered_question", skip(params),fields(request_id = %Uuid::new_v4()))]
+pub async fn find_unanswered_question(
+    Extension(pool): Extension<MySqlPool>,
+    Query(params): Query<RequestFindUnansweredQuestion>,
+    )
+    -> Result<Json<AppResponse<PageResponse<Question>>>,BusinessError> {
+    params.custom_validate()?;
+    let page = QuestionDao::query_unanswered_question(&pool, &params).await?;
+    let list = page.data.into_iter()
+        .map(|question|Question::from_db_questions(question, vec![]))
+        .collect();
+    let response = PageResponse::new(
+        page.total_records,
+        page.current_pageno,
+        page.page_size,
+        page.total_pages,
+        Some(list),
+    );
+    Ok(Json(AppResponse::success(response)))
+}
+
+/// 报表：按商品统计回答时效（问题创建到第一个有效回答的时间），按问题的创建日期筛选
+#[instrument(name = "find_answer_latency", skip(params),fields(request_id = %Uuid::new_v4()))]
+pub async fn find_answer_latency(
+    Extension(pool): Extension<MySqlPool>,
+    Query(params): Query<RequestFindAnswerLatency>,
+    )
+    -> Result<Json<AppResponse<Vec<ResponseAnswerLatency>>>,BusinessError> {
+    params.custom_validate()?;
+    let stats = QuestionDao::query_answer_latency(&pool, &params).await?;
+    let response = stats.into_iter()
+        .map(ResponseAnswerLatency::from_db)
+        .collect();
+    Ok(Json(AppResponse::success(response)))
+}
 
+/// 保存问题及其回答某个语言的翻译，回答必须属于该问题
+#[instrument(name = "save_question_translation", fields(request_id = %Uuid::new_v4()))]
+pub async fn save_question_translation(
+    Extension(pool): Extension<MySqlPool>,
+    Json(request): Json<RequestSaveQuestionTranslation>,
+)-> Result<Json<AppResponse<bool>>,BusinessError> {
+    request.custom_validate()?;
+    let question = match QuestionDao::find_question_by_question_code(&pool, &request.question_code).await? {
+        Some(question) => question,
+        None => {
+            let mut parameters= HashMap::new();
+            parameters.insert("question_code".to_string(), request.question_code.clone());
+            return Err(BusinessError::QuestionNotFound((None,Some(parameters))));
+        }
+    };
+    let answer_ids: Vec<i64> = QuestionDao::query_answer_by_question_code(&pool, &request.question_code).await?
+        .into_iter()
+        .filter_map(|answer|answer.id)
+        .collect();
+    if let Some(answer) = request.answers.iter().find(|answer|!answer_ids.contains(&answer.answer_id)){
+        let mut parameters= HashMap::new();
+        parameters.insert("answerId".to_string(), answer.answer_id.to_string());
+        return Err(BusinessError::AnswerNotFound((None,Some(parameters))));
+    }
+    let now = now_local();
+    // 开始一个事务
+    let mut transaction = pool.begin().await?;
+    QuestionDao::save_question_translation(&mut transaction, &request.question_code, request.locale, &request.question_content, now).await?;
+    for answer in &request.answers{
+        QuestionDao::save_answer_translation(&mut transaction, answer.answer_id, &request.question_code, request.locale, &answer.answer_content, now).await?;
+    }
+    // 提交事务
+    transaction.commit().await?;
+    invalidate_question_cache(&question.question_code, &question.product_code);
+    Ok(Json(AppResponse::success(true)))
+}
 
+/// 查询问题及其回答的全部翻译，按语言分组
+#[instrument(name = "find_question_translation", skip(params),fields(request_id = %Uuid::new_v4()))]
+pub async fn find_question_translation(
+    Extension(pool): Extension<MySqlPool>,
+    Query(params): Query<RequestFindQuestionTranslation>,
+    )
+    -> Result<Json<AppResponse<Vec<ResponseQuestionTranslation>>>,BusinessError> {
+    params.validate()?;
+    let question_codes = vec![params.question_code.clone()];
+    let question_translations = QuestionDao::query_question_translations(&pool, &question_codes, &[]).await?;
+    let answer_translations = QuestionDao::query_answer_translations(&pool, &question_codes, &[]).await?;
+    Ok(Json(AppResponse::success(ResponseQuestionTranslation::group(question_translations, answer_translations))))
+}
+
+/// 删除问题及其回答某个语言的翻译，返回是否删除了翻译
+#[instrument(name = "delete_question_translation", fields(request_id = %Uuid::new_v4()))]
+pub async fn delete_question_translation(
+    Extension(pool): Extension<MySqlPool>,
+    Json(request): Json<RequestDeleteQuestionTranslation>,
+)-> Result<Json<AppResponse<bool>>,BusinessError> {
+    request.validate()?;
+    // 开始一个事务
+    let mut transaction = pool.begin().await?;
+    let deleted = QuestionDao::delete_question_translation(&mut transaction, &request.question_code, request.locale).await?;
+    // 提交事务
+    transaction.commit().await?;
+    if deleted {
+        if let Some(question) = QuestionDao::find_question_by_question_code(&pool, &request.question_code).await? {
+            invalidate_question_cache(&question.question_code, &question.product_code);
+        }
+    }
+    Ok(Json(AppResponse::success(deleted)))
+}
+
+/// 分页查询回收站，每个问题附带恢复时会一起恢复的回答
+#[instrument(name = "find_recycle_bin", fields(request_id = %Uuid::new_v4()))]
+pub async fn find_recycle_bin(
+    Extension(pool): Extension<MySqlPool>,
+    Json(request): Json<RequestFindRecycleBin>,
+)-> Result<Json<AppResponse<PageResponse<ResponseRecycleBinQuestion>>>,BusinessError> {
+    request.validate()?;
+    let page = QuestionDao::query_recycle_bin(&pool, &request).await?;
+    let disable_batches: Vec<String> = page.data.iter()
+        .filter_map(|question|question.disable_batch.clone())
+        .collect();
+    let mut answers_by_question_code = QuestionDao::query_answer_by_disable_batches(&pool, &disable_batches).await?;
+    let list = page.data.into_iter()
+        .map(|question|{
+            let answers = answers_by_question_code.remove(&question.question_code).unwrap_or_default();
+            ResponseRecycleBinQuestion::from_db(question, answers)
+        })
+        .collect();
+    let response = PageResponse::new(
+        page.total_records,
+        page.current_pageno,
+        page.page_size,
+        page.total_pages,
+        Some(list),
+    );
+    Ok(Json(AppResponse::success(response)))
+}
+
+/// 从回收站恢复问题及与其一起删除的回答
+#[instrument(name = "restore_question", fields(request_id = %Uuid::new_v4()))]
+pub async fn restore_question(
+    Extension(pool): Extension<MySqlPool>,
+    Json(request): Json<RequestRestoreQuestion>,
+)-> Result<Json<AppResponse<bool>>,BusinessError> {
+    request.validate()?;
+    let now = now_local();
+
+    // 开始一个事务
+    let mut transaction = pool.begin().await?;
+    let question = QuestionDao::restore_disabled_question(&mut transaction, &request.question_code, now).await?;
+    QuestionDao::insert_revision(&mut transaction, &request.question_code, RevisionAction::Restore, None, now).await?;
+    // 提交事务
+    transaction.commit().await?;
+
+    invalidate_question_cache(&question.question_code, &question.product_code);
+    Ok(Json(AppResponse::success(true)))
+}
+
+/// 合并重复的问题，来源问题的回答移到目标问题，前台以来源问题编号查询时返回目标问题
+#[instrument(name = "merge_question", fields(request_id = %Uuid::new_v4()))]
+pub async fn merge_question(
+    Extension(pool): Extension<MySqlPool>,
+    Json(request): Json<RequestMergeQuestion>,
+)-> Result<Json<AppResponse<bool>>,BusinessError> {
+    request.custom_validate().await?;
+    let now = now_local();
+
+    // 开始一个事务
+    let mut transaction = pool.begin().await?;
+    let (source_question, target_question) = QuestionDao::merge_question(&mut transaction, &request.source_question_code, &request.target_question_code, &request.operator_id, now).await?;
+    QuestionDao::insert_revision(&mut transaction, &source_question.question_code, RevisionAction::Merge, request.operator_id.clone(), now).await?;
+    QuestionDao::insert_revision(&mut transaction, &target_question.question_code, RevisionAction::Merge, request.operator_id.clone(), now).await?;
+    //来源问题的回答移到了目标问题，两个问题的订阅用户都需要通知
+    let payload = serde_json::json!({
+        "questionCode": source_question.question_code,
+        "mergedInto": target_question.question_code,
+    }).to_string();
+    SubscriptionDao::enqueue_notification(&mut transaction, &source_question.question_code, NotificationEvent::QuestionUpdated, &payload, now).await?;
+    let payload = serde_json::json!({
+        "questionCode": target_question.question_code,
+        "mergedFrom": source_question.question_code,
+    }).to_string();
+    SubscriptionDao::enqueue_notification(&mut transaction, &target_question.question_code, NotificationEvent::QuestionUpdated, &payload, now).await?;
+    // 提交事务
+    transaction.commit().await?;
+
+    invalidate_question_cache(&source_question.question_code, &source_question.product_code);
+    invalidate_question_cache(&target_question.question_code, &target_question.product_code);
+    //之前重定向到来源问题的记录也已改为目标问题，清除全部重定向缓存
+    QUESTION_REDIRECT_CACHE.invalidate_all();
+    Ok(Json(AppResponse::success(true)))
+}
+
+/// 分页查询问题的修订记录
+#[instrument(name = "find_question_revision_list", fields(request_id = %Uuid::new_v4()))]
+pub async fn find_question_revision_list(
+    Extension(pool): Extension<MySqlPool>,
+    Json(request): Json<RequestFindQuestionRevisionList>,
+)-> Result<Json<AppResponse<PageResponse<ResponseQuestionRevision>>>,BusinessError> {
+    request.validate()?;
+    let page = QuestionDao::query_revision_list(&pool, &request.question_code, request.current_pageno, request.page_size).await?;
+    let list = page.data.into_iter()
+        .map(ResponseQuestionRevision::from_db)
+        .collect::<Result<Vec<_>,BusinessError>>()?;
+    let response = PageResponse::new(
+        page.total_records,
+        page.current_pageno,
+        page.page_size,
+        page.total_pages,
+        Some(list),
+    );
+    Ok(Json(AppResponse::success(response)))
+}
+
+/// 比较问题的两个修订版本
+#[instrument(name = "diff_question_revision", skip(params),fields(request_id = %Uuid::new_v4()))]
+pub async fn diff_question_revision(
+    Extension(pool): Extension<MySqlPool>,
+    Query(params): Query<RequestDiffQuestionRevision>,
+)-> Result<Json<AppResponse<ResponseDiffQuestionRevision>>,BusinessError> {
+    params.validate()?;
+    let from_revision = find_revision(&pool, &params.question_code, params.from_revision).await?;
+    let to_revision = find_revision(&pool, &params.question_code, params.to_revision).await?;
+    let changes = from_revision.snapshot()?.diff(&to_revision.snapshot()?);
+    Ok(Json(AppResponse::success(ResponseDiffQuestionRevision{
+        question_code: params.question_code,
+        from_revision: params.from_revision,
+        to_revision: params.to_revision,
+        changes,
+    })))
+}
+
+/// 将问题及其回答恢复到指定的修订版本，恢复本身也会生成一条新的修订记录
+#[instrument(name = "restore_question_revision", fields(request_id = %Uuid::new_v4()))]
+pub async fn restore_question_revision(
+    Extension(pool): Extension<MySqlPool>,
+    Json(request): Json<RequestRestoreQuestionRevision>,
+)-> Result<Json<AppResponse<i32>>,BusinessError> {
+    request.validate()?;
+    let snapshot = find_revision(&pool, &request.question_code, request.revision).await?.snapshot()?;
+    let now = now_local();
+
+    // 开始一个事务
+    let mut transaction = pool.begin().await?;
+    //恢复前的商品编号，商品编号可能随恢复改变
+    let old_product_code = QuestionDao::find_question_for_update(&mut transaction, &request.question_code).await?.product_code;
+    QuestionDao::restore_question(&mut transaction, &request.question_code, &snapshot, now).await?;
+    let new_revision = QuestionDao::insert_revision(&mut transaction, &request.question_code, RevisionAction::Restore, request.operator_id.clone(), now).await?;
+    // 提交事务
+    transaction.commit().await?;
+
+    invalidate_question_cache(&request.question_code, &old_product_code);
+    invalidate_question_cache(&request.question_code, &snapshot.product_code);
+    Ok(Json(AppResponse::success(new_revision)))
+}
+
+async fn find_revision(pool:&MySqlPool, question_code:&String, revision:i32)->Result<DbQuestionRevision, BusinessError>{
+    match QuestionDao::find_revision(pool, question_code, revision).await? {
+        Some(revision) => Ok(revision),
+        None => {
+            let mut parameters= HashMap::new();
+            parameters.insert("question_code".to_string(), question_code.clone());
+            parameters.insert("revision".to_string(), revision.to_string());
+            Err(BusinessError::RevisionNotFound((None,Some(parameters))))
+        }
+    }
+}
+
+/// 启动后台任务，定期将置顶已到期的问题重置为未置顶
+/// 间隔由环境变量TOP_EXPIRE_SWEEP_INTERVAL_SECONDS指定，默认60秒
+pub fn start_top_expire_sweeper(pool: MySqlPool){
+    let interval_seconds = env::var("TOP_EXPIRE_SWEEP_INTERVAL_SECONDS").ok()
+        .and_then(|value|value.parse::<u64>().ok())
+        .unwrap_or(60);
+    tokio::spawn(
+        async move{
+            loop{
+                if let Err(error) = sweep_expired_top_question(&pool).await {
+                    tracing::error!("重置到期置顶失败: {:?}", error);
+                }
+                tokio::time::sleep(Duration::from_secs(interval_seconds)).await;
+            }
+        }
+    );
+}
+
+/// 重置置顶已到期的问题，记录修订并清除相关缓存，返回重置的问题数量
+pub async fn sweep_expired_top_question(pool: &MySqlPool)->Result<usize, BusinessError>{
+    let now = now_local();
+    // 开始一个事务
+    let mut transaction = pool.begin().await?;
+    let expired = QuestionDao::reset_expired_top_question(&mut transaction, now).await?;
+    for (question_code, _) in &expired{
+        QuestionDao::insert_revision(&mut transaction, question_code, RevisionAction::CancelTop, None, now).await?;
+    }
+    // 提交事务
+    transaction.commit().await?;
+
+    for (question_code, product_code) in &expired{
+        invalidate_question_cache(question_code, product_code);
+    }
+    if !expired.is_empty(){
+        info!("已重置{}个到期置顶的问题", expired.len());
+    }
+    Ok(expired.len())
+}
+
+/// 问题变更后清除前台缓存：问题详情（每个语言一个key，直接按key清除）以及所属商品的问题列表
+pub fn invalidate_question_cache(question_code:&str, product_code:&str){
+    for locale in Locale::ALL {
+        QUESTION_CACHE.invalidate(&QuestionKey::new(question_code, locale));
+    }
+    invalidate_question_list_cache(product_code);
+}
+
+/// 清除商品的前台问题列表缓存
+pub fn invalidate_question_list_cache(product_code:&str){
+    let product_code = product_code.to_string();
+    QUESTION_PAGE_CACHE.invalidate_if(move |key|key.product_code == product_code);
+}
+
+/// 立即生成商品的FAQ静态快照，内容未变化时沿用已有的快照
+#[instrument(name = "build_faq_snapshot", fields(request_id = %Uuid::new_v4()))]
+pub async fn build_faq_snapshot(
+    Extension(pool): Extension<MySqlPool>,
+    Json(request): Json<RequestBuildFaqSnapshot>,
+)-> Result<Json<AppResponse<ResponseBuildFaqSnapshot>>,BusinessError> {
+    request.validate()?;
+    let response = build_product_faq_snapshot(&pool, &request.product_code).await?;
+    Ok(Json(AppResponse::success(response)))
+}
+
+/// 生成商品的FAQ静态快照：问题内容的哈希值与已有快照相同时不重新写入，否则版本号加1后写入
+/// 同一商品的快照依次生成（后台任务与手动生成可能同时进行）
+pub async fn build_product_faq_snapshot(pool: &MySqlPool, product_code: &String)->Result<ResponseBuildFaqSnapshot, BusinessError>{
+    let Some(file) = faq_snapshot::snapshot_file(&SNAPSHOT_DIR, product_code) else {
+        let mut parameters = HashMap::new();
+        parameters.insert("field".to_string(), "productCode".to_string());
+        parameters.insert("message".to_string(), "productCode只能包含字母、数字、-和_".to_string());
+        return Err(BusinessError::InvalidParameter((None,Some(vec![parameters]))));
+    };
+    let _guard = faq_snapshot::lock(product_code).await;
+    let questions = QuestionDao::query_all_approved_question_by_product(pool, product_code).await?;
+    let question_codes: Vec<String> = questions.iter()
+        .map(|question|question.question_code.clone())
+        .collect();
+    let mut answers_by_question_code = QuestionDao::query_approved_answer_by_question_codes(pool, &question_codes).await?;
+    let questions: Vec<ResponseGetQuestionByCode> = questions.into_iter()
+        .map(|question|{
+            let answers = answers_by_question_code.remove(&question.question_code).unwrap_or_default();
+            ResponseGetQuestionByCode::from_db(question, answers)
+        })
+        .collect();
+    let content_hash = faq_snapshot::content_hash(&serde_json::to_vec(&questions)?);
+    let meta = faq_snapshot::read_meta(&file).await?;
+    if let Some(meta) = meta.as_ref().filter(|meta|meta.content_hash == content_hash) {
+        return Ok(ResponseBuildFaqSnapshot{
+            product_code: product_code.clone(),
+            version: meta.version,
+            content_hash,
+            question_count: questions.len(),
+            changed: false,
+        });
+    }
+    let snapshot = FaqSnapshot{
+        format_version: FAQ_SNAPSHOT_FORMAT_VERSION,
+        product_code: product_code.clone(),
+        version: meta.map(|meta|meta.version + 1).unwrap_or(1),
+        content_hash,
+        generate_time: now_local().format("%Y-%m-%d %H:%M:%S").to_string(),
+        question_count: questions.len(),
+        questions,
+    };
+    let meta = faq_snapshot::SnapshotMeta{
+        version: snapshot.version,
+        content_hash: snapshot.content_hash.clone(),
+    };
+    faq_snapshot::write(&file, &serde_json::to_vec(&snapshot)?, &meta).await?;
+    info!("已生成商品{}的FAQ快照，版本{}", product_code, snapshot.version);
+    Ok(ResponseBuildFaqSnapshot{
+        product_code: snapshot.product_code,
+        version: snapshot.version,
+        content_hash: snapshot.content_hash,
+        question_count: snapshot.question_count,
+        changed: true,
+    })
+}
+
+/// 启动后台任务，定期为有前台展示问题的商品生成FAQ静态快照
+/// 间隔由环境变量FAQ_SNAPSHOT_INTERVAL_SECONDS指定，默认600秒，设置为0时不启动
+pub fn start_faq_snapshot_builder(pool: MySqlPool){
+    let interval_seconds = env::var("FAQ_SNAPSHOT_INTERVAL_SECONDS").ok()
+        .and_then(|value|value.parse::<u64>().ok())
+        .unwrap_or(600);
+    if interval_seconds == 0 {
+        return;
+    }
+    tokio::spawn(
+        async move{
+            loop{
+                if let Err(error) = build_all_faq_snapshot(&pool).await {
+                    tracing::error!("生成FAQ快照失败: {:?}", error);
+                }
+                tokio::time::sleep(Duration::from_secs(interval_seconds)).await;
+            }
+        }
+    );
+}
+
+/// 为有前台展示问题的商品生成FAQ静态快照，单个商品失败不影响其它商品，返回内容有变化的商品数量
+pub async fn build_all_faq_snapshot(pool: &MySqlPool)->Result<usize, BusinessError>{
+    let mut changed = 0;
+    for product_code in QuestionDao::query_product_code_with_approved_question(pool).await?{
+        if !faq_snapshot::is_valid_product_code(&product_code){
+            tracing::warn!("商品编号不能用作快照文件名，跳过生成FAQ快照: {}", product_code);
+            continue;
+        }
+        match build_product_faq_snapshot(pool, &product_code).await {
+            Ok(result) if result.changed => changed += 1,
+            Ok(_) => {},
+            Err(error) => tracing::error!("生成商品{}的FAQ快照失败: {:?}", product_code, error),
+        }
+    }
+    Ok(changed)
+}
+
+/// 分页查询订阅通知及其发送状态，可按问题、订阅用户和发送状态筛选
+#[instrument(name = "find_notification_list", skip(params),fields(request_id = %Uuid::new_v4()))]
+pub async fn find_notification_list(
+    Extension(pool): Extension<MySqlPool>,
+    Query(params): Query<RequestFindNotificationList>,
+    )
+    -> Result<Json<AppResponse<PageResponse<ResponseNotification>>>,BusinessError> {
+    params.validate()?;
+    let page = SubscriptionDao::query_notification_list(&pool, &params).await?;
+    let list = page.data.into_iter()
+        .map(ResponseNotification::from)
+        .collect();
+    let response = PageResponse::new(
+        page.total_records,
+        page.current_pageno,
+        page.page_size,
+        page.total_pages,
+        Some(list),
+    );
+    Ok(Json(AppResponse::success(response)))
+}
+
+/// 启动后台任务，定期发送到期的订阅通知
+/// 间隔由环境变量QA_NOTIFY_DISPATCH_INTERVAL_SECONDS指定，默认10秒
+pub fn start_notification_dispatcher(pool: MySqlPool){
+    let interval_seconds = env::var("QA_NOTIFY_DISPATCH_INTERVAL_SECONDS").ok()
+        .and_then(|value|value.parse::<u64>().ok())
+        .unwrap_or(10);
+    info!("订阅通知发送方式: {}", notifier::current().name());
+    tokio::spawn(
+        async move{
+            loop{
+                if let Err(error) = dispatch_notifications(&pool).await {
+                    tracing::error!("发送订阅通知失败: {:?}", error);
+                }
+                tokio::time::sleep(Duration::from_secs(interval_seconds)).await;
+            }
+        }
+    );
+}
+
+// 每次领取并发送的通知数量上限
+const NOTIFICATION_DISPATCH_BATCH_SIZE: i64 = 50;
+// 领取的有效时间，应大于一批通知全部超时的发送时间，到期仍未记录结果的通知会被重新领取
+const NOTIFICATION_LEASE_SECONDS: i64 = 600;
+// 记录的发送失败原因的最大长度，与表字段长度一致
+const NOTIFICATION_ERROR_MAX_CHARS: usize = 1000;
+
+/// 发送到期的订阅通知并记录结果：发送失败时按退避时间重试，达到最大次数后标记为发送失败，返回送达的通知数量
+/// 通知先在短事务中领取，多个实例同时发送时不会重复领取；发送在事务之外进行，每条通知的结果单独写入
+pub async fn dispatch_notifications(pool: &MySqlPool)->Result<usize, BusinessError>{
+    let notifier = notifier::current();
+    let now = now_local();
+    let lease_until = now + chrono::Duration::seconds(NOTIFICATION_LEASE_SECONDS);
+    // 开始一个事务
+    let mut transaction = pool.begin().await?;
+    let notifications = SubscriptionDao::claim_due_notifications(&mut transaction, now, lease_until, NOTIFICATION_DISPATCH_BATCH_SIZE).await?;
+    // 提交事务
+    transaction.commit().await?;
+    let mut delivered = 0;
+    for notification in &notifications{
+        let message = NotificationMessage{
+            notification_id: notification.id,
+            subscriber_id: notification.subscriber_id.clone(),
+            question_code: notification.question_code.clone(),
+            event_type: notification.event_type.clone(),
+            //payload无法解析时原样发送，避免一条通知阻塞后续的发送
+            payload: serde_json::from_str(&notification.payload)
+                .unwrap_or_else(|_|serde_json::Value::String(notification.payload.clone())),
+            create_time: notification.create_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+            unsubscribe_token: unsubscribe_token::sign(&notification.question_code, &notification.subscriber_id),
+        };
+        //领取时已累加尝试次数
+        let attempt_count = notification.attempt_count;
+        let result = match notifier.send(&message).await {
+            Ok(()) => {
+                delivered += 1;
+                SubscriptionDao::update_delivery(pool, notification.id, DeliveryStatus::Delivered, now_local(), None, now_local()).await
+            },
+            Err(error) => {
+                let status = if attempt_count >= *notifier::MAX_ATTEMPTS { DeliveryStatus::Failed } else { DeliveryStatus::Pending };
+                tracing::warn!("订阅通知{}第{}次发送失败: {}", notification.id, attempt_count, error);
+                let next_retry_time = now_local() + chrono::Duration::seconds(notifier::retry_delay_seconds(attempt_count));
+                let error: String = error.chars().take(NOTIFICATION_ERROR_MAX_CHARS).collect();
+                SubscriptionDao::update_delivery(pool, notification.id, status, next_retry_time, Some(&error), now_local()).await
+            }
+        };
+        //结果写入失败时继续发送其它通知，该通知在领取到期后重新发送
+        if let Err(error) = result {
+            tracing::error!("记录订阅通知{}的发送结果失败: {:?}", notification.id, error);
+        }
+    }
+    if !notifications.is_empty(){
+        info!("已发送{}条订阅通知，其中{}条送达", notifications.len(), delivered);
+    }
+    Ok(delivered)
+}
@@ -10,6 +10,19 @@ pub mod utils{
     pub mod request_loging;
     pub mod default_value;
     pub mod datetime;
+    pub mod rate_limit;
+    pub mod highlight;
+    pub mod similarity;
+    pub mod sensitive_word;
+    pub mod locale;
+    pub mod markdown;
+    pub mod view_counter;
+    pub mod faq_snapshot;
+    pub mod notifier;
+    pub mod single_flight;
+    pub mod admin_auth;
+    pub mod client_identity;
+    pub mod unsubscribe_token;
 }
 pub mod models;
 pub mod model;
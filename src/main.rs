@@ -4,6 +4,7 @@ use axum::{
     error_handling::HandleErrorLayer, http::StatusCode, BoxError, Extension, 
 };
 use std::time::Duration;
+use std::net::SocketAddr;
 use tower::limit::ConcurrencyLimitLayer;
 use tower_http::trace::TraceLayer;
 use tower::ServiceBuilder;
@@ -19,7 +20,11 @@ use rust_axum_example::utils::logging::init_log;
 use rust_axum_example::utils::db::init_pool;
 use rust_axum_example::utils::request_counter::request_counter_middleware;
 use rust_axum_example::utils::request_loging::print_request_response;
-use rust_axum_example::routes::app_router;
+use rust_axum_example::routes::{app_router, admin_router};
+use rust_axum_example::utils::admin_auth::AdminToken;
+use rust_axum_example::handlers::operation_qa::{start_top_expire_sweeper, start_faq_snapshot_builder, start_notification_dispatcher};
+use rust_axum_example::handlers::frontend_qa::{start_question_view_flusher, flush_question_views};
+use rust_axum_example::utils::sensitive_word::start_sensitive_word_reloader;
 
 #[tokio::main]
 async fn main() {    
@@ -30,6 +35,17 @@ async fn main() {
     // 获取数据库连接地址并初始化连接池
     // let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set in .env file");
     let pool = init_pool().await.expect("Cannot init the database pool");
+    // 定期重置到期的限时置顶
+    start_top_expire_sweeper(pool.clone());
+    // 敏感词词典变更后自动重新加载
+    start_sensitive_word_reloader();
+    // 定期将问题浏览量批量写入数据库
+    start_question_view_flusher(pool.clone());
+    let view_flush_pool = pool.clone();
+    // 定期生成商品的FAQ静态快照
+    start_faq_snapshot_builder(pool.clone());
+    // 定期发送问题订阅通知，发送失败的按退避时间重试
+    start_notification_dispatcher(pool.clone());
 
     // Nacos 配置
     let nacos_url = env::var("NACOS_URL").unwrap_or_else(|_| "http://localhost:8848".to_string());
@@ -62,8 +78,21 @@ async fn main() {
     // let request_counter_clone = Arc::clone(&request_counter);
     // tokio::spawn(log_request_count(port_num.clone(),request_counter_clone));
 
+    // 管理接口：配置了ADMIN_PORT时只在管理端口上提供，否则在业务端口上提供并要求令牌（未配置ADMIN_TOKEN时不提供）
+    let admin_token = AdminToken::from_env();
+    let admin_port = env::var("ADMIN_PORT").ok()
+        .map(|admin_port|admin_port.parse::<u16>().expect("ADMIN_PORT must be a number"));
+    let mut router = app_router();
+    if admin_port.is_none() {
+        if admin_token.0.is_some() {
+            router = router.merge(admin_router(admin_token.clone()));
+        } else {
+            info!("ADMIN_PORT and ADMIN_TOKEN are not set, admin endpoints are disabled");
+        }
+    }
+
     // 创建 API 路由
-    let app = app_router()
+    let app = router
         .layer(TraceLayer::new_for_http()) // 添加日志记录中间件
         .layer(ConcurrencyLimitLayer::new(100)) // 限制并发请求数量
         .layer(
@@ -135,11 +164,26 @@ async fn main() {
     // let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
     info!("Bound to {}", addr);
 
+    // 启动管理端口，默认只监听本机地址，可通过ADMIN_BIND_IP修改
+    if let Some(admin_port) = admin_port {
+        let admin_ip = env::var("ADMIN_BIND_IP").unwrap_or_else(|_| "127.0.0.1".to_string());
+        let admin_addr = format!("{}:{}", admin_ip, admin_port);
+        let admin_listener = TcpListener::bind(&admin_addr).await.expect("Cannot bind the admin port");
+        info!("Admin endpoints bound to {}", admin_addr);
+        let admin_app = admin_router(admin_token).layer(TraceLayer::new_for_http());
+        tokio::spawn(async move {
+            if let Err(e) = axum::serve(admin_listener, admin_app).await {
+                error!("Admin server failed: {:?}", e);
+            }
+        });
+    }
+
     // 启动 Web 服务器任务
     let server_handle = {
         tokio::spawn(async move {
             // 使用 axum::serve_with_shutdown 来启动服务器
-            let server = axum::serve(listener, app)
+            // 记录连接的对端地址，前台接口以此识别没有登录的调用方
+            let server = axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
                 .with_graceful_shutdown(async move {
                     shutdown_notify_2.notified().await
             });
@@ -212,6 +256,11 @@ async fn main() {
             }
             tokio::time::sleep(Duration::from_secs(1)).await;
         }
+
+        // 写入尚未写入数据库的问题浏览量
+        if let Err(e) = flush_question_views(&view_flush_pool).await {
+            error!("Failed to flush question views: {:?}", e);
+        }
                    
         // 发送关闭信号给服务器
         shutdown_notify_1.notify_one();
@@ -1,5 +1,6 @@
 use crate::model::db::qa::Question as DbQuestion;
 use crate::model::db::qa::Answer as DbAnswer;
+use crate::model::db::qa::Reply as DbReply;
 #[derive(Clone,Debug)]
 pub struct Question{
     pub id:i64,
@@ -16,16 +17,43 @@ pub struct Question{
     pub answer_list:Vec<Answer>,
 }
 
+/// 前台问题列表的一页
+#[derive(Clone,Debug)]
+pub struct QuestionPage{
+    pub total_records:i64,
+    pub current_pageno:i64,
+    pub page_size:i64,
+    pub total_pages:i64,
+    pub list:Vec<Question>,
+}
+
 #[derive(Clone,Debug)]
 pub struct Answer{
     pub id:i64,
     pub question_code:String,
+    /// Markdown原文
     pub answer_content:String,
+    /// 渲染后的HTML
+    pub answer_html:String,
     pub create_user_id:Option<String>,
     pub create_time:chrono::NaiveDateTime,
     pub update_time:chrono::NaiveDateTime,
     pub status:i8,
     pub creator_name:Option<String>,
+    pub helpful_count:i32,
+    pub unhelpful_count:i32,
+    /// 审核通过的追问回复（按id排序），未加载回复时为None
+    pub replies:Option<Vec<Reply>>,
+}
+
+#[derive(Clone,Debug)]
+pub struct Reply{
+    pub id:i64,
+    pub parent_id:Option<i64>,
+    pub reply_content:String,
+    pub create_user_id:Option<String>,
+    pub creator_name:Option<String>,
+    pub create_time:chrono::NaiveDateTime,
 }
 
 impl Question {
@@ -52,15 +80,33 @@ impl Question {
 
 impl Answer{
     pub fn from_db(db_answer:DbAnswer)->Self{
+        let answer_html = db_answer.html();
         Self{
             id:db_answer.id.unwrap_or(0),
             question_code:db_answer.question_code,
             answer_content:db_answer.answer_content,
+            answer_html,
             create_user_id:db_answer.create_user_id,
             creator_name:db_answer.creator_name,
             create_time:db_answer.create_time,
             update_time:db_answer.update_time,
             status:db_answer.status,
+            helpful_count:db_answer.helpful_count,
+            unhelpful_count:db_answer.unhelpful_count,
+            replies:None,
+        }
+    }
+}
+
+impl Reply{
+    pub fn from_db(db_reply:DbReply)->Self{
+        Self{
+            id:db_reply.id.unwrap_or(0),
+            parent_id:db_reply.parent_id,
+            reply_content:db_reply.reply_content,
+            create_user_id:db_reply.create_user_id,
+            creator_name:db_reply.creator_name,
+            create_time:db_reply.create_time,
         }
     }
 }
\ No newline at end of file
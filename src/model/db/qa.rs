@@ -1,5 +1,13 @@
 use sqlx::prelude::FromRow;
+use sqlx::{MySql, QueryBuilder};
+use serde::{Serialize,Deserialize};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use std::collections::HashMap;
 use crate::utils::datetime::now_local;
+use crate::utils::error::BusinessError;
+use crate::utils::locale::Locale;
+use crate::utils::markdown;
 use uuid::Uuid;
 
 pub enum QuestionStatus{
@@ -26,6 +34,35 @@ impl Into<i8> for QuestionStatus{
     }
 }
 
+/// 问题和答案的审核状态
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReviewStatus{
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl From<&i8> for ReviewStatus{
+    fn from(status:&i8)->Self{
+        match status{
+            0=>ReviewStatus::Pending,
+            1=>ReviewStatus::Approved,
+            2=>ReviewStatus::Rejected,
+            _=>ReviewStatus::Pending,
+        }
+    }
+}
+
+impl Into<i8> for ReviewStatus{
+    fn into(self) -> i8 {
+        match self {
+            ReviewStatus::Pending => 0,
+            ReviewStatus::Approved => 1,
+            ReviewStatus::Rejected => 2,
+        }
+    }
+}
+
 #[derive(FromRow,Clone,Debug, PartialEq)]
 #[allow(dead_code)]
 pub struct Question{
@@ -41,6 +78,15 @@ pub struct Question{
     pub status:i8,
     pub sort:i32,
     pub rank:i32,
+    pub review_status:i8,
+    pub reject_reason:Option<String>,
+    pub reviewer_id:Option<String>,
+    pub review_time:Option<chrono::NaiveDateTime>,
+    /// 软删除批次，与同一次删除中失效的回答相同，用于从回收站恢复
+    pub disable_batch:Option<String>,
+    pub disable_time:Option<chrono::NaiveDateTime>,
+    /// 置顶到期时间，为空表示置顶不过期
+    pub top_expire_time:Option<chrono::NaiveDateTime>,
 }
 
 impl Question{
@@ -63,6 +109,13 @@ impl Question{
             status:QuestionStatus::Active.into(),
             sort:0,
             rank,
+            review_status:ReviewStatus::Pending.into(),
+            reject_reason:None,
+            reviewer_id:None,
+            review_time:None,
+            disable_batch:None,
+            disable_time:None,
+            top_expire_time:None,
         };
         return question;
     }
@@ -84,6 +137,13 @@ impl Question{
             status:QuestionStatus::Active.into(),
             sort:0,
             rank,
+            review_status:ReviewStatus::Pending.into(),
+            reject_reason:None,
+            reviewer_id:None,
+            review_time:None,
+            disable_batch:None,
+            disable_time:None,
+            top_expire_time:None,
         };
         return question;
     }
@@ -91,6 +151,19 @@ impl Question{
     pub fn update_time(&mut self, new_date_time:chrono::NaiveDateTime){
         self.update_time = new_date_time;
     }
+
+    /// 参与排序的置顶值，置顶到期后视为未置顶
+    pub fn effective_sort(&self, now:chrono::NaiveDateTime)->i32{
+        match self.top_expire_time {
+            Some(top_expire_time) if top_expire_time <= now => 0,
+            _ => self.sort,
+        }
+    }
+}
+
+/// 追加与Question::effective_sort对应的SQL表达式，now以参数绑定，保证与游标中的置顶值一致
+pub fn push_effective_sort(builder:&mut QueryBuilder<'_, MySql>, now:chrono::NaiveDateTime){
+    builder.push("(case when `top_expire_time` is not null and `top_expire_time` <= ").push_bind(now).push(" then 0 else `sort` end)");
 }
 
 pub enum AnswerStatus{
@@ -121,12 +194,22 @@ impl Into<i8> for AnswerStatus{
 pub struct Answer{
     pub id:Option<i64>,
     pub question_code:String,
+    /// Markdown原文
     pub answer_content:String,
+    /// 写入时渲染并清理后的HTML，之前的回答为空
+    pub answer_html:Option<String>,
     pub create_user_id:Option<String>,
     pub create_time:chrono::NaiveDateTime,
     pub update_time:chrono::NaiveDateTime,
     pub status:i8,
     pub creator_name:Option<String>,
+    pub review_status:i8,
+    pub reject_reason:Option<String>,
+    pub reviewer_id:Option<String>,
+    pub review_time:Option<chrono::NaiveDateTime>,
+    pub disable_batch:Option<String>,
+    pub helpful_count:i32,
+    pub unhelpful_count:i32,
 }
 
 impl Answer{
@@ -135,15 +218,142 @@ impl Answer{
         let answer = Answer{
             id:None,
             question_code,
+            answer_html:Some(markdown::render(&answer_content)),
             answer_content,
             create_user_id,
             create_time:now,
             update_time:now,
             status:AnswerStatus::Active.into(),
             creator_name,
+            review_status:ReviewStatus::Pending.into(),
+            reject_reason:None,
+            reviewer_id:None,
+            review_time:None,
+            disable_batch:None,
+            helpful_count:0,
+            unhelpful_count:0,
         };
         return answer;
     }
+
+    /// 有效且审核通过，前台可以看到的回答
+    pub fn is_visible(&self)->bool{
+        let active: i8 = AnswerStatus::Active.into();
+        let approved: i8 = ReviewStatus::Approved.into();
+        self.status == active && self.review_status == approved
+    }
+
+    /// 回答渲染后的HTML，之前未保存HTML的回答按原文渲染
+    pub fn html(&self)->String{
+        match &self.answer_html {
+            Some(answer_html) => answer_html.clone(),
+            None => markdown::render(&self.answer_content),
+        }
+    }
+}
+
+/// 问题的回答列表整体修改时需要执行的变更
+/// 回答在原处更新，保留id、创建时间和投票计数，投票和追问回复仍指向原回答
+#[derive(Debug, Default, PartialEq)]
+pub struct AnswerChanges{
+    /// 没有id的新回答
+    pub inserted:Vec<Answer>,
+    /// 有id且与原记录不同的回答
+    pub updated:Vec<Answer>,
+    /// 不在新回答列表中的原回答id
+    pub deleted_ids:Vec<i64>,
+    /// updated中内容有变化的回答id
+    pub content_changed_ids:Vec<i64>,
+}
+
+impl AnswerChanges{
+    /// 比较原回答和新的回答列表，新回答的id为空时新增，与原记录完全相同的回答不做更新
+    pub fn diff(old_answers:&[Answer], new_answers:Vec<Answer>)->Self{
+        let mut changes = AnswerChanges::default();
+        for old_answer in old_answers{
+            if let Some(id) = old_answer.id{
+                if !new_answers.iter().any(|answer|answer.id == Some(id)){
+                    changes.deleted_ids.push(id);
+                }
+            }
+        }
+        for answer in new_answers{
+            match old_answers.iter().find(|old_answer|answer.id.is_some() && old_answer.id == answer.id){
+                Some(old_answer) if *old_answer == answer => {},
+                Some(old_answer) => {
+                    if old_answer.answer_content != answer.answer_content{
+                        changes.content_changed_ids.extend(answer.id);
+                    }
+                    changes.updated.push(answer);
+                },
+                //原记录已不存在的id按新回答插入
+                None => changes.inserted.push(Answer{ id:None, ..answer }),
+            }
+        }
+        changes
+    }
+
+    pub fn is_empty(&self)->bool{
+        self.inserted.is_empty() && self.updated.is_empty() && self.deleted_ids.is_empty()
+    }
+
+    /// 是否修改或删除了前台可以看到的原回答；新增和修改后的回答需要重新审核，不会直接展示
+    pub fn affects_visible_answers(&self, old_answers:&[Answer])->bool{
+        old_answers.iter()
+            .filter(|old_answer|old_answer.is_visible())
+            .filter_map(|old_answer|old_answer.id)
+            .any(|id|self.deleted_ids.contains(&id) || self.updated.iter().any(|answer|answer.id == Some(id)))
+    }
+
+    /// 翻译需要删除的回答id：被删除的回答和内容有变化的回答
+    pub fn stale_translation_ids(&self)->Vec<i64>{
+        self.deleted_ids.iter().chain(&self.content_changed_ids).copied().collect()
+    }
+}
+
+/// 回答下的追问回复，parent_id为空时直接回复回答
+#[derive(FromRow,Clone,Debug, PartialEq)]
+pub struct Reply{
+    pub id:Option<i64>,
+    pub answer_id:i64,
+    pub parent_id:Option<i64>,
+    pub question_code:String,
+    /// 回复层级，直接回复回答为1
+    pub depth:i32,
+    pub reply_content:String,
+    pub create_user_id:Option<String>,
+    pub creator_name:Option<String>,
+    pub status:i8,
+    pub review_status:i8,
+    pub reject_reason:Option<String>,
+    pub reviewer_id:Option<String>,
+    pub review_time:Option<chrono::NaiveDateTime>,
+    pub create_time:chrono::NaiveDateTime,
+    pub update_time:chrono::NaiveDateTime,
+}
+
+impl Reply{
+    /// 新建待审核的回复，parent为空时直接回复回答
+    pub fn new(answer_id:i64,question_code:String,parent:Option<&Reply>,reply_content:String,create_user_id:Option<String>,creator_name:Option<String>)->Self{
+        let now = now_local();
+        Reply{
+            id:None,
+            answer_id,
+            parent_id:parent.and_then(|parent|parent.id),
+            question_code,
+            depth:parent.map(|parent|parent.depth+1).unwrap_or(1),
+            reply_content,
+            create_user_id,
+            creator_name,
+            status:AnswerStatus::Active.into(),
+            review_status:ReviewStatus::Pending.into(),
+            reject_reason:None,
+            reviewer_id:None,
+            review_time:None,
+            create_time:now,
+            update_time:now,
+        }
+    }
 }
 
 #[derive(Clone,Debug, PartialEq)]
@@ -180,4 +390,601 @@ impl<T> Page<T>{
             data,
         }
     }
-}
\ No newline at end of file
+}
+
+/// 回答投票：有用或没用
+#[derive(Serialize,Deserialize,Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum Vote{
+    Helpful,
+    Unhelpful,
+}
+
+impl From<&i8> for Vote{
+    fn from(vote:&i8)->Self{
+        match vote{
+            1=>Vote::Helpful,
+            _=>Vote::Unhelpful,
+        }
+    }
+}
+
+impl Into<i8> for Vote{
+    fn into(self) -> i8 {
+        match self {
+            Vote::Helpful => 1,
+            Vote::Unhelpful => -1,
+        }
+    }
+}
+
+impl Vote{
+    /// 用户从old改投为self时，有用票数和没用票数的变化量；重复投同一票时不变
+    pub fn counter_delta(&self, old:Option<Vote>)->(i32, i32){
+        let count = |vote:Option<Vote>|match vote {
+            Some(Vote::Helpful) => (1, 0),
+            Some(Vote::Unhelpful) => (0, 1),
+            None => (0, 0),
+        };
+        let (new_helpful, new_unhelpful) = count(Some(*self));
+        let (old_helpful, old_unhelpful) = count(old);
+        (new_helpful - old_helpful, new_unhelpful - old_unhelpful)
+    }
+}
+
+/// 审核记录，每次审核决定记录一条
+#[derive(FromRow,Clone,Debug, PartialEq)]
+pub struct ReviewLog{
+    pub id:Option<i64>,
+    pub target_type:String,
+    pub target_id:i64,
+    pub question_code:String,
+    pub review_status:i8,
+    pub reject_reason:Option<String>,
+    pub reviewer_id:String,
+    pub reviewer_name:Option<String>,
+    pub create_time:chrono::NaiveDateTime,
+}
+
+/// 待审核队列中的一项，问题和答案共用
+#[derive(FromRow,Clone,Debug, PartialEq)]
+pub struct ReviewItem{
+    pub id:i64,
+    pub question_code:String,
+    pub product_code:String,
+    pub content:String,
+    pub create_user_id:Option<String>,
+    pub creator_name:Option<String>,
+    pub create_time:chrono::NaiveDateTime,
+    pub review_status:i8,
+    pub reject_reason:Option<String>,
+}
+
+/// 全文检索命中的问题，score为问题内容与回答内容的相关度之和
+#[derive(FromRow,Clone,Debug, PartialEq)]
+pub struct SearchHit{
+    #[sqlx(flatten)]
+    pub question:Question,
+    pub score:f64,
+}
+
+/// 一个商品的回答时效统计，时效为问题创建到第一个有效回答的秒数
+#[derive(FromRow,Clone,Debug, PartialEq)]
+pub struct AnswerLatencyStat{
+    pub product_code:String,
+    pub question_count:i64,
+    pub answered_count:i64,
+    pub total_latency_seconds:Option<i64>,
+    pub min_latency_seconds:Option<i64>,
+    pub max_latency_seconds:Option<i64>,
+}
+
+/// 触发订阅通知的事件
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NotificationEvent{
+    /// 问题新增了回答
+    AnswerAdded,
+    /// 问题或已有回答被更新
+    QuestionUpdated,
+}
+
+impl NotificationEvent{
+    pub fn as_str(&self)->&'static str{
+        match self {
+            NotificationEvent::AnswerAdded => "answerAdded",
+            NotificationEvent::QuestionUpdated => "questionUpdated",
+        }
+    }
+}
+
+/// 通知的发送状态
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DeliveryStatus{
+    Pending,
+    Delivered,
+    Failed,
+    /// 已被某个实例领取正在发送，领取到期后仍未记录结果时可以被重新领取
+    Sending,
+}
+
+impl From<&i8> for DeliveryStatus{
+    fn from(status:&i8)->Self{
+        match status{
+            1=>DeliveryStatus::Delivered,
+            2=>DeliveryStatus::Failed,
+            3=>DeliveryStatus::Sending,
+            _=>DeliveryStatus::Pending,
+        }
+    }
+}
+
+impl Into<i8> for DeliveryStatus{
+    fn into(self) -> i8 {
+        match self {
+            DeliveryStatus::Pending => 0,
+            DeliveryStatus::Delivered => 1,
+            DeliveryStatus::Failed => 2,
+            DeliveryStatus::Sending => 3,
+        }
+    }
+}
+
+/// 一条订阅通知及其发送状态，payload为JSON格式的通知内容
+#[derive(FromRow,Clone,Debug, PartialEq)]
+pub struct Notification{
+    pub id:i64,
+    pub subscription_id:i64,
+    pub question_code:String,
+    pub subscriber_id:String,
+    pub event_type:String,
+    pub payload:String,
+    pub status:i8,
+    pub attempt_count:i32,
+    pub next_retry_time:chrono::NaiveDateTime,
+    pub last_error:Option<String>,
+    pub create_time:chrono::NaiveDateTime,
+    pub update_time:chrono::NaiveDateTime,
+}
+
+/// 游标分页的结果，next_cursor为None表示已经是最后一页
+#[derive(Clone,Debug, PartialEq)]
+pub struct CursorPage<T>{
+    pub total_records:Option<i64>,
+    pub page_size:i64,
+    pub next_cursor:Option<String>,
+    pub data:Vec<T>,
+}
+
+/// 问题列表的游标，记录上一页最后一条记录的排序字段
+/// 排序规则与列表查询一致：`sort` desc,`rank`,`create_time` desc,`id`，其中sort为未到期的置顶值
+#[derive(Serialize,Deserialize,Clone,Debug, PartialEq)]
+pub struct QuestionCursor{
+    pub sort:i32,
+    pub rank:i32,
+    pub create_time:chrono::NaiveDateTime,
+    pub id:i64,
+}
+
+impl QuestionCursor{
+    pub fn from_question(question:&Question, now:chrono::NaiveDateTime)->Self{
+        Self{
+            sort:question.effective_sort(now),
+            rank:question.rank,
+            create_time:question.create_time,
+            id:question.id.unwrap_or(0),
+        }
+    }
+
+    /// 编码为对调用方不透明的字符串
+    pub fn encode(&self)->String{
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    pub fn decode(cursor:&str)->Result<Self, BusinessError>{
+        URL_SAFE_NO_PAD.decode(cursor).ok()
+            .and_then(|bytes|serde_json::from_slice::<QuestionCursor>(&bytes).ok())
+            .ok_or_else(||{
+                let mut parameters = HashMap::new();
+                parameters.insert("field".to_string(), "cursor".to_string());
+                parameters.insert("message".to_string(), "cursor不合法".to_string());
+                BusinessError::InvalidParameter((None,Some(vec![parameters])))
+            })
+    }
+}
+
+/// 问题修订记录的触发动作
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RevisionAction{
+    Create,
+    Update,
+    Top,
+    CancelTop,
+    Disable,
+    Restore,
+    Reorder,
+    Merge,
+}
+
+impl RevisionAction{
+    pub fn as_str(&self)->&'static str{
+        match self {
+            RevisionAction::Create => "create",
+            RevisionAction::Update => "update",
+            RevisionAction::Top => "top",
+            RevisionAction::CancelTop => "cancelTop",
+            RevisionAction::Disable => "disable",
+            RevisionAction::Restore => "restore",
+            RevisionAction::Reorder => "reorder",
+            RevisionAction::Merge => "merge",
+        }
+    }
+}
+
+/// 问题修订记录，snapshot为变更后问题及其全部回答的JSON快照
+#[derive(FromRow,Clone,Debug, PartialEq)]
+pub struct QuestionRevision{
+    pub id:Option<i64>,
+    pub question_code:String,
+    pub revision:i32,
+    pub action:String,
+    pub snapshot:String,
+    pub operator_id:Option<String>,
+    pub create_time:chrono::NaiveDateTime,
+}
+
+impl QuestionRevision{
+    pub fn snapshot(&self)->Result<QuestionSnapshot, BusinessError>{
+        serde_json::from_str(&self.snapshot).map_err(|error|BusinessError::InternalServerError(
+            (Some(format!("修订记录快照解析失败：{}",error)),None)
+        ))
+    }
+}
+
+/// 问题及其回答在某一时刻的快照
+#[derive(Serialize,Deserialize,Clone,Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuestionSnapshot{
+    pub sku_code:Option<String>,
+    pub product_code:String,
+    pub question_content:String,
+    pub status:i8,
+    pub sort:i32,
+    pub rank:i32,
+    pub review_status:i8,
+    pub answers:Vec<AnswerSnapshot>,
+}
+
+#[derive(Serialize,Deserialize,Clone,Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnswerSnapshot{
+    /// 回答id，之前记录的快照中没有
+    #[serde(default)]
+    pub id:Option<i64>,
+    pub answer_content:String,
+    pub create_user_id:Option<String>,
+    pub creator_name:Option<String>,
+    pub status:i8,
+    pub review_status:i8,
+}
+
+/// 两个快照之间一个字段的差异，值为None表示该字段在对应快照中不存在
+#[derive(Serialize,Clone,Debug, PartialEq)]
+pub struct SnapshotChange{
+    pub field:String,
+    #[serde(rename = "oldValue")]
+    pub old_value:Option<String>,
+    #[serde(rename = "newValue")]
+    pub new_value:Option<String>,
+}
+
+impl QuestionSnapshot{
+    pub fn from_db(question:&Question, answers:&[Answer])->Self{
+        Self{
+            sku_code:question.sku_code.clone(),
+            product_code:question.product_code.clone(),
+            question_content:question.question_content.clone(),
+            status:question.status,
+            sort:question.sort,
+            rank:question.rank,
+            review_status:question.review_status,
+            answers:answers.iter().map(AnswerSnapshot::from_db).collect(),
+        }
+    }
+
+    /// 比较两个快照，回答按顺序逐条比较，多出或缺少的回答整条列出
+    pub fn diff(&self, other:&QuestionSnapshot)->Vec<SnapshotChange>{
+        let mut changes = vec![];
+        push_change(&mut changes, "skuCode", self.sku_code.clone(), other.sku_code.clone());
+        push_change(&mut changes, "productCode", Some(self.product_code.clone()), Some(other.product_code.clone()));
+        push_change(&mut changes, "questionContent", Some(self.question_content.clone()), Some(other.question_content.clone()));
+        push_change(&mut changes, "status", Some(self.status.to_string()), Some(other.status.to_string()));
+        push_change(&mut changes, "sort", Some(self.sort.to_string()), Some(other.sort.to_string()));
+        push_change(&mut changes, "rank", Some(self.rank.to_string()), Some(other.rank.to_string()));
+        push_change(&mut changes, "reviewStatus", Some(self.review_status.to_string()), Some(other.review_status.to_string()));
+        let answer_count = self.answers.len().max(other.answers.len());
+        for index in 0..answer_count{
+            let old_answer = self.answers.get(index);
+            let new_answer = other.answers.get(index);
+            let field = |name:&str|format!("answers[{}].{}", index, name);
+            push_change(&mut changes, &field("answerContent"), old_answer.map(|answer|answer.answer_content.clone()), new_answer.map(|answer|answer.answer_content.clone()));
+            push_change(&mut changes, &field("status"), old_answer.map(|answer|answer.status.to_string()), new_answer.map(|answer|answer.status.to_string()));
+            push_change(&mut changes, &field("reviewStatus"), old_answer.map(|answer|answer.review_status.to_string()), new_answer.map(|answer|answer.review_status.to_string()));
+        }
+        changes
+    }
+}
+
+impl AnswerSnapshot{
+    pub fn from_db(answer:&Answer)->Self{
+        Self{
+            id:answer.id,
+            answer_content:answer.answer_content.clone(),
+            create_user_id:answer.create_user_id.clone(),
+            creator_name:answer.creator_name.clone(),
+            status:answer.status,
+            review_status:answer.review_status,
+        }
+    }
+
+    /// 恢复修订时按快照生成回答记录，快照中的回答仍存在时在原记录上恢复，保留id和投票计数
+    pub fn into_db_answer(&self, question_code:String, current_answers:&[Answer], update_time:chrono::NaiveDateTime)->Answer{
+        let current_answer = current_answers.iter().find(|answer|self.id.is_some() && answer.id == self.id);
+        let mut answer = match current_answer {
+            Some(current_answer) => current_answer.clone(),
+            None => Answer::new(question_code, self.answer_content.clone(), self.create_user_id.clone(), self.creator_name.clone()),
+        };
+        if answer.answer_content != self.answer_content{
+            answer.answer_html = Some(markdown::render(&self.answer_content));
+            answer.answer_content = self.answer_content.clone();
+        }
+        answer.status = self.status;
+        answer.review_status = self.review_status;
+        if current_answer != Some(&answer){
+            answer.update_time = update_time;
+        }
+        answer
+    }
+}
+
+fn push_change(changes:&mut Vec<SnapshotChange>, field:&str, old_value:Option<String>, new_value:Option<String>){
+    if old_value != new_value {
+        changes.push(SnapshotChange{
+            field:field.to_string(),
+            old_value,
+            new_value,
+        });
+    }
+}
+
+/// 问题内容的翻译，locale为Locale::as_str()的值
+#[derive(FromRow,Clone,Debug)]
+pub struct QuestionTranslation{
+    pub question_code:String,
+    pub locale:String,
+    pub question_content:String,
+    pub update_time:chrono::NaiveDateTime,
+}
+
+/// 回答内容的翻译
+#[derive(FromRow,Clone,Debug)]
+pub struct AnswerTranslation{
+    pub answer_id:i64,
+    pub question_code:String,
+    pub locale:String,
+    pub answer_content:String,
+    pub update_time:chrono::NaiveDateTime,
+}
+
+/// 一批问题、回答的翻译，按语言回退链替换问题和回答的内容
+#[derive(Default)]
+pub struct Translations{
+    questions:HashMap<(String,String), String>,
+    answers:HashMap<(i64,String), String>,
+}
+
+impl Translations{
+    pub fn new(question_translations:Vec<QuestionTranslation>, answer_translations:Vec<AnswerTranslation>)->Self{
+        Self{
+            questions:question_translations.into_iter()
+                .map(|translation|((translation.question_code, translation.locale), translation.question_content))
+                .collect(),
+            answers:answer_translations.into_iter()
+                .map(|translation|((translation.answer_id, translation.locale), translation.answer_content))
+                .collect(),
+        }
+    }
+
+    /// 按回退链依次查找翻译，到默认语言时使用原始内容
+    pub fn apply(&self, question:&mut Question, answers:&mut [Answer], chain:&[Locale]){
+        if let Some(content) = Self::lookup(chain, |locale|self.questions.get(&(question.question_code.clone(), locale.as_str().to_string()))){
+            question.question_content = content.clone();
+        }
+        for answer in answers{
+            let id = answer.id.unwrap_or(0);
+            if let Some(content) = Self::lookup(chain, |locale|self.answers.get(&(id, locale.as_str().to_string()))){
+                answer.answer_content = content.clone();
+                answer.answer_html = Some(markdown::render(content));
+            }
+        }
+    }
+
+    fn lookup<'a>(chain:&[Locale], find:impl Fn(&Locale)->Option<&'a String>)->Option<&'a String>{
+        for locale in chain{
+            if locale.is_default(){
+                return None;
+            }
+            if let Some(content) = find(locale){
+                return Some(content);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test{
+    use super::*;
+    use chrono::{NaiveDate,NaiveTime,NaiveDateTime};
+
+    #[test]
+    fn test_question_cursor_encode_decode(){
+        let d = NaiveDate::from_ymd_opt(2014, 10, 22).unwrap();
+        let t = NaiveTime::from_hms_milli_opt(12, 34, 56, 000).unwrap();
+        let cursor = QuestionCursor{
+            sort:3,
+            rank:1,
+            create_time:NaiveDateTime::new(d, t),
+            id:1251,
+        };
+        let encoded = cursor.encode();
+        let decoded = QuestionCursor::decode(&encoded).unwrap();
+        assert_eq!(decoded,cursor);
+    }
+
+    #[test]
+    fn test_question_cursor_decode_invalid(){
+        let result = QuestionCursor::decode("not a cursor");
+        match result {
+            Err(BusinessError::InvalidParameter((_,errors_parameters))) => {
+                let error_parameters = errors_parameters.unwrap();
+                assert_eq!(error_parameters[0].get("field").unwrap(),"cursor");
+            }
+            _ => {
+                panic!("错误类型不匹配")
+            }
+        }
+    }
+
+    #[test]
+    fn test_question_snapshot_diff(){
+        let question = Question::new(None,"product_code".to_string(),"会缩水吗".to_string(),None,None,1);
+        let answers = vec![
+            Answer::new(question.question_code.clone(),"不会".to_string(),None,None),
+        ];
+        let old_snapshot = QuestionSnapshot::from_db(&question, &answers);
+        assert_eq!(old_snapshot.diff(&old_snapshot), vec![]);
+
+        let mut new_snapshot = old_snapshot.clone();
+        new_snapshot.question_content = "洗后会缩水吗".to_string();
+        new_snapshot.sort = 2;
+        new_snapshot.answers.push(AnswerSnapshot::from_db(&Answer::new(question.question_code.clone(),"建议冷水洗".to_string(),None,None)));
+        let changes = old_snapshot.diff(&new_snapshot);
+        let fields: Vec<&str> = changes.iter().map(|change|change.field.as_str()).collect();
+        assert_eq!(fields, vec!["questionContent","sort","answers[1].answerContent","answers[1].status","answers[1].reviewStatus"]);
+        assert_eq!(changes[0].old_value, Some("会缩水吗".to_string()));
+        assert_eq!(changes[0].new_value, Some("洗后会缩水吗".to_string()));
+        assert_eq!(changes[2].old_value, None);
+        assert_eq!(changes[2].new_value, Some("建议冷水洗".to_string()));
+    }
+
+    #[test]
+    fn test_effective_sort(){
+        let d = NaiveDate::from_ymd_opt(2024, 11, 11).unwrap();
+        let now = NaiveDateTime::new(d, NaiveTime::from_hms_opt(12, 0, 0).unwrap());
+        let mut question = Question::new(None,"product_code".to_string(),"question_content".to_string(),None,None,1);
+        question.sort = 3;
+        assert_eq!(question.effective_sort(now), 3);
+        question.top_expire_time = Some(NaiveDateTime::new(d, NaiveTime::from_hms_opt(12, 0, 1).unwrap()));
+        assert_eq!(question.effective_sort(now), 3);
+        question.top_expire_time = Some(now);
+        assert_eq!(question.effective_sort(now), 0);
+        assert_eq!(QuestionCursor::from_question(&question, now).sort, 0);
+        let mut builder = QueryBuilder::<MySql>::new("");
+        push_effective_sort(&mut builder, now);
+        assert_eq!(builder.sql(), "(case when `top_expire_time` is not null and `top_expire_time` <= ? then 0 else `sort` end)");
+    }
+
+    #[test]
+    fn test_vote_counter_delta(){
+        assert_eq!(Vote::Helpful.counter_delta(None), (1, 0));
+        assert_eq!(Vote::Unhelpful.counter_delta(None), (0, 1));
+        assert_eq!(Vote::Helpful.counter_delta(Some(Vote::Helpful)), (0, 0));
+        assert_eq!(Vote::Helpful.counter_delta(Some(Vote::Unhelpful)), (1, -1));
+        assert_eq!(Vote::Unhelpful.counter_delta(Some(Vote::Helpful)), (-1, 1));
+    }
+
+    #[test]
+    fn test_translations_apply(){
+        let now = now_local();
+        let mut question = Question::new(None,"product_code".to_string(),"会缩水吗".to_string(),None,None,1);
+        let mut answers = vec![
+            Answer::new(question.question_code.clone(),"不会".to_string(),None,None),
+            Answer::new(question.question_code.clone(),"洗后略有缩水".to_string(),None,None),
+        ];
+        answers[0].id = Some(1);
+        answers[1].id = Some(2);
+        let translations = Translations::new(
+            vec![QuestionTranslation{question_code:question.question_code.clone(),locale:"zh-HK".to_string(),question_content:"會縮水嗎".to_string(),update_time:now}],
+            vec![AnswerTranslation{answer_id:1,question_code:question.question_code.clone(),locale:"en".to_string(),answer_content:"No".to_string(),update_time:now}],
+        );
+        //英文没有问题的翻译时回退到繁体，第二个回答没有任何翻译时保留原文
+        translations.apply(&mut question, &mut answers, &[Locale::En, Locale::ZhHk, Locale::ZhCn]);
+        assert_eq!(question.question_content, "會縮水嗎");
+        assert_eq!(answers[0].answer_content, "No");
+        assert_eq!(answers[1].answer_content, "洗后略有缩水");
+    }
+
+    #[test]
+    fn test_answer_changes_diff(){
+        let mut old_answers = vec![
+            Answer::new("q1".to_string(),"不会".to_string(),None,None),
+            Answer::new("q1".to_string(),"洗后略有缩水".to_string(),None,None),
+            Answer::new("q1".to_string(),"看面料".to_string(),None,None),
+        ];
+        for (index, answer) in old_answers.iter_mut().enumerate(){
+            answer.id = Some(index as i64 + 1);
+            answer.helpful_count = 3;
+        }
+        //第一个回答不变，第二个回答修改内容，第三个回答被移除，另新增一个回答
+        let mut changed = old_answers[1].clone();
+        changed.answer_content = "会缩水".to_string();
+        let added = Answer::new("q1".to_string(),"建议冷水洗".to_string(),None,None);
+        let changes = AnswerChanges::diff(&old_answers, vec![old_answers[0].clone(), changed.clone(), added.clone()]);
+        assert_eq!(changes.deleted_ids, vec![3]);
+        assert_eq!(changes.updated, vec![changed]);
+        assert_eq!(changes.updated[0].helpful_count, 3);
+        assert_eq!(changes.inserted, vec![added]);
+        assert_eq!(changes.stale_translation_ids(), vec![3, 2]);
+
+        let changes = AnswerChanges::diff(&old_answers, old_answers.clone());
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn test_answer_changes_affects_visible_answers(){
+        let mut approved = Answer::new("q1".to_string(),"不会".to_string(),None,None);
+        approved.id = Some(1);
+        approved.review_status = ReviewStatus::Approved.into();
+        let mut pending = Answer::new("q1".to_string(),"会缩水".to_string(),None,None);
+        pending.id = Some(2);
+        let old_answers = vec![approved.clone(), pending.clone()];
+        //只新增回答或修改待审核的回答，前台看不到变化
+        let added = Answer::new("q1".to_string(),"看面料".to_string(),None,None);
+        let mut changed_pending = pending.clone();
+        changed_pending.answer_content = "洗后略有缩水".to_string();
+        let changes = AnswerChanges::diff(&old_answers, vec![approved.clone(), changed_pending, added]);
+        assert!(!changes.affects_visible_answers(&old_answers));
+        //删除审核通过的回答
+        let changes = AnswerChanges::diff(&old_answers, vec![pending]);
+        assert!(changes.affects_visible_answers(&old_answers));
+    }
+
+    #[test]
+    fn test_answer_snapshot_restore_keeps_id(){
+        let update_time = now_local();
+        let mut current = Answer::new("q1".to_string(),"会缩水".to_string(),None,None);
+        current.id = Some(7);
+        current.helpful_count = 5;
+        let mut snapshot = AnswerSnapshot::from_db(&current);
+        snapshot.answer_content = "不会".to_string();
+        let restored = snapshot.into_db_answer("q1".to_string(), &[current.clone()], update_time);
+        assert_eq!(restored.id, Some(7));
+        assert_eq!(restored.helpful_count, 5);
+        assert_eq!(restored.answer_content, "不会");
+        assert_eq!(restored.update_time, update_time);
+        //快照中的回答已被删除时生成新的回答
+        let restored = snapshot.into_db_answer("q1".to_string(), &[], update_time);
+        assert_eq!(restored.helpful_count, 0);
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Serialize,Deserialize};
+use validator::Validate;
+
+/// 一次最多返回的缓存key数量的默认值
+pub const DEFAULT_CACHE_KEY_LIMIT: usize = 100;
+
+/// 按前缀查询缓存命名空间中的key，prefix为空时查询全部key
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct FindCacheKeyList {
+    #[validate(length(min = 1, message = "namespace不能为空"))]
+    pub namespace: String,
+    #[serde(default)]
+    pub prefix: String,
+    #[validate(range(min = 1, max = 1000, message = "limit应该在1到1000之间"))]
+    pub limit: Option<usize>,
+}
+
+/// 查询单个缓存项
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct GetCacheEntry {
+    #[validate(length(min = 1, message = "namespace不能为空"))]
+    pub namespace: String,
+    #[validate(length(min = 1, message = "key不能为空"))]
+    pub key: String,
+}
+
+/// 使单个缓存项失效
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct EvictCacheKey {
+    #[validate(length(min = 1, message = "namespace不能为空"))]
+    pub namespace: String,
+    #[validate(length(min = 1, message = "key不能为空"))]
+    pub key: String,
+}
+
+/// 使key以prefix开头的缓存项失效，清空整个命名空间请使用FlushCache
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct EvictCachePrefix {
+    #[validate(length(min = 1, message = "namespace不能为空"))]
+    pub namespace: String,
+    #[validate(length(min = 1, message = "prefix不能为空"))]
+    pub prefix: String,
+}
+
+/// 清空缓存命名空间
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct FlushCache {
+    #[validate(length(min = 1, message = "namespace不能为空"))]
+    pub namespace: String,
+}
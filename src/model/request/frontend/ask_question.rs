@@ -0,0 +1,114 @@
+use serde::{Serialize,Deserialize};
+use validator::Validate;
+use crate::model::request::operation::create_question::CreateQuestion;
+use crate::utils::error::BusinessError;
+use crate::utils::default_value::deserialize_null_to_empty_string;
+
+/// 前台用户针对商品提问，提交后进入待审核状态
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct AskQuestion {
+    #[serde(rename = "skuCode")]
+    pub sku_code: Option<String>,
+    #[validate(length(min = 1, message = "productCode不能为空"))]
+    #[serde(rename = "productCode")]
+    pub product_code: String,
+    #[validate(length(min = 5, max = 500, message = "questionContent长度应该在5到500个字符之间"))]
+    #[serde(rename = "questionContent")]
+    pub question_content: String,
+    #[validate(length(min = 1, message = "createUserId不能为空"))]
+    #[serde(rename = "createUserId")]
+    pub create_user_id: String,
+    #[validate(length(max = 100, message = "creatorName不能超过100个字符"))]
+    #[serde(rename = "creatorName", default, deserialize_with = "deserialize_null_to_empty_string")]
+    pub creator_name: String,
+}
+
+impl AskQuestion{
+    /// 转换为运营端的创建问题请求，复用其校验和入库逻辑
+    pub fn into_create_question(&self)->CreateQuestion{
+        CreateQuestion{
+            sku_code: self.sku_code.clone(),
+            product_code: self.product_code.trim().to_string(),
+            question_content: self.question_content.trim().to_string(),
+            create_user_id: self.create_user_id.trim().to_string(),
+            creator_name: self.creator_name.trim().to_string(),
+            rank: 1,
+            answer_list: vec![],
+            tags: vec![],
+        }
+    }
+
+    pub async fn custom_validate(&self)->Result<(), BusinessError>{
+        self.validate()?;
+        //去掉首尾空白后再按运营端的规则校验一次
+        self.into_create_question().validate_question()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::request::frontend::ask_question::AskQuestion;
+    use crate::utils::error::BusinessError;
+    use crate::utils::logging::init_log;
+
+    fn assert_invalid_field(result: Result<(), BusinessError>, field: &str, message: &str) {
+        assert_eq!(result.is_err(), true);
+        if let Err(error) = result {
+            match error {
+                BusinessError::InvalidParameter ((_,errors_parameters)) => {
+                    let error_parameters = errors_parameters.unwrap();
+                    let error_parameters0 = error_parameters.get(0).unwrap();
+                    assert_eq!(error_parameters0.get("field").unwrap(),field);
+                    assert_eq!(error_parameters0.get("message").unwrap(),message);
+                }
+                _ => {
+                    panic!("错误类型不匹配")
+                }
+            }
+        }
+    }
+
+    fn ask_question(question_content: &str, create_user_id: &str) -> AskQuestion {
+        AskQuestion {
+            sku_code: Some("sku_code".to_string()),
+            product_code: "product_code".to_string(),
+            question_content: question_content.to_string(),
+            create_user_id: create_user_id.to_string(),
+            creator_name: "creator_name".to_string(),
+        }
+    }
+
+    //模拟question_content过短的情况
+    #[tokio::test]
+    async fn test_validate_question_content_too_short() {
+        init_log().await;
+        let question = ask_question("abc", "create_user_id");
+        assert_invalid_field(question.custom_validate().await, "questionContent", "questionContent长度应该在5到500个字符之间");
+    }
+
+    //模拟question_content过长的情况
+    #[tokio::test]
+    async fn test_validate_question_content_too_long() {
+        init_log().await;
+        let question = ask_question(&"问".repeat(501), "create_user_id");
+        assert_invalid_field(question.custom_validate().await, "questionContent", "questionContent长度应该在5到500个字符之间");
+    }
+
+    //模拟create_user_id只有空白的情况，去掉空白后应按运营端规则校验失败
+    #[tokio::test]
+    async fn test_validate_create_user_id_blank() {
+        init_log().await;
+        let question = ask_question("这件衣服会缩水吗？", "  ");
+        assert_invalid_field(question.custom_validate().await, "createUserId", "createUserId不能为空");
+    }
+
+    //用户提问不需要带回答
+    #[tokio::test]
+    async fn test_validate_without_answers() {
+        init_log().await;
+        let question = ask_question("这件衣服会缩水吗？", "create_user_id");
+        assert_eq!(question.custom_validate().await.is_ok(), true);
+        assert_eq!(question.into_create_question().answer_list.len(), 0);
+    }
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// 前台按商品（可选按SKU）分页查询问题列表
+#[derive(Deserialize,Debug,Serialize,Validate,Clone)]
+pub struct FindQuestionList{
+    #[validate(length(min = 1, message = "productCode不能为空"))]
+    #[serde(rename="productCode")]
+    pub product_code:String,
+    #[serde(rename="skuCode")]
+    pub sku_code:Option<String>,
+    /// 标签名称，只查询带有该标签的问题
+    pub tag:Option<String>,
+    #[serde(rename = "pageNum")]
+    #[validate(range(min = 1, message = "页面参数不合法，pageNum应该是大于等于1的整数"))]
+    pub current_pageno:i64,
+    #[serde(rename = "pageSize")]
+    #[validate(range(min = 1, max = 50, message = "页面参数不合法，pageSize应该是1到50之间的整数"))]
+    pub page_size:i64,
+}
+
+#[cfg(test)]
+mod test {
+    use validator::Validate;
+
+    use crate::model::request::frontend::find_question_list::FindQuestionList;
+    use crate::utils::error::BusinessError;
+    use crate::utils::logging::init_log;
+
+    //模拟page_size超出范围的情况
+    #[tokio::test]
+    async fn test_validate_page_size() {
+        init_log().await;
+        let possible_values = vec![0, 51];
+        for value in possible_values{
+            let request = FindQuestionList {
+                product_code: "product_code".to_string(),
+                sku_code: None,
+                tag: None,
+                current_pageno: 1,
+                page_size: value,
+            };
+            let result = request.validate();
+            assert_eq!(result.is_err(), true);
+            if let Err(validation_errors) = result {
+                let business_error = validation_errors.into();
+                match business_error {
+                    BusinessError::InvalidParameter ((_,errors_parameters)) => {
+                        let error_parameters = errors_parameters.unwrap();
+                        let error_parameters0 = error_parameters.get(0).unwrap();
+                        assert_eq!(error_parameters0.get("field").unwrap(),"pageSize");
+                        assert_eq!(error_parameters0.get("message").unwrap(),"页面参数不合法，pageSize应该是1到50之间的整数");
+                    }
+                    _ => {
+                        panic!("错误类型不匹配")
+                    }
+                }
+            }
+        }
+
+        let request = FindQuestionList {
+            product_code: "product_code".to_string(),
+            sku_code: Some("sku_code".to_string()),
+            tag: Some("shipping".to_string()),
+            current_pageno: 1,
+            page_size: 50,
+        };
+        assert_eq!(request.validate().is_err(), false);
+    }
+}
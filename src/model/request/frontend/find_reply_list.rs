@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// 前台分页查询回答下的追问回复，按一级回复分页，每个一级回复附带全部下级回复
+#[derive(Deserialize,Debug,Serialize,Validate,Clone)]
+pub struct FindReplyList{
+    #[validate(range(min = 1, message = "answerId应该是大于等于1的整数"))]
+    #[serde(rename="answerId")]
+    pub answer_id:i64,
+    #[serde(rename = "pageNum")]
+    #[validate(range(min = 1, message = "页面参数不合法，pageNum应该是大于等于1的整数"))]
+    pub current_pageno:i64,
+    #[serde(rename = "pageSize")]
+    #[validate(range(min = 1, max = 50, message = "页面参数不合法，pageSize应该是1到50之间的整数"))]
+    pub page_size:i64,
+}
@@ -0,0 +1,11 @@
+use serde::{Serialize,Deserialize};
+use validator::Validate;
+use crate::model::request::operation::build_faq_snapshot::validate_product_code;
+
+/// 获取商品的FAQ静态快照，请求头If-None-Match与快照的ETag相同时返回304
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct GetFaqSnapshot {
+    #[validate(custom = "validate_product_code")]
+    #[serde(rename = "productCode")]
+    pub product_code: String,
+}
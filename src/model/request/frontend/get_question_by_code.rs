@@ -1,6 +1,37 @@
 use serde::{Deserialize, Serialize};
-#[derive(Deserialize,Debug,Serialize)]
+use validator::Validate;
+#[derive(Deserialize,Debug,Serialize,Validate)]
 pub struct GetQuestionByCode{
     #[serde(rename="questionCode")]
-    pub question_code:String
+    pub question_code:String,
+    /// 回答的排序方式，为空时使用环境变量FRONTEND_ANSWER_SORT配置的默认排序
+    #[serde(rename="answerSort")]
+    pub answer_sort:Option<AnswerSort>,
+    /// 每个回答返回的一级追问回复数量，为空时返回5条，更多回复通过findReplyList分页查询
+    #[serde(rename="replyPageSize")]
+    #[validate(range(min = 1, max = 50, message = "replyPageSize应该是1到50之间的整数"))]
+    pub reply_page_size:Option<i64>,
+}
+
+/// 每个回答默认返回的一级追问回复数量
+pub const DEFAULT_REPLY_PAGE_SIZE: i64 = 5;
+
+/// 前台回答的排序方式
+#[derive(Deserialize,Debug,Serialize,Clone,Copy,PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum AnswerSort{
+    /// 按回答创建顺序
+    Default,
+    /// 按有用票数减去没用票数从高到低，相同时有用票数多的在前
+    Helpfulness,
+}
+
+impl AnswerSort{
+    pub fn from_env_value(value:&str)->Option<Self>{
+        match value {
+            "default" => Some(AnswerSort::Default),
+            "helpfulness" => Some(AnswerSort::Helpfulness),
+            _ => None,
+        }
+    }
 }
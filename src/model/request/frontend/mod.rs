@@ -1,2 +1,10 @@
 pub mod find_sku;
-pub mod get_question_by_code;
\ No newline at end of file
+pub mod get_question_by_code;
+pub mod ask_question;
+pub mod find_question_list;
+pub mod search_question;
+pub mod vote_answer;
+pub mod reply_answer;
+pub mod find_reply_list;
+pub mod get_faq_snapshot;
+pub mod subscribe_question;
\ No newline at end of file
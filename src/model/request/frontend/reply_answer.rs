@@ -0,0 +1,62 @@
+use serde::{Serialize,Deserialize};
+use validator::Validate;
+use crate::utils::error::BusinessError;
+use crate::utils::default_value::deserialize_null_to_empty_string;
+use crate::utils::sensitive_word;
+
+/// 前台用户针对某个回答追问，或者回复其它追问，提交后进入待审核状态
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct ReplyAnswer {
+    #[validate(range(min = 1, message = "answerId应该是大于等于1的整数"))]
+    #[serde(rename = "answerId")]
+    pub answer_id: i64,
+    /// 回复的上级回复id，为空时直接回复回答
+    #[serde(rename = "parentId")]
+    pub parent_id: Option<i64>,
+    #[validate(length(min = 2, max = 500, message = "replyContent长度应该在2到500个字符之间"))]
+    #[serde(rename = "replyContent")]
+    pub reply_content: String,
+    #[validate(length(min = 1, message = "createUserId不能为空"))]
+    #[serde(rename = "createUserId")]
+    pub create_user_id: String,
+    #[validate(length(max = 100, message = "creatorName不能超过100个字符"))]
+    #[serde(rename = "creatorName", default, deserialize_with = "deserialize_null_to_empty_string")]
+    pub creator_name: String,
+}
+
+impl ReplyAnswer{
+    pub fn custom_validate(&self)->Result<(), BusinessError>{
+        self.validate()?;
+        sensitive_word::current().reject("replyContent", &self.reply_content)?;
+        Ok(())
+    }
+
+    /// 去掉首尾空白，并将需要屏蔽的敏感词替换为*（回复本身需要审核，需要审核的敏感词不再单独处理）
+    pub fn normalized_reply_content(&self)->String{
+        sensitive_word::current().mask(self.reply_content.trim())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate_reply_content() {
+        let mut request = ReplyAnswer {
+            answer_id: 1,
+            parent_id: None,
+            reply_content: "能机洗吗".to_string(),
+            create_user_id: "create_user_id".to_string(),
+            creator_name: "".to_string(),
+        };
+        assert!(request.custom_validate().is_ok());
+        request.reply_content = "a".to_string();
+        match request.custom_validate() {
+            Err(BusinessError::InvalidParameter((_, Some(parameters)))) => {
+                assert_eq!(parameters[0].get("field").unwrap(), "replyContent");
+            },
+            _ => panic!("错误类型不匹配"),
+        }
+    }
+}
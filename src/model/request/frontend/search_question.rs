@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// 前台在商品范围内全文检索问题与回答
+#[derive(Deserialize,Debug,Serialize,Validate,Clone)]
+pub struct SearchQuestion{
+    #[validate(length(min = 1, message = "productCode不能为空"))]
+    #[serde(rename="productCode")]
+    pub product_code:String,
+    /// 全文索引使用ngram分词（默认2个字符），少于2个字符的关键词检索不到结果
+    #[validate(length(min = 2, max = 50, message = "keyword长度应该在2到50个字符之间"))]
+    pub keyword:String,
+    #[serde(rename = "pageNum")]
+    #[validate(range(min = 1, message = "页面参数不合法，pageNum应该是大于等于1的整数"))]
+    pub current_pageno:i64,
+    #[serde(rename = "pageSize")]
+    #[validate(range(min = 1, max = 50, message = "页面参数不合法，pageSize应该是1到50之间的整数"))]
+    pub page_size:i64,
+}
+
+#[cfg(test)]
+mod test {
+    use validator::Validate;
+
+    use crate::model::request::frontend::search_question::SearchQuestion;
+    use crate::utils::error::BusinessError;
+    use crate::utils::logging::init_log;
+
+    //模拟keyword长度不合法的情况
+    #[tokio::test]
+    async fn test_validate_keyword() {
+        init_log().await;
+        let possible_values = vec!["缩".to_string(), "a".repeat(51)];
+        for value in possible_values{
+            let request = SearchQuestion {
+                product_code: "product_code".to_string(),
+                keyword: value,
+                current_pageno: 1,
+                page_size: 10,
+            };
+            let result = request.validate();
+            assert_eq!(result.is_err(), true);
+            if let Err(validation_errors) = result {
+                let business_error = validation_errors.into();
+                match business_error {
+                    BusinessError::InvalidParameter ((_,errors_parameters)) => {
+                        let error_parameters = errors_parameters.unwrap();
+                        let error_parameters0 = error_parameters.get(0).unwrap();
+                        assert_eq!(error_parameters0.get("field").unwrap(),"keyword");
+                        assert_eq!(error_parameters0.get("message").unwrap(),"keyword长度应该在2到50个字符之间");
+                    }
+                    _ => {
+                        panic!("错误类型不匹配")
+                    }
+                }
+            }
+        }
+
+        let request = SearchQuestion {
+            product_code: "product_code".to_string(),
+            keyword: "缩水".to_string(),
+            current_pageno: 1,
+            page_size: 10,
+        };
+        assert_eq!(request.validate().is_err(), false);
+    }
+}
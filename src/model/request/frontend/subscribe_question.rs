@@ -0,0 +1,24 @@
+use serde::{Serialize,Deserialize};
+use validator::Validate;
+
+/// 前台登录用户订阅问题，订阅后问题有新回答或被修改时会收到通知
+/// 订阅用户取自网关认证的用户（见ClientIdentity），不从请求体读取
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct SubscribeQuestion {
+    #[validate(length(min = 1, message = "questionCode不能为空"))]
+    #[serde(rename = "questionCode")]
+    pub question_code: String,
+}
+
+/// 前台用户取消订阅问题：登录用户取消自己的订阅，或凭通知中的subscriberId和unsubscribeToken取消订阅
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct UnsubscribeQuestion {
+    #[validate(length(min = 1, message = "questionCode不能为空"))]
+    #[serde(rename = "questionCode")]
+    pub question_code: String,
+    #[validate(length(min = 1, max = 100, message = "subscriberId长度应该在1到100个字符之间"))]
+    #[serde(rename = "subscriberId")]
+    pub subscriber_id: Option<String>,
+    #[serde(rename = "unsubscribeToken")]
+    pub unsubscribe_token: Option<String>,
+}
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::model::db::qa::Vote;
+
+/// 前台用户对回答投票（有用/没用），同一调用方对同一回答重复投票时以最后一次为准
+/// 投票用户取自认证用户或客户端IP（见ClientIdentity），不再从请求体读取
+#[derive(Deserialize,Debug,Serialize,Validate,Clone)]
+pub struct VoteAnswer{
+    #[validate(range(min = 1, message = "answerId应该是大于等于1的整数"))]
+    #[serde(rename="answerId")]
+    pub answer_id:i64,
+    pub vote:Vote,
+}
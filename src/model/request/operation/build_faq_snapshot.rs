@@ -0,0 +1,21 @@
+use serde::{Serialize,Deserialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use validator::{Validate, ValidationError};
+use crate::utils::faq_snapshot::is_valid_product_code;
+
+/// 立即生成商品的FAQ静态快照
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct BuildFaqSnapshot {
+    #[validate(custom = "validate_product_code")]
+    #[serde(rename = "productCode")]
+    pub product_code: String,
+}
+
+/// 商品编号用作快照文件名，只允许字母、数字、-和_
+pub fn validate_product_code(value: &str) -> Result<(), ValidationError> {
+    if !is_valid_product_code(value) {
+        return Err(ValidationError{code:"".into(),message:Some(Cow::from("productCode只能包含字母、数字、-和_".to_string())),params:HashMap::new()});
+    }
+    Ok(())
+}
@@ -2,13 +2,14 @@
 use serde::{Serialize,Deserialize};
 use std::borrow::Cow;
 use std::collections::HashMap;
-use validator::{Validate, ValidationError};
+use validator::{Validate, ValidationError, ValidationErrors};
 use crate::model::db::qa::{
     Answer as DbAnswer,
     Question as DbQuestion
 };
 use crate::utils::error::BusinessError;
 use crate::utils::default_value::deserialize_null_to_empty_string;
+use crate::utils::sensitive_word;
 
 #[derive(Serialize,Deserialize, Debug, Validate, Clone)]
 pub struct CreateQuestion {
@@ -26,9 +27,12 @@ pub struct CreateQuestion {
     #[serde(rename = "creatorName", deserialize_with = "deserialize_null_to_empty_string")]
     pub creator_name: String,
     pub rank: i32,
-    #[validate(custom = "validate_answer_list")]
     #[serde(rename = "answers")]
     pub answer_list: Vec<CreateAnswer>,
+    /// 问题的标签，如"shipping"、"sizing"
+    #[validate(custom = "validate_tags")]
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 impl CreateQuestion{
@@ -50,12 +54,38 @@ impl CreateQuestion{
     }
 
     pub async fn custom_validate(&self)->Result<(), BusinessError>{
-        self.validate()?;
+        self.validate_question()?;
+        validate_answer_list(&self.answer_list)?;
+        let filter = sensitive_word::current();
         for answer in &self.answer_list{
             let _ = answer.validate()?;
+            filter.reject("answerContent", &answer.answer_content)?;
         }        
         Ok(())
     }
+
+    /// 只校验问题本身的字段，不要求带回答（前台用户提问时使用）
+    pub fn validate_question(&self)->Result<(), BusinessError>{
+        self.validate()?;
+        sensitive_word::current().reject("questionContent", &self.question_content)?;
+        Ok(())
+    }
+
+    /// 将问题和回答中需要屏蔽的敏感词替换为*，返回命中的需要人工审核的敏感词
+    pub fn mask_sensitive_words(&mut self)->Vec<String>{
+        let filter = sensitive_word::current();
+        let mut flagged = filter.check(&self.question_content).flagged;
+        self.question_content = filter.mask(&self.question_content);
+        for answer in &mut self.answer_list{
+            for word in filter.check(&answer.answer_content).flagged{
+                if !flagged.contains(&word){
+                    flagged.push(word);
+                }
+            }
+            answer.answer_content = filter.mask(&answer.answer_content);
+        }
+        flagged
+    }
 }
 
 
@@ -73,6 +103,7 @@ impl CreateAnswer{
 
 #[derive(Serialize, Deserialize, Debug, Validate,Clone)]
 pub struct CreateAnswer{
+    /// 回答内容，支持Markdown（列表、粗体、链接等），保存时同时保存渲染后的HTML
     #[validate(length(min = 1, message = "answerContent不能为空"))]
     #[serde(rename = "answerContent")]
     pub answer_content:String,
@@ -83,9 +114,42 @@ pub struct CreateAnswer{
     pub creator_name: String,
 }
 
-fn validate_answer_list(value: &Vec<CreateAnswer>) -> Result<(), ValidationError> {
+/// 每个问题最多的标签数量
+const MAX_TAG_COUNT: usize = 10;
+/// 标签的最大字符数
+const MAX_TAG_LENGTH: usize = 20;
+
+/// 校验标签：最多10个，去掉首尾空白后不能为空且不超过20个字符
+pub fn validate_tags(value: &Vec<String>) -> Result<(), ValidationError> {
+    if value.len() > MAX_TAG_COUNT {
+        return Err(ValidationError{code:"".into(),message:Some(Cow::from(format!("tags不能超过{}个",MAX_TAG_COUNT))),params:HashMap::new()});
+    }
+    for tag in value{
+        let length = tag.trim().chars().count();
+        if length == 0 || length > MAX_TAG_LENGTH {
+            return Err(ValidationError{code:"".into(),message:Some(Cow::from(format!("每个标签应该在1到{}个字符之间",MAX_TAG_LENGTH))),params:HashMap::new()});
+        }
+    }
+    Ok(())
+}
+
+/// 去掉标签首尾空白并去重，保持原有顺序
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = vec![];
+    for tag in tags{
+        let tag = tag.trim().to_string();
+        if !normalized.contains(&tag){
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+fn validate_answer_list(value: &Vec<CreateAnswer>) -> Result<(), ValidationErrors> {
     if value.len() == 0 {
-        return Err(ValidationError{code:"".into(),message:Some(Cow::from("answers列表不能为空".to_string())),params:HashMap::new()});
+        let mut errors = ValidationErrors::new();
+        errors.add("answers", ValidationError{code:"".into(),message:Some(Cow::from("answers列表不能为空".to_string())),params:HashMap::new()});
+        return Err(errors);
     }
     Ok(())
 }
@@ -94,6 +158,7 @@ fn validate_answer_list(value: &Vec<CreateAnswer>) -> Result<(), ValidationError
 mod test {
     use crate::model::request::operation::create_question::CreateQuestion;
     use crate::model::request::operation::create_question::CreateAnswer;
+    use crate::model::request::operation::create_question::{validate_tags, normalize_tags};
     use crate::utils::error::BusinessError;
     use crate::utils::logging::init_log;
 
@@ -130,6 +195,7 @@ mod test {
             creator_name,
             rank,
             answer_list,
+            tags: vec![],
         };
         let result = question.custom_validate().await;
         assert_eq!(result.is_err(), true);
@@ -179,6 +245,7 @@ mod test {
             creator_name,
             rank,
             answer_list,
+            tags: vec![],
         };
         let result = question.custom_validate().await;
         assert_eq!(result.is_err(), true);
@@ -227,6 +294,7 @@ mod test {
             creator_name,
             rank,
             answer_list,
+            tags: vec![],
         };
         let result = question.custom_validate().await;
         assert_eq!(result.is_err(), true);
@@ -270,6 +338,7 @@ mod test {
             creator_name,
             rank,
             answer_list,
+            tags: vec![],
         };
         let result = question.custom_validate().await;
         assert_eq!(result.is_err(), true);
@@ -318,6 +387,7 @@ mod test {
             creator_name,
             rank,
             answer_list,
+            tags: vec![],
         };
         let result = question.custom_validate().await;
         assert_eq!(result.is_err(), true);
@@ -367,6 +437,7 @@ mod test {
             creator_name,
             rank,
             answer_list,
+            tags: vec![],
         };
         let result = question.custom_validate().await;
         assert_eq!(result.is_err(), true);
@@ -389,4 +460,16 @@ mod test {
             }
         }
     }
-}
\ No newline at end of file
+
+    //标签校验与去重
+    #[tokio::test]
+    async fn test_validate_tags() {
+        init_log().await;
+        assert_eq!(validate_tags(&vec!["shipping".to_string(), " sizing ".to_string()]).is_ok(), true);
+        assert_eq!(validate_tags(&vec![" ".to_string()]).is_err(), true);
+        assert_eq!(validate_tags(&vec!["a".repeat(21)]).is_err(), true);
+        assert_eq!(validate_tags(&(0..11).map(|i|i.to_string()).collect()).is_err(), true);
+        let tags = normalize_tags(&vec![" shipping".to_string(), "sizing".to_string(), "shipping ".to_string()]);
+        assert_eq!(tags, vec!["shipping".to_string(), "sizing".to_string()]);
+    }
+}
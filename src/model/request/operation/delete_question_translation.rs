@@ -0,0 +1,12 @@
+use serde::{Serialize,Deserialize};
+use validator::Validate;
+use crate::utils::locale::Locale;
+
+/// 删除问题及其回答某个语言的翻译
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct DeleteQuestionTranslation {
+    #[validate(length(min = 1, message = "questionCode不能为空"))]
+    #[serde(rename = "questionCode")]
+    pub question_code: String,
+    pub locale: Locale,
+}
@@ -0,0 +1,16 @@
+use serde::{Serialize,Deserialize};
+use validator::Validate;
+
+/// 比较问题的两个修订版本
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct DiffQuestionRevision {
+    #[validate(length(min = 1, message = "questionCode不能为空"))]
+    #[serde(rename = "questionCode")]
+    pub question_code: String,
+    #[validate(range(min = 1, message = "fromRevision应该是大于等于1的整数"))]
+    #[serde(rename = "fromRevision")]
+    pub from_revision: i32,
+    #[validate(range(min = 1, message = "toRevision应该是大于等于1的整数"))]
+    #[serde(rename = "toRevision")]
+    pub to_revision: i32,
+}
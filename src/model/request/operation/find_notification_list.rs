@@ -0,0 +1,20 @@
+use serde::{Serialize,Deserialize};
+use validator::Validate;
+
+/// 查询订阅通知及其发送状态，用于排查通知未送达的问题
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct FindNotificationList {
+    #[serde(rename = "pageNum")]
+    #[validate(range(min = 1, message = "页面参数不合法，pageNum应该是大于等于1的整数"))]
+    pub current_pageno:i64,
+    #[serde(rename = "pageSize")]
+    #[validate(range(min = 1, message = "页面参数不合法，pageSize应该是大于等于1的整数"))]
+    pub page_size:i64,
+    #[serde(rename = "questionCode")]
+    pub question_code: Option<String>,
+    #[serde(rename = "subscriberId")]
+    pub subscriber_id: Option<String>,
+    /// 发送状态(0:待发送 1:已送达 2:发送失败 3:发送中)
+    #[validate(range(min = 0, max = 3, message = "status应该是0、1、2或3"))]
+    pub status: Option<i8>,
+}
@@ -1,18 +1,26 @@
 use serde::{Serialize,Deserialize};
-use validator::Validate;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use validator::{Validate, ValidationError};
+use sqlx::{MySql, QueryBuilder};
+use crate::model::db::qa::push_effective_sort;
 
 #[derive(Serialize,Deserialize, Debug, Validate, Clone)]
 pub struct FindQuestionListForTrad {
 
-    #[serde(rename = "pageNum")]
+    #[serde(rename = "pageNum", default = "default_current_pageno")]
     #[validate(range(min = 1, message = "页面参数不合法，pageNum应该是大于等于1的整数"))]
     pub current_pageno:i64,
     #[serde(rename = "pageSize")]
     #[validate(range(min = 1, message = "页面参数不合法，pageSize应该是大于等于1的整数"))]
     pub page_size:i64,
 
+    /// 以逗号分隔的问题id列表
     #[serde(rename="idStr")]
+    #[validate(custom = "validate_id_str")]
     pub id_str: Option<String>,
+    #[serde(rename = "skuCode")]
+    pub sku_code: Option<String>,
     #[serde(rename = "productCode")]
     pub product_code: Option<String>,
     #[serde(rename = "questionContent")]
@@ -21,14 +29,148 @@ pub struct FindQuestionListForTrad {
     pub question_code: Option<String>,
     #[serde(rename = "creatorName")]
     pub create_name: Option<String>,
+    /// 回答编号（rc_qa_answer的id），查询包含该回答的问题
     #[serde(rename = "answerCode")]
     pub answer_code: Option<String>,
+    /// 问题状态(0/1)，为空时只查询有效的问题
+    pub status: Option<i8>,
     #[serde(rename = "startTime")]
     pub start_time: Option<chrono::NaiveDateTime>,
     #[serde(rename = "endTime")]
     pub end_time: Option<chrono::NaiveDateTime>,
     #[serde(rename = "answerContent")]
-    pub answer_content:Option<String>
+    pub answer_content:Option<String>,
+    /// 标签名称，只查询带有该标签的问题
+    pub tag:Option<String>,
+    /// 文本条件的匹配方式，为空时按调用方传入的值直接like匹配
+    #[serde(rename = "matchMode")]
+    pub match_mode:Option<MatchMode>,
+    /// 排序字段，为空时按`sort` desc,`rank`,`create_time` desc,`id`排序
+    #[serde(rename = "sortField")]
+    pub sort_field:Option<QuestionSortField>,
+    #[serde(rename = "sortOrder")]
+    pub sort_order:Option<SortOrder>,
+
+    /// 游标分页模式（按`sort` desc,`rank`,`create_time` desc,`id`翻页），开启后忽略pageNum
+    #[serde(rename = "cursorMode", default)]
+    pub cursor_mode:bool,
+    /// 上一页返回的nextCursor，为空表示查询第一页
+    #[serde(rename = "cursor")]
+    pub cursor:Option<String>,
+    /// 游标分页模式下是否同时返回总记录数
+    #[serde(rename = "withTotal", default)]
+    pub with_total:bool,
+}
+
+fn default_current_pageno() -> i64 {
+    1
+}
+
+/// 文本条件的匹配方式
+#[derive(Serialize,Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum MatchMode {
+    /// 完全相等
+    Exact,
+    /// 前缀匹配
+    Prefix,
+    /// 包含匹配
+    Contains,
+    /// 全文检索，只对建有全文索引的questionContent和answerContent生效，其它字段按包含匹配处理
+    FullText,
+}
+
+impl MatchMode {
+    /// 生成like匹配串，调用方传入的`%`、`_`和`\`按普通字符处理
+    pub fn like_pattern(&self, value: &str) -> String {
+        let escaped = value
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        match self {
+            MatchMode::Exact => escaped,
+            MatchMode::Prefix => format!("{}%", escaped),
+            MatchMode::Contains | MatchMode::FullText => format!("%{}%", escaped),
+        }
+    }
+}
+
+/// 问题列表可选的排序字段
+#[derive(Serialize,Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum QuestionSortField {
+    /// 置顶顺序：`sort` desc,`rank`,`create_time` desc
+    Default,
+    CreateTime,
+    UpdateTime,
+    Rank,
+    Id,
+}
+
+#[derive(Serialize,Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl FindQuestionListForTrad {
+    /// 解析idStr中的问题id列表
+    pub fn id_list(&self) -> Vec<i64> {
+        match &self.id_str {
+            Some(id_str) => parse_id_str(id_str).unwrap_or_default(),
+            None => vec![],
+        }
+    }
+
+    /// 是否按默认的置顶顺序排序（游标分页只支持默认排序）
+    pub fn is_default_sort(&self) -> bool {
+        matches!(self.sort_field, None | Some(QuestionSortField::Default))
+    }
+
+    /// 追加order by子句，排序字段均为固定的列名，最后以`id`保证顺序稳定
+    /// 默认排序中已到期的置顶不参与排序，now为判断置顶是否到期的时间
+    pub fn push_order_by(&self, builder: &mut QueryBuilder<'_, MySql>, now: chrono::NaiveDateTime) {
+        let order = match self.sort_order {
+            Some(SortOrder::Asc) => "asc",
+            Some(SortOrder::Desc) => "desc",
+            None => "desc",
+        };
+        match self.sort_field {
+            None | Some(QuestionSortField::Default) => {
+                builder.push(" order by ");
+                push_effective_sort(builder, now);
+                builder.push(" desc,`rank`,`create_time` desc,`id`");
+            }
+            Some(QuestionSortField::CreateTime) => {
+                builder.push(format!(" order by `create_time` {},`id` {}", order, order));
+            }
+            Some(QuestionSortField::UpdateTime) => {
+                builder.push(format!(" order by `update_time` {},`id` {}", order, order));
+            }
+            Some(QuestionSortField::Rank) => {
+                builder.push(format!(" order by `rank` {},`id` {}", order, order));
+            }
+            Some(QuestionSortField::Id) => {
+                builder.push(format!(" order by `id` {}", order));
+            }
+        }
+    }
+}
+
+fn parse_id_str(id_str: &str) -> Option<Vec<i64>> {
+    id_str.split(',')
+        .map(|id| id.trim())
+        .filter(|id| !id.is_empty())
+        .map(|id| id.parse::<i64>().ok())
+        .collect()
+}
+
+fn validate_id_str(value: &str) -> Result<(), ValidationError> {
+    if parse_id_str(value).is_none() {
+        return Err(ValidationError{code:"".into(),message:Some(Cow::from("idStr应该是以逗号分隔的问题id".to_string())),params:HashMap::new()});
+    }
+    Ok(())
 }
 
 impl FindQuestionListForTrad {
@@ -37,14 +179,23 @@ impl FindQuestionListForTrad {
             current_pageno: 1,
             page_size: 10,
             id_str: None,
+            sku_code: None,
             product_code: None,
             question_content: None,
             question_code: None,
             create_name: None,
             answer_code: None,
+            status: None,
             start_time: None,
             end_time: None,
             answer_content: None,
+            match_mode: None,
+            sort_field: None,
+            sort_order: None,
+            cursor_mode: false,
+            cursor: None,
+            with_total: false,
+            tag: None,
         }
     }
 }
@@ -52,8 +203,9 @@ impl FindQuestionListForTrad {
 #[cfg(test)]
 mod test {
     use validator::Validate;
+    use sqlx::{MySql, QueryBuilder};
 
-    use crate::model::request::operation::find_question_list_for_trad::FindQuestionListForTrad;
+    use crate::model::request::operation::find_question_list_for_trad::{FindQuestionListForTrad, MatchMode, QuestionSortField, SortOrder};
     use crate::utils::error::BusinessError;
     use crate::utils::logging::init_log;
 
@@ -67,14 +219,23 @@ mod test {
                 current_pageno: value,
                 page_size: 10,
                 id_str: None,
+                sku_code: None,
                 product_code: None,
                 question_content: None,
                 question_code: None,
                 create_name: None,
                 answer_code: None,
+                status: None,
                 start_time: None,
                 end_time: None,
                 answer_content: None,
+                match_mode: None,
+                sort_field: None,
+                sort_order: None,
+                cursor_mode: false,
+                cursor: None,
+                with_total: false,
+                tag: None,
             };
             let result = request.validate();
             assert_eq!(result.is_err(), true);
@@ -103,14 +264,23 @@ mod test {
             current_pageno: 1,
             page_size: 10,
             id_str: None,
+            sku_code: None,
             product_code: None,
             question_content: None,
             question_code: None,
             create_name: None,
             answer_code: None,
+            status: None,
             start_time: None,
             end_time: None,
             answer_content: None,
+            match_mode: None,
+            sort_field: None,
+            sort_order: None,
+            cursor_mode: false,
+            cursor: None,
+            with_total: false,
+            tag: None,
         };
         let result = request.validate();
         assert_eq!(result.is_err(), false);
@@ -126,14 +296,23 @@ mod test {
                 current_pageno: 1,
                 page_size: value,
                 id_str: None,
+                sku_code: None,
                 product_code: None,
                 question_content: None,
                 question_code: None,
                 create_name: None,
                 answer_code: None,
+                status: None,
                 start_time: None,
                 end_time: None,
                 answer_content: None,
+                match_mode: None,
+                sort_field: None,
+                sort_order: None,
+                cursor_mode: false,
+                cursor: None,
+                with_total: false,
+                tag: None,
             };
             let result = request.validate();
             assert_eq!(result.is_err(), true);
@@ -162,17 +341,82 @@ mod test {
             current_pageno: 1,
             page_size: 10,
             id_str: None,
+            sku_code: None,
             product_code: None,
             question_content: None,
             question_code: None,
             create_name: None,
             answer_code: None,
+            status: None,
             start_time: None,
             end_time: None,
             answer_content: None,
+            match_mode: None,
+            sort_field: None,
+            sort_order: None,
+            cursor_mode: false,
+            cursor: None,
+            with_total: false,
+            tag: None,
         };
         let result = request.validate();
         assert_eq!(result.is_err(), false);
     }
 
+    //模拟idStr不是以逗号分隔的数字的情况
+    #[tokio::test]
+    async fn test_validate_id_str() {
+        init_log().await;
+        let mut request = FindQuestionListForTrad::new();
+        request.id_str = Some("1,a,3".to_string());
+        let result = request.validate();
+        assert_eq!(result.is_err(), true);
+        if let Err(validation_errors) = result {
+            let business_error = validation_errors.into();
+            match business_error {
+                BusinessError::InvalidParameter ((_,errors_parameters)) => {
+                    let error_parameters = errors_parameters.unwrap();
+                    let error_parameters0 = error_parameters.get(0).unwrap();
+                    assert_eq!(error_parameters0.get("field").unwrap(),"idStr");
+                    assert_eq!(error_parameters0.get("message").unwrap(),"idStr应该是以逗号分隔的问题id");
+                }
+                _ => {
+                    panic!("错误类型不匹配")
+                }
+            }
+        }
+
+        request.id_str = Some(" 1, 2,,3 ".to_string());
+        assert_eq!(request.validate().is_err(), false);
+        assert_eq!(request.id_list(), vec![1, 2, 3]);
+    }
+
+    //匹配方式生成的like匹配串应转义调用方传入的通配符
+    #[test]
+    fn test_like_pattern() {
+        assert_eq!(MatchMode::Exact.like_pattern("a%b"), "a\\%b");
+        assert_eq!(MatchMode::Prefix.like_pattern("a_b"), "a\\_b%");
+        assert_eq!(MatchMode::Contains.like_pattern("a\\b"), "%a\\\\b%");
+    }
+
+    fn order_by_sql(request: &FindQuestionListForTrad) -> String {
+        let now = chrono::NaiveDate::from_ymd_opt(2024, 11, 11).unwrap().and_hms_opt(12, 0, 0).unwrap();
+        let mut builder = QueryBuilder::<MySql>::new("");
+        request.push_order_by(&mut builder, now);
+        builder.sql().to_string()
+    }
+
+    #[test]
+    fn test_push_order_by() {
+        let mut request = FindQuestionListForTrad::new();
+        assert_eq!(order_by_sql(&request), " order by (case when `top_expire_time` is not null and `top_expire_time` <= ? then 0 else `sort` end) desc,`rank`,`create_time` desc,`id`");
+        assert_eq!(request.is_default_sort(), true);
+        request.sort_field = Some(QuestionSortField::CreateTime);
+        request.sort_order = Some(SortOrder::Asc);
+        assert_eq!(order_by_sql(&request), " order by `create_time` asc,`id` asc");
+        assert_eq!(request.is_default_sort(), false);
+        request.sort_field = Some(QuestionSortField::Id);
+        request.sort_order = None;
+        assert_eq!(order_by_sql(&request), " order by `id` desc");
+    }
 }
@@ -0,0 +1,16 @@
+use serde::{Serialize,Deserialize};
+use validator::Validate;
+
+/// 分页查询问题的修订记录
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct FindQuestionRevisionList {
+    #[validate(length(min = 1, message = "questionCode不能为空"))]
+    #[serde(rename = "questionCode")]
+    pub question_code: String,
+    #[serde(rename = "pageNum")]
+    #[validate(range(min = 1, message = "页面参数不合法，pageNum应该是大于等于1的整数"))]
+    pub current_pageno:i64,
+    #[serde(rename = "pageSize")]
+    #[validate(range(min = 1, message = "页面参数不合法，pageSize应该是大于等于1的整数"))]
+    pub page_size:i64,
+}
@@ -0,0 +1,10 @@
+use serde::{Serialize,Deserialize};
+use validator::Validate;
+
+/// 查询问题及其回答的全部翻译
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct FindQuestionTranslation {
+    #[validate(length(min = 1, message = "questionCode不能为空"))]
+    #[serde(rename = "questionCode")]
+    pub question_code: String,
+}
@@ -0,0 +1,15 @@
+use serde::{Serialize,Deserialize};
+use validator::Validate;
+
+/// 分页查询回收站（已软删除的问题）
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct FindRecycleBin {
+    #[serde(rename = "pageNum")]
+    #[validate(range(min = 1, message = "页面参数不合法，pageNum应该是大于等于1的整数"))]
+    pub current_pageno:i64,
+    #[serde(rename = "pageSize")]
+    #[validate(range(min = 1, message = "页面参数不合法，pageSize应该是大于等于1的整数"))]
+    pub page_size:i64,
+    #[serde(rename = "productCode")]
+    pub product_code: Option<String>,
+}
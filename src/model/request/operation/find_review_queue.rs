@@ -0,0 +1,21 @@
+use serde::{Serialize,Deserialize};
+use validator::Validate;
+use crate::model::request::operation::review_qa::ReviewTargetType;
+
+/// 查询审核队列，默认查询待审核的记录
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct FindReviewQueue {
+    #[serde(rename = "pageNum")]
+    #[validate(range(min = 1, message = "页面参数不合法，pageNum应该是大于等于1的整数"))]
+    pub current_pageno:i64,
+    #[serde(rename = "pageSize")]
+    #[validate(range(min = 1, message = "页面参数不合法，pageSize应该是大于等于1的整数"))]
+    pub page_size:i64,
+    #[serde(rename = "targetType")]
+    pub target_type: ReviewTargetType,
+    /// 审核状态(0/1/2)，为空时查询待审核的记录
+    #[serde(rename = "reviewStatus")]
+    pub review_status: Option<i8>,
+    #[serde(rename = "productCode")]
+    pub product_code: Option<String>,
+}
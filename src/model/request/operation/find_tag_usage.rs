@@ -0,0 +1,10 @@
+use serde::{Serialize,Deserialize};
+use validator::Validate;
+
+/// 查询商品下各标签关联的有效问题数量
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct FindTagUsage {
+    #[validate(length(min = 1, message = "productCode不能为空"))]
+    #[serde(rename = "productCode")]
+    pub product_code: String,
+}
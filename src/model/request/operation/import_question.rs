@@ -0,0 +1,227 @@
+//! 问答CSV的格式（导入与导出相同），方便运营人员用表格软件维护
+//! 每行一个问题，按表头名称匹配列，列的顺序不限；回答放在重复的回答列中（表头为answerContent、answerContent1、answerContent2...），空的回答列忽略
+//! 标签以|分隔；回答的创建人与问题相同；questionCode等其它列只用于导出，导入时忽略
+use std::collections::HashMap;
+use crate::model::request::operation::create_question::{CreateQuestion, CreateAnswer};
+use crate::utils::error::BusinessError;
+
+pub const COLUMN_QUESTION_CODE: &str = "questionCode";
+pub const COLUMN_PRODUCT_CODE: &str = "productCode";
+pub const COLUMN_SKU_CODE: &str = "skuCode";
+pub const COLUMN_QUESTION_CONTENT: &str = "questionContent";
+pub const COLUMN_CREATE_USER_ID: &str = "createUserId";
+pub const COLUMN_CREATOR_NAME: &str = "creatorName";
+pub const COLUMN_RANK: &str = "rank";
+pub const COLUMN_TAGS: &str = "tags";
+pub const COLUMN_ANSWER_CONTENT: &str = "answerContent";
+/// 标签列中多个标签的分隔符
+pub const TAG_SEPARATOR: char = '|';
+/// 一次最多导入的问题数量
+pub const MAX_IMPORT_ROWS: usize = 1000;
+/// Excel保存的UTF-8 CSV文件以BOM开头，导出时同样写入BOM
+pub const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// 以这些字符开头的单元格会被表格软件当作公式执行，导出时在前面加上'，导入时去掉
+const FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+/// 导出时转义可能被当作公式的单元格
+pub fn escape_cell(value: &str) -> String {
+    if value.starts_with(FORMULA_PREFIXES) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// 导入时还原导出时转义的单元格
+pub fn unescape_cell(value: &str) -> &str {
+    match value.strip_prefix('\'') {
+        Some(rest) if rest.starts_with(FORMULA_PREFIXES) => rest,
+        _ => value,
+    }
+}
+
+/// 是否为回答列：answerContent或answerContent后跟数字
+fn is_answer_column(header: &str) -> bool {
+    header.strip_prefix(COLUMN_ANSWER_CONTENT)
+        .map(|suffix| suffix.chars().all(|c| c.is_ascii_digit()))
+        .unwrap_or(false)
+}
+
+/// CSV中的一行问题，row_number为该行在表格中的行号（表头为第1行）
+#[derive(Debug)]
+pub struct ImportRow {
+    pub row_number: usize,
+    pub question: Result<CreateQuestion, BusinessError>,
+}
+
+/// 解析CSV内容，文件本身无法解析、缺少必需的列或超过行数上限时返回错误；每行的格式错误放在对应行的结果中
+pub fn parse_question_csv(content: &[u8]) -> Result<Vec<ImportRow>, BusinessError> {
+    let content = content.strip_prefix(UTF8_BOM).unwrap_or(content);
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(content);
+    let headers: Vec<String> = reader.headers()
+        .map_err(|error| file_error(format!("CSV表头无法解析: {}", error)))?
+        .iter()
+        .map(|header| header.trim().to_string())
+        .collect();
+    let column = |name: &str| headers.iter().position(|header| header == name);
+    for required in [COLUMN_PRODUCT_CODE, COLUMN_QUESTION_CONTENT, COLUMN_CREATE_USER_ID] {
+        if column(required).is_none() {
+            return Err(file_error(format!("CSV缺少{}列", required)));
+        }
+    }
+    let columns = Columns {
+        product_code: column(COLUMN_PRODUCT_CODE),
+        sku_code: column(COLUMN_SKU_CODE),
+        question_content: column(COLUMN_QUESTION_CONTENT),
+        create_user_id: column(COLUMN_CREATE_USER_ID),
+        creator_name: column(COLUMN_CREATOR_NAME),
+        rank: column(COLUMN_RANK),
+        tags: column(COLUMN_TAGS),
+        answers: headers.iter().enumerate()
+            .filter(|(_, header)| is_answer_column(header))
+            .map(|(index, _)| index)
+            .collect(),
+        header_count: headers.len(),
+    };
+
+    let mut rows = vec![];
+    for (index, record) in reader.records().enumerate() {
+        let row_number = index + 2;
+        let question = match record {
+            Ok(record) if record.iter().all(|value| value.trim().is_empty()) => continue,
+            Ok(record) => columns.to_create_question(&record),
+            Err(error) => Err(file_error(format!("第{}行无法解析: {}", row_number, error))),
+        };
+        rows.push(ImportRow { row_number, question });
+        if rows.len() > MAX_IMPORT_ROWS {
+            return Err(file_error(format!("一次最多导入{}个问题", MAX_IMPORT_ROWS)));
+        }
+    }
+    Ok(rows)
+}
+
+/// 各字段所在的列
+struct Columns {
+    product_code: Option<usize>,
+    sku_code: Option<usize>,
+    question_content: Option<usize>,
+    create_user_id: Option<usize>,
+    creator_name: Option<usize>,
+    rank: Option<usize>,
+    tags: Option<usize>,
+    answers: Vec<usize>,
+    header_count: usize,
+}
+
+impl Columns {
+    fn to_create_question(&self, record: &csv::StringRecord) -> Result<CreateQuestion, BusinessError> {
+        let cell = |index: Option<usize>| -> String {
+            index.and_then(|index| record.get(index))
+                .map(|value| unescape_cell(value.trim()).to_string())
+                .unwrap_or_default()
+        };
+        let rank_value = cell(self.rank);
+        let rank = if rank_value.is_empty() {
+            0
+        } else {
+            rank_value.parse::<i32>().map_err(|_| {
+                let mut parameters = HashMap::new();
+                parameters.insert("field".to_string(), COLUMN_RANK.to_string());
+                parameters.insert("message".to_string(), "rank应该是整数".to_string());
+                BusinessError::InvalidParameter((None, Some(vec![parameters])))
+            })?
+        };
+        let create_user_id = cell(self.create_user_id);
+        let creator_name = cell(self.creator_name);
+        //表头之后多出的列也当作回答列（导出期间新增了回答时会出现）
+        let answer_indexes = self.answers.iter().copied().chain(self.header_count..record.len());
+        let answer_list = answer_indexes
+            .map(|index| cell(Some(index)))
+            .filter(|answer_content| !answer_content.is_empty())
+            .map(|answer_content| CreateAnswer {
+                answer_content,
+                create_user_id: create_user_id.clone(),
+                creator_name: creator_name.clone(),
+            })
+            .collect();
+        let tags = cell(self.tags)
+            .split(TAG_SEPARATOR)
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        let sku_code = cell(self.sku_code);
+        Ok(CreateQuestion {
+            sku_code: Some(sku_code).filter(|sku_code| !sku_code.is_empty()),
+            product_code: cell(self.product_code),
+            question_content: cell(self.question_content),
+            create_user_id,
+            creator_name,
+            rank,
+            answer_list,
+            tags,
+        })
+    }
+}
+
+fn file_error(message: String) -> BusinessError {
+    let mut parameters = HashMap::new();
+    parameters.insert("field".to_string(), "file".to_string());
+    parameters.insert("message".to_string(), message);
+    BusinessError::InvalidParameter((None, Some(vec![parameters])))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_question_csv() {
+        let content = "\u{FEFF}questionCode,productCode,questionContent,createUserId,rank,tags,answerContent1,answerContent2\n\
+            ignored,P001,能机洗吗？,user_1,2,洗涤| 面料 ,可以,\"建议30度以下, 轻柔模式\"\n\
+            ,,,,,,,\n\
+            ,P001,'=1+1,user_1,abc,,,\n";
+        let rows = parse_question_csv(content.as_bytes()).unwrap();
+        assert_eq!(rows.len(), 2);
+
+        assert_eq!(rows[0].row_number, 2);
+        let question = rows[0].question.as_ref().unwrap();
+        assert_eq!(question.product_code, "P001");
+        assert_eq!(question.sku_code, None);
+        assert_eq!(question.rank, 2);
+        assert_eq!(question.tags, vec!["洗涤".to_string(), "面料".to_string()]);
+        assert_eq!(question.answer_list.len(), 2);
+        assert_eq!(question.answer_list[1].answer_content, "建议30度以下, 轻柔模式");
+        assert_eq!(question.answer_list[1].create_user_id, "user_1");
+
+        //空行跳过，行号仍按表格中的行计算
+        assert_eq!(rows[1].row_number, 4);
+        match &rows[1].question {
+            Err(BusinessError::InvalidParameter((_, Some(parameters)))) => {
+                assert_eq!(parameters[0].get("field").unwrap(), "rank");
+            },
+            _ => panic!("错误类型不匹配"),
+        }
+    }
+
+    #[test]
+    fn test_missing_column() {
+        let result = parse_question_csv("productCode,questionContent\nP001,能机洗吗？\n".as_bytes());
+        match result {
+            Err(BusinessError::InvalidParameter((_, Some(parameters)))) => {
+                assert_eq!(parameters[0].get("message").unwrap(), "CSV缺少createUserId列");
+            },
+            _ => panic!("错误类型不匹配"),
+        }
+    }
+
+    #[test]
+    fn test_escape_cell() {
+        assert_eq!(escape_cell("=SUM(A1)"), "'=SUM(A1)");
+        assert_eq!(escape_cell("能机洗吗"), "能机洗吗");
+        assert_eq!(unescape_cell(&escape_cell("-1")), "-1");
+        assert_eq!(unescape_cell("'普通内容"), "'普通内容");
+    }
+}
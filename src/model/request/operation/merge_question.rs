@@ -0,0 +1,57 @@
+use serde::{Serialize,Deserialize};
+use std::collections::HashMap;
+use validator::Validate;
+use crate::utils::error::BusinessError;
+
+/// 合并重复的问题：来源问题的回答移到目标问题，来源问题失效并重定向到目标问题
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct MergeQuestion {
+    #[validate(length(min = 1, message = "sourceQuestionCode不能为空"))]
+    #[serde(rename = "sourceQuestionCode")]
+    pub source_question_code: String,
+    #[validate(length(min = 1, message = "targetQuestionCode不能为空"))]
+    #[serde(rename = "targetQuestionCode")]
+    pub target_question_code: String,
+    #[serde(rename = "operatorId")]
+    pub operator_id: Option<String>,
+}
+
+impl MergeQuestion {
+    pub async fn custom_validate(&self)->Result<(), BusinessError>{
+        self.validate()?;
+        if self.source_question_code == self.target_question_code {
+            let mut parameters = HashMap::new();
+            parameters.insert("field".to_string(), "targetQuestionCode".to_string());
+            parameters.insert("message".to_string(), "targetQuestionCode不能与sourceQuestionCode相同".to_string());
+            return Err(BusinessError::InvalidParameter((None,Some(vec![parameters]))));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::request::operation::merge_question::MergeQuestion;
+    use crate::utils::error::BusinessError;
+    use crate::utils::logging::init_log;
+
+    //模拟来源问题与目标问题相同的情况
+    #[tokio::test]
+    async fn test_validate_same_question() {
+        init_log().await;
+        let request = MergeQuestion {
+            source_question_code: "question_code".to_string(),
+            target_question_code: "question_code".to_string(),
+            operator_id: None,
+        };
+        match request.custom_validate().await {
+            Err(BusinessError::InvalidParameter((_,errors_parameters))) => {
+                let error_parameters = errors_parameters.unwrap();
+                assert_eq!(error_parameters[0].get("field").unwrap(),"targetQuestionCode");
+            }
+            _ => {
+                panic!("错误类型不匹配")
+            }
+        }
+    }
+}
@@ -8,3 +8,20 @@ pub mod find_question_list_for_trad;
 pub mod top_question;
 pub mod delete_question;
 pub mod cancel_top_question;
+pub mod review_qa;
+pub mod find_review_queue;
+pub mod find_question_revision_list;
+pub mod diff_question_revision;
+pub mod restore_question_revision;
+pub mod find_recycle_bin;
+pub mod restore_question;
+pub mod reorder_top_question;
+pub mod merge_question;
+pub mod find_tag_usage;
+pub mod save_question_translation;
+pub mod find_question_translation;
+pub mod delete_question_translation;
+pub mod qa_report;
+pub mod build_faq_snapshot;
+pub mod import_question;
+pub mod find_notification_list;
\ No newline at end of file
@@ -0,0 +1,147 @@
+use serde::{Serialize,Deserialize};
+use std::collections::HashMap;
+use validator::Validate;
+use chrono::{NaiveDate, NaiveDateTime};
+use crate::utils::error::BusinessError;
+
+/// 热门问题报表的默认返回数量
+pub const DEFAULT_TOP_VIEWED_LIMIT: i64 = 10;
+
+/// 查询商品下浏览量最高的问题，按浏览日期筛选
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct FindTopViewedQuestion {
+    #[validate(length(min = 1, message = "productCode不能为空"))]
+    #[serde(rename = "productCode")]
+    pub product_code: String,
+    #[serde(rename = "startDate")]
+    pub start_date: Option<NaiveDate>,
+    #[serde(rename = "endDate")]
+    pub end_date: Option<NaiveDate>,
+    #[validate(range(min = 1, max = 100, message = "limit应该是1到100之间的整数"))]
+    pub limit: Option<i64>,
+}
+
+impl FindTopViewedQuestion {
+    pub fn custom_validate(&self)->Result<(), BusinessError>{
+        self.validate()?;
+        validate_date_range(self.start_date, self.end_date)
+    }
+}
+
+/// 分页查询没有有效且审核通过的回答的问题，按问题的创建日期筛选
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct FindUnansweredQuestion {
+    #[serde(rename = "pageNum")]
+    #[validate(range(min = 1, message = "页面参数不合法，pageNum应该是大于等于1的整数"))]
+    pub current_pageno:i64,
+    #[serde(rename = "pageSize")]
+    #[validate(range(min = 1, max = 100, message = "页面参数不合法，pageSize应该是1到100之间的整数"))]
+    pub page_size:i64,
+    #[serde(rename = "productCode")]
+    pub product_code: Option<String>,
+    #[serde(rename = "startDate")]
+    pub start_date: Option<NaiveDate>,
+    #[serde(rename = "endDate")]
+    pub end_date: Option<NaiveDate>,
+}
+
+impl FindUnansweredQuestion {
+    pub fn custom_validate(&self)->Result<(), BusinessError>{
+        self.validate()?;
+        validate_date_range(self.start_date, self.end_date)
+    }
+
+    pub fn create_time_range(&self)->(Option<NaiveDateTime>, Option<NaiveDateTime>){
+        create_time_range(self.start_date, self.end_date)
+    }
+}
+
+/// 按商品统计回答时效（问题创建到第一个回答的时间），按问题的创建日期筛选
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct FindAnswerLatency {
+    #[serde(rename = "productCode")]
+    pub product_code: Option<String>,
+    #[serde(rename = "startDate")]
+    pub start_date: Option<NaiveDate>,
+    #[serde(rename = "endDate")]
+    pub end_date: Option<NaiveDate>,
+}
+
+impl FindAnswerLatency {
+    pub fn custom_validate(&self)->Result<(), BusinessError>{
+        self.validate()?;
+        validate_date_range(self.start_date, self.end_date)
+    }
+
+    pub fn create_time_range(&self)->(Option<NaiveDateTime>, Option<NaiveDateTime>){
+        create_time_range(self.start_date, self.end_date)
+    }
+}
+
+/// 开始日期不能晚于结束日期
+fn validate_date_range(start_date: Option<NaiveDate>, end_date: Option<NaiveDate>)->Result<(), BusinessError>{
+    if let (Some(start_date), Some(end_date)) = (start_date, end_date) {
+        if start_date > end_date {
+            let mut parameters = HashMap::new();
+            parameters.insert("field".to_string(), "startDate".to_string());
+            parameters.insert("message".to_string(), "startDate不能晚于endDate".to_string());
+            return Err(BusinessError::InvalidParameter((None,Some(vec![parameters]))));
+        }
+    }
+    Ok(())
+}
+
+/// 日期范围（包含结束日期当天）对应的创建时间范围：create_time >= 开始时间 and create_time < 结束时间
+fn create_time_range(start_date: Option<NaiveDate>, end_date: Option<NaiveDate>)->(Option<NaiveDateTime>, Option<NaiveDateTime>){
+    let start_time = start_date.and_then(|date|date.and_hms_opt(0, 0, 0));
+    let end_time = end_date
+        .and_then(|date|date.succ_opt())
+        .and_then(|date|date.and_hms_opt(0, 0, 0));
+    (start_time, end_time)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_date_range() {
+        let request = FindAnswerLatency {
+            product_code: None,
+            start_date: NaiveDate::from_ymd_opt(2024, 11, 1),
+            end_date: NaiveDate::from_ymd_opt(2024, 11, 30),
+        };
+        assert!(request.custom_validate().is_ok());
+        let (start_time, end_time) = request.create_time_range();
+        assert_eq!(start_time.unwrap().to_string(), "2024-11-01 00:00:00");
+        assert_eq!(end_time.unwrap().to_string(), "2024-12-01 00:00:00");
+
+        let request = FindAnswerLatency {
+            start_date: NaiveDate::from_ymd_opt(2024, 12, 1),
+            ..request
+        };
+        match request.custom_validate() {
+            Err(BusinessError::InvalidParameter((_, Some(parameters)))) => {
+                assert_eq!(parameters[0].get("field").unwrap(), "startDate");
+            },
+            _ => panic!("错误类型不匹配"),
+        }
+    }
+
+    #[test]
+    fn test_unanswered_page_size() {
+        let request = FindUnansweredQuestion {
+            current_pageno: 1,
+            page_size: 100,
+            product_code: None,
+            start_date: None,
+            end_date: None,
+        };
+        assert!(request.custom_validate().is_ok());
+        let request = FindUnansweredQuestion {
+            page_size: 101,
+            ..request
+        };
+        assert!(request.custom_validate().is_err());
+    }
+}
@@ -0,0 +1,68 @@
+use serde::{Serialize,Deserialize};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use validator::{Validate, ValidationError};
+
+/// 调整同一商品下置顶问题的顺序，questionCodes中靠前的问题排在前面
+/// 未出现在列表中的已置顶问题保持原有相对顺序，排在列表中的问题之后
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct ReorderTopQuestion {
+    #[validate(length(min = 1, message = "productCode不能为空"))]
+    #[serde(rename = "productCode")]
+    pub product_code: String,
+    #[validate(custom = "validate_question_codes")]
+    #[serde(rename = "questionCodes")]
+    pub question_codes: Vec<String>,
+}
+
+fn validate_question_codes(value: &Vec<String>) -> Result<(), ValidationError> {
+    if value.len() == 0 {
+        return Err(ValidationError{code:"".into(),message:Some(Cow::from("questionCodes列表不能为空".to_string())),params:HashMap::new()});
+    }
+    let mut question_codes = HashSet::new();
+    if !value.iter().all(|question_code|question_codes.insert(question_code)) {
+        return Err(ValidationError{code:"".into(),message:Some(Cow::from("questionCodes列表不能有重复的问题编号".to_string())),params:HashMap::new()});
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use validator::Validate;
+
+    use crate::model::request::operation::reorder_top_question::ReorderTopQuestion;
+    use crate::utils::error::BusinessError;
+    use crate::utils::logging::init_log;
+
+    //模拟questionCodes为空或有重复的情况
+    #[tokio::test]
+    async fn test_validate_question_codes() {
+        init_log().await;
+        let possible_values = vec![
+            (vec![], "questionCodes列表不能为空"),
+            (vec!["a".to_string(), "b".to_string(), "a".to_string()], "questionCodes列表不能有重复的问题编号"),
+        ];
+        for (question_codes, message) in possible_values{
+            let request = ReorderTopQuestion {
+                product_code: "product_code".to_string(),
+                question_codes,
+            };
+            let result = request.validate();
+            assert_eq!(result.is_err(), true);
+            if let Err(validation_errors) = result {
+                let business_error = validation_errors.into();
+                match business_error {
+                    BusinessError::InvalidParameter ((_,errors_parameters)) => {
+                        let error_parameters = errors_parameters.unwrap();
+                        let error_parameters0 = error_parameters.get(0).unwrap();
+                        assert_eq!(error_parameters0.get("field").unwrap(),"questionCodes");
+                        assert_eq!(error_parameters0.get("message").unwrap(),message);
+                    }
+                    _ => {
+                        panic!("错误类型不匹配")
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use serde::{Serialize,Deserialize};
+use validator::Validate;
+
+/// 从回收站恢复问题
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct RestoreQuestion {
+    #[validate(length(min = 1, message = "questionCode不能为空"))]
+    #[serde(rename = "questionCode")]
+    pub question_code: String,
+}
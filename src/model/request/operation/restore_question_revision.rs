@@ -0,0 +1,50 @@
+use serde::{Serialize,Deserialize};
+use validator::Validate;
+
+/// 将问题及其回答恢复到指定的修订版本
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct RestoreQuestionRevision {
+    #[validate(length(min = 1, message = "questionCode不能为空"))]
+    #[serde(rename = "questionCode")]
+    pub question_code: String,
+    #[validate(range(min = 1, message = "revision应该是大于等于1的整数"))]
+    pub revision: i32,
+    #[serde(rename = "operatorId")]
+    pub operator_id: Option<String>,
+}
+
+#[cfg(test)]
+mod test {
+    use validator::Validate;
+
+    use crate::model::request::operation::restore_question_revision::RestoreQuestionRevision;
+    use crate::utils::error::BusinessError;
+    use crate::utils::logging::init_log;
+
+    //模拟revision小于1的情况
+    #[tokio::test]
+    async fn test_validate_revision() {
+        init_log().await;
+        let request = RestoreQuestionRevision {
+            question_code: "question_code".to_string(),
+            revision: 0,
+            operator_id: None,
+        };
+        let result = request.validate();
+        assert_eq!(result.is_err(), true);
+        if let Err(validation_errors) = result {
+            let business_error = validation_errors.into();
+            match business_error {
+                BusinessError::InvalidParameter ((_,errors_parameters)) => {
+                    let error_parameters = errors_parameters.unwrap();
+                    let error_parameters0 = error_parameters.get(0).unwrap();
+                    assert_eq!(error_parameters0.get("field").unwrap(),"revision");
+                    assert_eq!(error_parameters0.get("message").unwrap(),"revision应该是大于等于1的整数");
+                }
+                _ => {
+                    panic!("错误类型不匹配")
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use serde::{Serialize,Deserialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use validator::{Validate, ValidationError};
+use crate::model::db::qa::ReviewStatus;
+use crate::utils::error::BusinessError;
+
+/// 审核对象类型
+#[derive(Serialize,Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ReviewTargetType {
+    Question,
+    Answer,
+    Reply,
+}
+
+impl ReviewTargetType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReviewTargetType::Question => "question",
+            ReviewTargetType::Answer => "answer",
+            ReviewTargetType::Reply => "reply",
+        }
+    }
+}
+
+/// 审核决定
+#[derive(Serialize,Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ReviewDecision {
+    Approved,
+    Rejected,
+}
+
+impl From<ReviewDecision> for ReviewStatus {
+    fn from(decision: ReviewDecision) -> Self {
+        match decision {
+            ReviewDecision::Approved => ReviewStatus::Approved,
+            ReviewDecision::Rejected => ReviewStatus::Rejected,
+        }
+    }
+}
+
+/// 批量审核问题和答案
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct ReviewQa {
+    #[validate(length(min = 1, message = "reviewerId不能为空"))]
+    #[serde(rename = "reviewerId")]
+    pub reviewer_id: String,
+    #[serde(rename = "reviewerName")]
+    pub reviewer_name: Option<String>,
+    #[validate(custom = "validate_item_list")]
+    #[serde(rename = "items")]
+    pub item_list: Vec<ReviewItem>,
+}
+
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct ReviewItem {
+    #[serde(rename = "targetType")]
+    pub target_type: ReviewTargetType,
+    pub id: i64,
+    pub decision: ReviewDecision,
+    #[validate(length(max = 500, message = "rejectReason不能超过500个字符"))]
+    #[serde(rename = "rejectReason")]
+    pub reject_reason: Option<String>,
+}
+
+impl ReviewQa {
+    pub async fn custom_validate(&self)->Result<(), BusinessError>{
+        self.validate()?;
+        for item in &self.item_list{
+            item.validate()?;
+            //拒绝时必须填写原因
+            let has_reason = item.reject_reason.as_ref().map(|reason|!reason.trim().is_empty()).unwrap_or(false);
+            if item.decision == ReviewDecision::Rejected && !has_reason {
+                let mut parameters = HashMap::new();
+                parameters.insert("field".to_string(), "rejectReason".to_string());
+                parameters.insert("message".to_string(), "审核拒绝时rejectReason不能为空".to_string());
+                parameters.insert("id".to_string(), item.id.to_string());
+                return Err(BusinessError::InvalidParameter((None,Some(vec![parameters]))));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn validate_item_list(value: &Vec<ReviewItem>) -> Result<(), ValidationError> {
+    if value.len() == 0 {
+        return Err(ValidationError{code:"".into(),message:Some(Cow::from("items列表不能为空".to_string())),params:HashMap::new()});
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::request::operation::review_qa::{ReviewQa, ReviewItem, ReviewTargetType, ReviewDecision};
+    use crate::utils::error::BusinessError;
+    use crate::utils::logging::init_log;
+
+    fn assert_invalid_field(result: Result<(), BusinessError>, field: &str, message: &str) {
+        assert_eq!(result.is_err(), true);
+        if let Err(error) = result {
+            match error {
+                BusinessError::InvalidParameter ((_,errors_parameters)) => {
+                    let error_parameters = errors_parameters.unwrap();
+                    let error_parameters0 = error_parameters.get(0).unwrap();
+                    assert_eq!(error_parameters0.get("field").unwrap(),field);
+                    assert_eq!(error_parameters0.get("message").unwrap(),message);
+                }
+                _ => {
+                    panic!("错误类型不匹配")
+                }
+            }
+        }
+    }
+
+    //模拟items列表为空的情况
+    #[tokio::test]
+    async fn test_validate_item_list_empty() {
+        init_log().await;
+        let request = ReviewQa {
+            reviewer_id: "reviewer_id".to_string(),
+            reviewer_name: None,
+            item_list: vec![],
+        };
+        assert_invalid_field(request.custom_validate().await, "items", "items列表不能为空");
+    }
+
+    //模拟审核拒绝但没有填写原因的情况
+    #[tokio::test]
+    async fn test_validate_reject_without_reason() {
+        init_log().await;
+        let request = ReviewQa {
+            reviewer_id: "reviewer_id".to_string(),
+            reviewer_name: None,
+            item_list: vec![
+                ReviewItem {
+                    target_type: ReviewTargetType::Answer,
+                    id: 1,
+                    decision: ReviewDecision::Rejected,
+                    reject_reason: Some(" ".to_string()),
+                },
+            ],
+        };
+        assert_invalid_field(request.custom_validate().await, "rejectReason", "审核拒绝时rejectReason不能为空");
+    }
+
+    //模拟审核通过不需要原因的情况
+    #[tokio::test]
+    async fn test_validate_approved() {
+        init_log().await;
+        let request = ReviewQa {
+            reviewer_id: "reviewer_id".to_string(),
+            reviewer_name: None,
+            item_list: vec![
+                ReviewItem {
+                    target_type: ReviewTargetType::Question,
+                    id: 1,
+                    decision: ReviewDecision::Approved,
+                    reject_reason: None,
+                },
+            ],
+        };
+        assert_eq!(request.custom_validate().await.is_ok(), true);
+    }
+}
@@ -0,0 +1,82 @@
+use serde::{Serialize,Deserialize};
+use std::collections::HashMap;
+use validator::Validate;
+use crate::utils::error::BusinessError;
+use crate::utils::locale::Locale;
+use crate::utils::sensitive_word;
+
+/// 保存问题及其回答某个语言的翻译，已有的翻译会被覆盖
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct SaveQuestionTranslation {
+    #[validate(length(min = 1, message = "questionCode不能为空"))]
+    #[serde(rename = "questionCode")]
+    pub question_code: String,
+    pub locale: Locale,
+    #[validate(length(min = 1, max = 2000, message = "questionContent长度应该在1到2000个字符之间"))]
+    #[serde(rename = "questionContent")]
+    pub question_content: String,
+    /// 需要翻译的回答，未传入的回答保留原有翻译
+    #[serde(default)]
+    pub answers: Vec<AnswerTranslation>,
+}
+
+#[derive(Serialize,Deserialize, Debug, Validate, Clone)]
+pub struct AnswerTranslation {
+    #[serde(rename = "answerId")]
+    pub answer_id: i64,
+    #[validate(length(min = 1, max = 2000, message = "answerContent长度应该在1到2000个字符之间"))]
+    #[serde(rename = "answerContent")]
+    pub answer_content: String,
+}
+
+impl SaveQuestionTranslation {
+    pub fn custom_validate(&self)->Result<(), BusinessError>{
+        self.validate()?;
+        //默认语言的内容保存在问题、回答记录中，通过updateQuestion修改
+        if self.locale.is_default() {
+            let mut parameters = HashMap::new();
+            parameters.insert("field".to_string(), "locale".to_string());
+            parameters.insert("message".to_string(), "默认语言的内容请通过updateQuestion修改".to_string());
+            return Err(BusinessError::InvalidParameter((None,Some(vec![parameters]))));
+        }
+        let filter = sensitive_word::current();
+        filter.reject("questionContent", &self.question_content)?;
+        for answer in &self.answers{
+            answer.validate()?;
+            filter.reject("answerContent", &answer.answer_content)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::locale::DEFAULT_LOCALE;
+
+    fn request(locale: Locale, answer_content: &str) -> SaveQuestionTranslation {
+        SaveQuestionTranslation {
+            question_code: "question_code".to_string(),
+            locale,
+            question_content: "Does it shrink?".to_string(),
+            answers: vec![AnswerTranslation { answer_id: 1, answer_content: answer_content.to_string() }],
+        }
+    }
+
+    fn assert_invalid_field(result: Result<(), BusinessError>, field: &str) {
+        match result {
+            Err(BusinessError::InvalidParameter((_, Some(parameters)))) => {
+                assert_eq!(parameters[0].get("field").unwrap(), field);
+            },
+            _ => panic!("错误类型不匹配"),
+        }
+    }
+
+    #[test]
+    fn test_custom_validate() {
+        let locale = Locale::ALL.into_iter().find(|locale| !locale.is_default()).unwrap();
+        assert!(request(locale, "No").custom_validate().is_ok());
+        assert_invalid_field(request(*DEFAULT_LOCALE, "No").custom_validate(), "locale");
+        assert_invalid_field(request(locale, "").custom_validate(), "answerContent");
+    }
+}
@@ -1,6 +1,56 @@
 use serde::Deserialize;
+use std::collections::HashMap;
+use crate::utils::error::BusinessError;
+
 #[derive(Deserialize)]
 pub struct TopQuestion {
     #[serde(rename = "questionCode")]
     pub question_code: String,
-}
\ No newline at end of file
+    /// 置顶到期时间，为空表示置顶不过期
+    #[serde(rename = "expireTime")]
+    pub expire_time: Option<chrono::NaiveDateTime>,
+}
+
+impl TopQuestion {
+    /// 置顶到期时间必须晚于当前时间
+    pub fn validate_expire_time(&self, now: chrono::NaiveDateTime) -> Result<(), BusinessError> {
+        match self.expire_time {
+            Some(expire_time) if expire_time <= now => {
+                let mut parameters = HashMap::new();
+                parameters.insert("field".to_string(), "expireTime".to_string());
+                parameters.insert("message".to_string(), "expireTime应该晚于当前时间".to_string());
+                Err(BusinessError::InvalidParameter((None,Some(vec![parameters]))))
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::request::operation::top_question::TopQuestion;
+    use crate::utils::error::BusinessError;
+
+    //模拟置顶到期时间早于当前时间的情况
+    #[test]
+    fn test_validate_expire_time() {
+        let now = chrono::NaiveDate::from_ymd_opt(2024, 11, 11).unwrap().and_hms_opt(12, 0, 0).unwrap();
+        let mut request = TopQuestion {
+            question_code: "question_code".to_string(),
+            expire_time: None,
+        };
+        assert_eq!(request.validate_expire_time(now).is_ok(), true);
+        request.expire_time = Some(now + chrono::Duration::days(1));
+        assert_eq!(request.validate_expire_time(now).is_ok(), true);
+        request.expire_time = Some(now);
+        match request.validate_expire_time(now) {
+            Err(BusinessError::InvalidParameter((_,errors_parameters))) => {
+                let error_parameters = errors_parameters.unwrap();
+                assert_eq!(error_parameters[0].get("field").unwrap(),"expireTime");
+            }
+            _ => {
+                panic!("错误类型不匹配")
+            }
+        }
+    }
+}
@@ -1,12 +1,16 @@
 use serde::{Serialize,Deserialize};
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use validator::{Validate, ValidationError};
+use crate::model::request::operation::create_question::validate_tags;
 use crate::model::db::qa::{
     Answer as DbAnswer,
-    Question as DbQuestion
+    Question as DbQuestion,
+    ReviewStatus,
 };
 use crate::utils::error::BusinessError;
+use crate::utils::markdown;
+use crate::utils::sensitive_word;
 
 #[derive(Serialize,Deserialize, Debug, Validate, Clone)]
 pub struct UpdateQuestion {
@@ -24,6 +28,9 @@ pub struct UpdateQuestion {
     #[validate(length(min = 1, message = "questionCode不能为空"))]
     #[serde(rename = "questionCode")]
     pub question_code: String,
+    /// 问题的标签，为空时保留原有标签，传入空列表时清除全部标签
+    #[validate(custom = "validate_tags")]
+    pub tags: Option<Vec<String>>,
 }
 
 impl UpdateQuestion{
@@ -47,14 +54,80 @@ impl UpdateQuestion{
 
     pub async fn custom_validate(&self)->Result<(), BusinessError>{
         self.validate()?;
+        let filter = sensitive_word::current();
+        filter.reject("questionContent", &self.question_content)?;
         for answer in &self.answer_list{
             answer.validate()?;
+            filter.reject("answerContent", &answer.answer_content)?;
         }        
         Ok(())
     }
+
+    /// 将问题和回答中需要屏蔽的敏感词替换为*，返回命中的需要人工审核的敏感词
+    pub fn mask_sensitive_words(&mut self)->Vec<String>{
+        let filter = sensitive_word::current();
+        let mut flagged = filter.check(&self.question_content).flagged;
+        self.question_content = filter.mask(&self.question_content);
+        for answer in &mut self.answer_list{
+            for word in filter.check(&answer.answer_content).flagged{
+                if !flagged.contains(&word){
+                    flagged.push(word);
+                }
+            }
+            answer.answer_content = filter.mask(&answer.answer_content);
+        }
+        flagged
+    }
 }
 
 
+impl UpdateQuestion{
+    /// 依据原有回答生成更新后的回答列表：带id的回答在原记录上修改，保留id、创建时间和投票计数
+    /// 没有id的回答与内容相同且未被其它回答使用的原有回答对应，找不到时为新增
+    /// 内容不变的回答保持原审核状态，内容修改后重新进入待审核；id不属于该问题时返回参数错误
+    pub fn into_db_answers(&self, old_answers:&[DbAnswer], now:chrono::NaiveDateTime)->Result<Vec<DbAnswer>, BusinessError>{
+        let mut used_ids: HashSet<i64> = self.answer_list.iter().filter_map(|answer|answer.id).collect();
+        let mut db_answers = vec![];
+        for answer in &self.answer_list{
+            let old_answer = match answer.id {
+                Some(id) => match old_answers.iter().find(|old_answer|old_answer.id == Some(id)) {
+                    Some(old_answer) => Some(old_answer),
+                    None => {
+                        let mut parameters = HashMap::new();
+                        parameters.insert("field".to_string(), "answers".to_string());
+                        parameters.insert("message".to_string(), format!("回答{}不属于该问题", id));
+                        return Err(BusinessError::InvalidParameter((None,Some(vec![parameters]))));
+                    }
+                },
+                None => old_answers.iter()
+                    .filter(|old_answer|old_answer.id.map(|id|!used_ids.contains(&id)).unwrap_or(false))
+                    .find(|old_answer|old_answer.answer_content == answer.answer_content),
+            };
+            let db_answer = match old_answer {
+                Some(old_answer) => {
+                    if let Some(id) = old_answer.id{
+                        used_ids.insert(id);
+                    }
+                    let mut db_answer = old_answer.clone();
+                    if db_answer.answer_content != answer.answer_content{
+                        db_answer.answer_html = Some(markdown::render(&answer.answer_content));
+                        db_answer.answer_content = answer.answer_content.clone();
+                        db_answer.review_status = ReviewStatus::Pending.into();
+                        db_answer.reject_reason = None;
+                        db_answer.reviewer_id = None;
+                        db_answer.review_time = None;
+                        db_answer.update_time = now;
+                    }
+                    db_answer
+                },
+                None => answer.into_db_answer(self.question_code.clone()),
+            };
+            db_answers.push(db_answer);
+        }
+        Ok(db_answers)
+    }
+}
+
 impl UpdateAnswer{
     pub fn into_db_answer(&self,question_code:String)->DbAnswer{
         let db_answer: DbAnswer = DbAnswer::new(
@@ -69,6 +142,9 @@ impl UpdateAnswer{
 
 #[derive(Serialize, Deserialize, Debug, Validate,Clone)]
 pub struct UpdateAnswer{
+    /// 原有回答的id，为空时新增回答；不在列表中的原有回答会被删除
+    pub id:Option<i64>,
+    /// 回答内容，支持Markdown（列表、粗体、链接等），保存时同时保存渲染后的HTML
     #[validate(length(min = 1, message = "answerContent不能为空"))]
     #[serde(rename = "answerContent")]
     pub answer_content:String,
@@ -78,6 +154,10 @@ fn validate_answer_list(value: &Vec<UpdateAnswer>) -> Result<(), ValidationError
     if value.len() == 0 {
         return Err(ValidationError{code:"".into(),message:Some(Cow::from("answers列表不能为空".to_string())),params:HashMap::new()});
     }
+    let mut ids = HashSet::new();
+    if value.iter().filter_map(|answer|answer.id).any(|id|!ids.insert(id)) {
+        return Err(ValidationError{code:"".into(),message:Some(Cow::from("answers中的回答id不能重复".to_string())),params:HashMap::new()});
+    }
     Ok(())
 }
 
@@ -87,6 +167,8 @@ mod test {
     use crate::model::request::operation::update_question::UpdateAnswer;
     use crate::utils::error::BusinessError;
     use crate::utils::logging::init_log;
+    use crate::utils::datetime::now_local;
+    use crate::model::db::qa::{Answer as DbAnswer, ReviewStatus};
 
     //验证参数校验异常情况的总体情况
     #[tokio::test]
@@ -107,6 +189,7 @@ mod test {
         let rank = 1;
         let answer_list = vec![
             UpdateAnswer {
+                id: None,
                 answer_content: "answer_content".to_string()
             },
         ];
@@ -117,6 +200,7 @@ mod test {
             question_content,
             rank,
             answer_list,
+            tags: None,
         };
         let result = question.custom_validate().await;
         assert_eq!(result.is_err(), true);
@@ -152,6 +236,7 @@ mod test {
         let rank = 1;
         let answer_list = vec![
             UpdateAnswer {
+                id: None,
                 answer_content: "answer_content".to_string()
             },
         ];
@@ -162,6 +247,7 @@ mod test {
             question_content,
             rank,
             answer_list,
+            tags: None,
         };
         let result = question.custom_validate().await;
         assert_eq!(result.is_err(), true);
@@ -204,6 +290,7 @@ mod test {
             question_content,
             rank,
             answer_list,
+            tags: None,
         };
         let result = question.custom_validate().await;
         assert_eq!(result.is_err(), true);
@@ -238,6 +325,7 @@ mod test {
         let rank = 1;
         let answer_list = vec![
             UpdateAnswer {
+                id: None,
                 answer_content: "".to_string()
             },
         ];
@@ -248,6 +336,7 @@ mod test {
             question_content,
             rank,
             answer_list,
+            tags: None,
         };
         let result = question.custom_validate().await;
         assert_eq!(result.is_err(), true);
@@ -271,6 +360,71 @@ mod test {
         }
     }
 
-    
+    //带id的回答在原记录上修改，内容不变时保持原样，没有id的回答为新增
+    #[test]
+    fn test_into_db_answers(){
+        let mut old_answer = DbAnswer::new("question_code".to_string(),"answer_content".to_string(),None,None);
+        old_answer.id = Some(1);
+        old_answer.helpful_count = 2;
+        let mut question = UpdateQuestion {
+            sku_code: None,
+            product_code: "product_code".to_string(),
+            question_code: "question_code".to_string(),
+            question_content: "question_content".to_string(),
+            rank: 1,
+            answer_list: vec![
+                UpdateAnswer { id: Some(1), answer_content: "answer_content".to_string() },
+                UpdateAnswer { id: None, answer_content: "new_answer".to_string() },
+            ],
+            tags: None,
+        };
+        let now = now_local();
+        let answers = question.into_db_answers(&[old_answer.clone()], now).unwrap();
+        assert_eq!(answers[0], old_answer);
+        assert_eq!(answers[1].id, None);
 
+        question.answer_list[0].answer_content = "changed".to_string();
+        let answers = question.into_db_answers(&[old_answer.clone()], now).unwrap();
+        assert_eq!(answers[0].id, Some(1));
+        assert_eq!(answers[0].helpful_count, 2);
+        assert_eq!(answers[0].answer_content, "changed");
+        assert_eq!(answers[0].update_time, now);
+
+        question.answer_list[0].id = Some(2);
+        assert!(question.into_db_answers(&[old_answer], now).is_err());
+    }
+
+    //审核通过的回答内容不变时保持审核通过，没有传入id时按内容对应原有回答，内容修改后重新待审核
+    #[test]
+    fn test_into_db_answers_keep_review_status(){
+        let mut approved_answer = DbAnswer::new("question_code".to_string(),"answer_content".to_string(),None,None);
+        approved_answer.id = Some(1);
+        approved_answer.review_status = ReviewStatus::Approved.into();
+        approved_answer.reviewer_id = Some("reviewer".to_string());
+        let mut question = UpdateQuestion {
+            sku_code: None,
+            product_code: "product_code".to_string(),
+            question_code: "question_code".to_string(),
+            question_content: "question_content".to_string(),
+            rank: 1,
+            answer_list: vec![
+                UpdateAnswer { id: None, answer_content: "answer_content".to_string() },
+                UpdateAnswer { id: None, answer_content: "answer_content".to_string() },
+            ],
+            tags: None,
+        };
+        let now = now_local();
+        let pending: i8 = ReviewStatus::Pending.into();
+        let answers = question.into_db_answers(&[approved_answer.clone()], now).unwrap();
+        assert_eq!(answers[0], approved_answer);
+        //同一个原有回答只对应一次，内容重复的第二个回答为新增
+        assert_eq!(answers[1].id, None);
+        assert_eq!(answers[1].review_status, pending);
+
+        question.answer_list = vec![UpdateAnswer { id: Some(1), answer_content: "changed".to_string() }];
+        let answers = question.into_db_answers(&[approved_answer], now).unwrap();
+        assert_eq!(answers[0].id, Some(1));
+        assert_eq!(answers[0].review_status, pending);
+        assert_eq!(answers[0].reviewer_id, None);
+    }
 }
\ No newline at end of file
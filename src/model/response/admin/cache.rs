@@ -0,0 +1,75 @@
+use serde::Serialize;
+use crate::utils::cache::{CacheEntryDetail, CacheEntryInfo, CacheNamespace as CacheNamespaceTrait};
+
+/// 缓存命名空间的配置和当前缓存项数量，ttl为秒数，null表示不会过期
+#[derive(Serialize,Debug, Clone)]
+pub struct CacheNamespace {
+    pub name: String,
+    pub capacity: u64,
+    #[serde(rename = "defaultTtlSeconds")]
+    pub default_ttl_seconds: Option<u64>,
+    #[serde(rename = "entryCount")]
+    pub entry_count: u64,
+    /// key的字符串格式，查询和清除单个缓存项时按该格式传入key
+    #[serde(rename = "keyFormat")]
+    pub key_format: String,
+}
+
+impl CacheNamespace {
+    pub fn from_namespace(namespace: &dyn CacheNamespaceTrait) -> Self {
+        Self {
+            name: namespace.name().to_string(),
+            capacity: namespace.capacity(),
+            default_ttl_seconds: namespace.default_expiration().as_duration().map(|duration|duration.as_secs()),
+            entry_count: namespace.entry_count(),
+            key_format: namespace.key_format().to_string(),
+        }
+    }
+}
+
+/// 缓存key及其剩余有效时间（秒），null表示不会过期
+#[derive(Serialize,Debug, Clone)]
+pub struct CacheKey {
+    pub key: String,
+    #[serde(rename = "remainingTtlSeconds")]
+    pub remaining_ttl_seconds: Option<u64>,
+}
+
+impl From<CacheEntryInfo> for CacheKey {
+    fn from(entry: CacheEntryInfo) -> Self {
+        Self {
+            key: entry.key,
+            remaining_ttl_seconds: entry.remaining_ttl.map(|duration|duration.as_secs()),
+        }
+    }
+}
+
+/// 按前缀查询的结果，matchedCount为匹配的key总数，keys最多返回limit个
+#[derive(Serialize,Debug, Clone)]
+pub struct CacheKeyList {
+    pub namespace: String,
+    #[serde(rename = "entryCount")]
+    pub entry_count: u64,
+    #[serde(rename = "matchedCount")]
+    pub matched_count: usize,
+    pub keys: Vec<CacheKey>,
+}
+
+/// 单个缓存项，value为调试格式的字符串
+#[derive(Serialize,Debug, Clone)]
+pub struct CacheEntry {
+    pub key: String,
+    #[serde(rename = "remainingTtlSeconds")]
+    pub remaining_ttl_seconds: Option<u64>,
+    pub value: String,
+}
+
+impl From<CacheEntryDetail> for CacheEntry {
+    fn from(entry: CacheEntryDetail) -> Self {
+        Self {
+            key: entry.key,
+            remaining_ttl_seconds: entry.remaining_ttl.map(|duration|duration.as_secs()),
+            value: entry.value,
+        }
+    }
+}
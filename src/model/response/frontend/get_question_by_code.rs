@@ -1,6 +1,9 @@
 use serde::Serialize;
 use crate::model::db::qa::{Question as DbQuestion, Answer as DbAnswer };
 use crate::model::cache::qa::{Question as CacheQuestion, Answer as CacheAnswer };
+use crate::model::request::frontend::get_question_by_code::AnswerSort;
+use crate::model::response::frontend::reply::ReplyNode;
+use crate::models::response_models::PageResponse;
 #[derive(Serialize,Debug)]
 pub struct GetQuestionByCode{
     #[serde(rename="idStr")]
@@ -30,8 +33,12 @@ pub struct AnswerGetQuestionByCode {
     pub id: i64,
     #[serde(rename = "questionCode")]
     pub question_code: String,
+    /// 回答内容，Markdown原文
     #[serde(rename = "answerContent")]
     pub answer_content: String,
+    /// 回答内容，Markdown渲染并清理后的HTML
+    #[serde(rename = "answerHtml")]
+    pub answer_html: String,
     #[serde(rename = "createUserId")]
     pub create_user_id: Option<String>,
     #[serde(rename = "creatorName")]
@@ -41,29 +48,35 @@ pub struct AnswerGetQuestionByCode {
     #[serde(rename = "updateTime")]
     pub update_time: String,
     pub status: i8,
+    #[serde(rename = "helpfulCount")]
+    pub helpful_count: i32,
+    #[serde(rename = "unhelpfulCount")]
+    pub unhelpful_count: i32,
+    /// 第一页一级追问回复（附带下级回复），列表接口不返回
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replies: Option<PageResponse<ReplyNode>>,
 }   
 
 
 impl GetQuestionByCode{
-    pub fn from_cache(question_option:Option<CacheQuestion>)->Option<Self>{
-        if let Some(question)=question_option{
-            let response = Self{
-                id_str: question.id.to_string(),
-                id: question.id,
-                question_code: question.question_code,
-                product_code: question.product_code,
-                question_content: question.question_content,
-                creator_name: question.creator_name,
-                create_user_id: question.create_user_id,
-                sort: question.sort,
-                rank: question.rank,
-                create_time: question.create_time.to_string(),
-                update_time: question.update_time.to_string(),
-                answers:question.answer_list.into_iter().map(|answer|AnswerGetQuestionByCode::from_cache(answer)).collect(),
-            };
-            Some(response)
-        }else{
-            None
+    /// reply_page_size为每个回答返回的一级追问回复数量
+    pub fn from_cache(question_option:Option<CacheQuestion>,reply_page_size:i64)->Option<Self>{
+        question_option.map(|question|Self::from_cache_question(question,reply_page_size))
+    }
+    pub fn from_cache_question(question:CacheQuestion,reply_page_size:i64)->Self{
+        Self{
+            id_str: question.id.to_string(),
+            id: question.id,
+            question_code: question.question_code,
+            product_code: question.product_code,
+            question_content: question.question_content,
+            creator_name: question.creator_name,
+            create_user_id: question.create_user_id,
+            sort: question.sort,
+            rank: question.rank,
+            create_time: question.create_time.to_string(),
+            update_time: question.update_time.to_string(),
+            answers:question.answer_list.into_iter().map(|answer|AnswerGetQuestionByCode::from_cache(answer,reply_page_size)).collect(),
         }
     }
     pub fn from_db(db_question:DbQuestion,db_answer:Vec<DbAnswer>)->Self{
@@ -86,29 +99,81 @@ impl GetQuestionByCode{
     }
 }
 
+impl GetQuestionByCode{
+    /// 按指定方式对回答排序，缓存和数据库中的回答都按创建顺序排列
+    pub fn sort_answers(&mut self, answer_sort:AnswerSort){
+        if answer_sort == AnswerSort::Helpfulness {
+            self.answers.sort_by(|a, b|{
+                let score = |answer:&AnswerGetQuestionByCode|answer.helpful_count - answer.unhelpful_count;
+                score(b).cmp(&score(a))
+                    .then(b.helpful_count.cmp(&a.helpful_count))
+                    .then(a.id.cmp(&b.id))
+            });
+        }
+    }
+}
+
 impl AnswerGetQuestionByCode{
-    pub fn from_cache(answer:CacheAnswer)->Self{
+    pub fn from_cache(answer:CacheAnswer,reply_page_size:i64)->Self{
+        let replies = answer.replies.map(|replies|ReplyNode::page(replies, 1, reply_page_size));
         Self{
             id: answer.id,
             question_code: answer.question_code,
             answer_content: answer.answer_content,
+            answer_html: answer.answer_html,
             create_user_id: answer.create_user_id,
             creator_name: answer.creator_name,
             create_time: answer.create_time.to_string(),
             update_time: answer.update_time.to_string(),
             status: answer.status,
+            helpful_count: answer.helpful_count,
+            unhelpful_count: answer.unhelpful_count,
+            replies,
         }
     }
     pub fn from_db(answer:DbAnswer)->Self{
+        let answer_html = answer.html();
         Self{
             id: answer.id.unwrap_or(0),
             question_code: answer.question_code,
             answer_content: answer.answer_content,
+            answer_html,
             create_user_id: answer.create_user_id,
             creator_name: answer.creator_name,
             create_time: answer.create_time.to_string(),
             update_time: answer.update_time.to_string(),
             status: answer.status,
+            helpful_count: answer.helpful_count,
+            unhelpful_count: answer.unhelpful_count,
+            replies: None,
         }
     }
 }
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_sort_answers_by_helpfulness(){
+        let question_code = "question_code".to_string();
+        let answers = vec![(1, 1, 0), (2, 5, 2), (3, 3, 0), (4, 0, 4)].into_iter()
+            .map(|(id, helpful_count, unhelpful_count)|{
+                let mut answer = DbAnswer::new(question_code.clone(), "answer_content".to_string(), None, None);
+                answer.id = Some(id);
+                answer.helpful_count = helpful_count;
+                answer.unhelpful_count = unhelpful_count;
+                answer
+            })
+            .collect();
+        let question = DbQuestion::with_question_code(question_code.clone(), None, "product_code".to_string(), "question_content".to_string(), None, None, 1);
+        let mut response = GetQuestionByCode::from_db(question, answers);
+        assert_eq!(response.answers[0].answer_content, "answer_content");
+        assert_eq!(response.answers[0].answer_html, "<p>answer_content</p>\n");
+        response.sort_answers(AnswerSort::Default);
+        assert_eq!(response.answers.iter().map(|answer|answer.id).collect::<Vec<i64>>(), vec![1, 2, 3, 4]);
+        //2和3的净票数相同，有用票数多的2排在前面
+        response.sort_answers(AnswerSort::Helpfulness);
+        assert_eq!(response.answers.iter().map(|answer|answer.id).collect::<Vec<i64>>(), vec![2, 3, 1, 4]);
+    }
+}
@@ -1,2 +1,4 @@
 pub mod find_sku;
-pub mod get_question_by_code;
\ No newline at end of file
+pub mod get_question_by_code;
+pub mod search_question;
+pub mod reply;
\ No newline at end of file
@@ -0,0 +1,111 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use crate::model::cache::qa::Reply as CacheReply;
+use crate::models::response_models::PageResponse;
+
+/// 追问回复树的节点，children为该回复下审核通过的回复
+#[derive(Serialize,Debug)]
+pub struct ReplyNode {
+    pub id: i64,
+    #[serde(rename = "parentId")]
+    pub parent_id: Option<i64>,
+    #[serde(rename = "replyContent")]
+    pub reply_content: String,
+    #[serde(rename = "createUserId")]
+    pub create_user_id: Option<String>,
+    #[serde(rename = "creatorName")]
+    pub creator_name: Option<String>,
+    #[serde(rename = "createTime")]
+    pub create_time: String,
+    pub children: Vec<ReplyNode>,
+}
+
+impl ReplyNode {
+    /// 将同一回答下的回复组装成树，返回一级回复；上级回复未展示（如未审核通过）的回复也不展示
+    pub fn tree(replies: Vec<CacheReply>) -> Vec<Self> {
+        let mut children_by_parent: HashMap<Option<i64>, Vec<CacheReply>> = HashMap::new();
+        for reply in replies {
+            children_by_parent.entry(reply.parent_id).or_default().push(reply);
+        }
+        Self::children(&mut children_by_parent, None)
+    }
+
+    fn children(children_by_parent: &mut HashMap<Option<i64>, Vec<CacheReply>>, parent_id: Option<i64>) -> Vec<Self> {
+        let mut replies = children_by_parent.remove(&parent_id).unwrap_or_default();
+        replies.sort_by_key(|reply| reply.id);
+        replies.into_iter()
+            .map(|reply| {
+                let children = Self::children(children_by_parent, Some(reply.id));
+                Self {
+                    id: reply.id,
+                    parent_id: reply.parent_id,
+                    reply_content: reply.reply_content,
+                    create_user_id: reply.create_user_id,
+                    creator_name: reply.creator_name,
+                    create_time: reply.create_time.to_string(),
+                    children,
+                }
+            })
+            .collect()
+    }
+
+    /// 按一级回复分页，每个一级回复附带全部下级回复
+    pub fn page(replies: Vec<CacheReply>, current_pageno: i64, page_size: i64) -> PageResponse<Self> {
+        let roots = Self::tree(replies);
+        let total_records = roots.len() as i64;
+        let total_pages = (total_records + page_size - 1) / page_size;
+        let offset = ((current_pageno - 1) * page_size) as usize;
+        let list = roots.into_iter().skip(offset).take(page_size as usize).collect();
+        PageResponse::new(total_records, current_pageno, page_size, total_pages, Some(list))
+    }
+
+    /// 已在数据库中分页的一页一级回复及其下级回复组装成的分页，total_records为一级回复的总数
+    pub fn page_of_roots(replies: Vec<CacheReply>, total_records: i64, current_pageno: i64, page_size: i64) -> PageResponse<Self> {
+        let total_pages = (total_records + page_size - 1) / page_size;
+        PageResponse::new(total_records, current_pageno, page_size, total_pages, Some(Self::tree(replies)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::datetime::now_local;
+
+    fn reply(id: i64, parent_id: Option<i64>) -> CacheReply {
+        CacheReply {
+            id,
+            parent_id,
+            reply_content: format!("reply_{}", id),
+            create_user_id: None,
+            creator_name: None,
+            create_time: now_local(),
+        }
+    }
+
+    #[test]
+    fn test_tree_and_page() {
+        //回复4的上级回复9未展示，回复4也不展示
+        let replies = vec![reply(5, Some(2)), reply(1, None), reply(2, Some(1)), reply(3, None), reply(4, Some(9)), reply(6, None)];
+        let page = ReplyNode::page(replies, 1, 2);
+        assert_eq!(page.total_records, 3);
+        assert_eq!(page.total_pages, 2);
+        let list = page.data.unwrap();
+        assert_eq!(list.iter().map(|node| node.id).collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(list[0].children[0].id, 2);
+        assert_eq!(list[0].children[0].children[0].id, 5);
+        assert!(list[1].children.is_empty());
+
+        let page = ReplyNode::page(vec![reply(1, None), reply(3, None), reply(6, None)], 2, 2);
+        assert_eq!(page.data.unwrap().iter().map(|node| node.id).collect::<Vec<_>>(), vec![6]);
+    }
+
+    #[test]
+    fn test_page_of_roots() {
+        let page = ReplyNode::page_of_roots(vec![reply(3, None), reply(6, None), reply(7, Some(6))], 5, 2, 2);
+        assert_eq!(page.total_records, 5);
+        assert_eq!(page.total_pages, 3);
+        let list = page.data.unwrap();
+        assert_eq!(list.iter().map(|node| node.id).collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(list[1].children[0].id, 7);
+    }
+}
@@ -0,0 +1,35 @@
+use serde::Serialize;
+use crate::model::db::qa::{SearchHit, Answer as DbAnswer };
+use crate::model::response::frontend::get_question_by_code::GetQuestionByCode;
+use crate::utils::highlight::highlight;
+
+/// 摘要的最大字符数
+const SNIPPET_MAX_CHARS: usize = 80;
+
+#[derive(Serialize,Debug)]
+pub struct SearchQuestion{
+    #[serde(flatten)]
+    pub question: GetQuestionByCode,
+    pub score: f64,
+    /// 问题内容的高亮摘要，内容已做HTML转义，命中的关键词用<em>包裹
+    #[serde(rename = "questionHighlight")]
+    pub question_highlight: String,
+    /// 第一条命中关键词的回答的高亮摘要，没有回答命中时为None
+    #[serde(rename = "answerHighlight", skip_serializing_if = "Option::is_none")]
+    pub answer_highlight: Option<String>,
+}
+
+impl SearchQuestion{
+    pub fn from_db(hit:SearchHit, db_answer:Vec<DbAnswer>, keywords:&[Vec<char>])->Self{
+        let question_highlight = highlight(&hit.question.question_content, keywords, SNIPPET_MAX_CHARS);
+        let answer_highlight = db_answer.iter()
+            .map(|answer|highlight(&answer.answer_content, keywords, SNIPPET_MAX_CHARS))
+            .find(|snippet|snippet.contains("<em>"));
+        Self{
+            question: GetQuestionByCode::from_db(hit.question, db_answer),
+            score: hit.score,
+            question_highlight,
+            answer_highlight,
+        }
+    }
+}
@@ -1,3 +1,4 @@
 pub mod operation;
 pub mod frontend;
-pub mod client;
\ No newline at end of file
+pub mod client;
+pub mod admin;
\ No newline at end of file
@@ -0,0 +1,65 @@
+use serde::Serialize;
+use crate::utils::similarity::similarity;
+
+/// 相似度达到该值的问题视为可能重复
+const DUPLICATE_THRESHOLD: f64 = 0.6;
+/// 最多返回的可能重复的问题数量
+const DUPLICATE_LIMIT: usize = 5;
+/// 先用全文索引取相关度最高的候选问题，再在候选中计算相似度
+pub const DUPLICATE_CANDIDATE_LIMIT: i64 = 50;
+
+#[derive(Serialize,Debug, Clone)]
+pub struct CreateQuestion {
+    pub id: String,
+    #[serde(rename = "questionCode")]
+    pub question_code: String,
+    /// 同一商品下可能重复的问题，按相似度从高到低排列
+    pub duplicates: Vec<DuplicateQuestion>,
+}
+
+#[derive(Serialize,Debug, Clone, PartialEq)]
+pub struct DuplicateQuestion {
+    #[serde(rename = "questionCode")]
+    pub question_code: String,
+    #[serde(rename = "questionContent")]
+    pub question_content: String,
+    pub similarity: f64,
+}
+
+impl DuplicateQuestion {
+    /// 从同一商品的问题(question_code, question_content)中找出与question_content相似的问题
+    pub fn find(question_content: &str, candidates: Vec<(String, String)>) -> Vec<Self> {
+        let mut duplicates: Vec<Self> = candidates.into_iter()
+            .map(|(question_code, candidate_content)|{
+                let similarity = similarity(question_content, &candidate_content);
+                Self {
+                    question_code,
+                    question_content: candidate_content,
+                    similarity,
+                }
+            })
+            .filter(|duplicate|duplicate.similarity >= DUPLICATE_THRESHOLD)
+            .collect();
+        duplicates.sort_by(|a, b|b.similarity.total_cmp(&a.similarity));
+        duplicates.truncate(DUPLICATE_LIMIT);
+        duplicates
+    }
+}
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_find_duplicate_question(){
+        let candidates = vec![
+            ("a".to_string(), "快递几天能到".to_string()),
+            ("b".to_string(), "这件衣服洗后会缩水吗".to_string()),
+            ("c".to_string(), "这件衣服会缩水吗？".to_string()),
+        ];
+        let duplicates = DuplicateQuestion::find("这件衣服会缩水吗", candidates);
+        let question_codes: Vec<&str> = duplicates.iter().map(|duplicate|duplicate.question_code.as_str()).collect();
+        assert_eq!(question_codes, vec!["c", "b"]);
+        assert_eq!(duplicates[0].similarity, 1.0);
+    }
+}
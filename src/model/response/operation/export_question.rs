@@ -0,0 +1,86 @@
+use crate::model::db::qa::AnswerStatus;
+use crate::model::request::operation::import_question::{
+    escape_cell, TAG_SEPARATOR,
+    COLUMN_QUESTION_CODE, COLUMN_PRODUCT_CODE, COLUMN_SKU_CODE, COLUMN_QUESTION_CONTENT,
+    COLUMN_CREATE_USER_ID, COLUMN_CREATOR_NAME, COLUMN_RANK, COLUMN_TAGS, COLUMN_ANSWER_CONTENT,
+};
+use crate::model::response::operation::find_question_list_for_trad::Question;
+
+/// 导出CSV的表头，answer_columns为回答列的数量
+pub fn csv_header(answer_columns: usize) -> Vec<String> {
+    let mut header: Vec<String> = [
+        COLUMN_QUESTION_CODE, COLUMN_PRODUCT_CODE, COLUMN_SKU_CODE, COLUMN_QUESTION_CONTENT,
+        COLUMN_CREATE_USER_ID, COLUMN_CREATOR_NAME, COLUMN_RANK, COLUMN_TAGS,
+    ].iter().map(|column| column.to_string()).collect();
+    header.extend((1..=answer_columns).map(|index| format!("{}{}", COLUMN_ANSWER_CONTENT, index)));
+    header
+}
+
+/// 问题对应的CSV行，只导出有效的回答（Markdown原文），回答不足answer_columns时补空列
+pub fn csv_row(question: &Question, answer_columns: usize) -> Vec<String> {
+    let active: i8 = AnswerStatus::Active.into();
+    let mut row = vec![
+        question.question_code.clone(),
+        question.product_code.clone(),
+        question.sku_code.clone().unwrap_or_default(),
+        question.question_content.clone(),
+        question.create_user_id.clone().unwrap_or_default(),
+        question.creator_name.clone().unwrap_or_default(),
+        question.rank.to_string(),
+        question.tags.join(&TAG_SEPARATOR.to_string()),
+    ];
+    let column_count = row.len() + answer_columns;
+    row.extend(question.answers.iter()
+        .filter(|answer| answer.status == active)
+        .map(|answer| answer.answer_content.clone()));
+    if row.len() < column_count {
+        row.resize(column_count, String::new());
+    }
+    row.iter().map(|value| escape_cell(value)).collect()
+}
+
+/// 将多行写成CSV内容，行的列数可以不同
+pub fn write_csv(rows: &[Vec<String>]) -> Result<Vec<u8>, csv::Error> {
+    let mut writer = csv::WriterBuilder::new()
+        .flexible(true)
+        .from_writer(vec![]);
+    for row in rows {
+        writer.write_record(row)?;
+    }
+    writer.into_inner().map_err(|error| error.into_error().into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::request::operation::import_question::parse_question_csv;
+
+    #[test]
+    fn test_export_can_be_imported() {
+        let question = Question {
+            id_str: "1".to_string(),
+            id: 1,
+            question_code: "q1".to_string(),
+            product_code: "P001".to_string(),
+            sku_code: None,
+            question_content: "=能机洗吗？".to_string(),
+            creator_name: Some("运营".to_string()),
+            create_user_id: Some("user_1".to_string()),
+            sort: 0,
+            rank: 3,
+            create_time: "2024-11-15 00:00:00".to_string(),
+            review_status: 1,
+            reject_reason: None,
+            tags: vec!["洗涤".to_string(), "面料".to_string()],
+            answers: vec![],
+        };
+        let content = write_csv(&[csv_header(2), csv_row(&question, 2)]).unwrap();
+        assert_eq!(String::from_utf8(content.clone()).unwrap().lines().nth(1).unwrap(),
+            "q1,P001,,'=能机洗吗？,user_1,运营,3,洗涤|面料,,");
+        let rows = parse_question_csv(&content).unwrap();
+        let imported = rows[0].question.as_ref().unwrap();
+        assert_eq!(imported.question_content, "=能机洗吗？");
+        assert_eq!(imported.tags, question.tags);
+        assert_eq!(imported.rank, 3);
+    }
+}
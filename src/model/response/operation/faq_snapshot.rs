@@ -0,0 +1,37 @@
+use serde::Serialize;
+use crate::model::response::frontend::get_question_by_code::GetQuestionByCode;
+
+/// FAQ静态快照文件的格式版本，快照结构不兼容地变化时加1
+pub const FAQ_SNAPSHOT_FORMAT_VERSION: i32 = 1;
+
+/// 商品的FAQ静态快照：商品下全部前台展示的问题和回答，排序与前台问题列表一致
+#[derive(Serialize,Debug)]
+pub struct FaqSnapshot {
+    #[serde(rename = "formatVersion")]
+    pub format_version: i32,
+    #[serde(rename = "productCode")]
+    pub product_code: String,
+    /// 快照内容每变化一次加1
+    pub version: i64,
+    /// questions的SHA-256哈希值，同时作为ETag
+    #[serde(rename = "contentHash")]
+    pub content_hash: String,
+    #[serde(rename = "generateTime")]
+    pub generate_time: String,
+    #[serde(rename = "questionCount")]
+    pub question_count: usize,
+    pub questions: Vec<GetQuestionByCode>,
+}
+
+/// 生成快照的结果，changed为false表示内容未变化，沿用已有的快照
+#[derive(Serialize,Debug, Clone)]
+pub struct BuildFaqSnapshot {
+    #[serde(rename = "productCode")]
+    pub product_code: String,
+    pub version: i64,
+    #[serde(rename = "contentHash")]
+    pub content_hash: String,
+    #[serde(rename = "questionCount")]
+    pub question_count: usize,
+    pub changed: bool,
+}
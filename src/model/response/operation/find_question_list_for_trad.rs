@@ -5,9 +5,15 @@ use crate::model::db::qa::Answer as DbAnswer;
 
 #[derive(Serialize,Deserialize, Debug, Validate, Clone)]
 pub struct FindQuestionListForTrad {
-    pub page:Page,
-    #[serde(rename = "totalCount")]
-    pub total_records:i64,
+    /// 游标分页模式下为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page:Option<Page>,
+    /// 游标分页模式下未要求返回总数时为空
+    #[serde(rename = "totalCount", skip_serializing_if = "Option::is_none")]
+    pub total_records:Option<i64>,
+    /// 游标分页模式下下一页的游标，为空表示没有下一页
+    #[serde(rename = "nextCursor", skip_serializing_if = "Option::is_none")]
+    pub next_cursor:Option<String>,
     pub list:Vec<Question>,
 }
 
@@ -31,9 +37,19 @@ impl FindQuestionListForTrad {
             page_size,
             total_pages
         };
+        FindQuestionListForTrad {
+            total_records:Some(total_records),
+            page:Some(page),
+            next_cursor:None,
+            list,
+        }
+    }
+
+    pub fn with_cursor(total_records:Option<i64>,next_cursor:Option<String>,list:Vec<Question>) -> Self {
         FindQuestionListForTrad {
             total_records,
-            page,
+            page:None,
+            next_cursor,
             list,
         }
     }
@@ -70,6 +86,8 @@ pub struct Question {
     pub question_code: String,
     #[serde(rename = "productCode")]
     pub product_code: String,
+    #[serde(rename = "skuCode")]
+    pub sku_code: Option<String>,
     #[serde(rename = "questionContent")]
     pub question_content: String,
     #[serde(rename = "creatorName")]
@@ -80,6 +98,11 @@ pub struct Question {
     pub rank: i32,
     #[serde(rename = "createTime")]
     pub create_time: String,
+    #[serde(rename = "reviewStatus")]
+    pub review_status: i8,
+    #[serde(rename = "rejectReason")]
+    pub reject_reason: Option<String>,
+    pub tags: Vec<String>,
     pub answers: Vec<Answer>,
 }
 
@@ -89,8 +112,12 @@ pub struct Answer {
     pub id: i64,
     #[serde(rename = "questionCode")]
     pub question_code: String,
+    /// Markdown原文，运营端编辑使用
     #[serde(rename = "answerContent")]
     pub answer_content: String,
+    /// 渲染并清理后的HTML，与前台展示一致
+    #[serde(rename = "answerHtml")]
+    pub answer_html: String,
     #[serde(rename = "createUserId")]
     pub create_user_id: Option<String>,
     #[serde(rename = "creatorName")]
@@ -100,6 +127,10 @@ pub struct Answer {
     #[serde(rename = "updateTime")]
     pub update_time: String,
     pub status: i8,
+    #[serde(rename = "reviewStatus")]
+    pub review_status: i8,
+    #[serde(rename = "rejectReason")]
+    pub reject_reason: Option<String>,
 }
 
 impl Answer{
@@ -108,11 +139,14 @@ impl Answer{
             id: answer.id.unwrap_or(0),
             question_code: answer.question_code.clone(),
             answer_content: answer.answer_content.clone(),
+            answer_html: answer.html(),
             create_user_id: answer.create_user_id.clone(),
             creator_name: answer.creator_name.clone(),
             create_time: answer.create_time.format("%Y-%m-%d %H:%M:%S").to_string(),
             update_time: answer.update_time.format("%Y-%m-%d %H:%M:%S").to_string(),
             status: answer.status,
+            review_status: answer.review_status,
+            reject_reason: answer.reject_reason.clone(),
         }
     }
 }
@@ -123,6 +157,7 @@ impl Question {
             id_str: question.id.unwrap_or(0).to_string(),
             id: question.id.unwrap_or(0),
             product_code: question.product_code.clone(),
+            sku_code: question.sku_code.clone(),
             question_content: question.question_content.clone(),
             question_code: question.question_code.clone(),
             creator_name: question.creator_name.clone(),
@@ -130,6 +165,9 @@ impl Question {
             sort: question.sort,
             rank: question.rank,
             create_time: question.create_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+            review_status: question.review_status,
+            reject_reason: question.reject_reason.clone(),
+            tags: vec![],
             answers: answers.into_iter().map(|answer|{
                 Answer::from_db_answer(answer)
             }).collect(),
@@ -0,0 +1,41 @@
+use serde::Serialize;
+use crate::model::db::qa::ReviewItem as DbReviewItem;
+
+#[derive(Serialize,Debug, Clone)]
+pub struct ReviewItem {
+    #[serde(rename = "targetType")]
+    pub target_type: String,
+    pub id: i64,
+    #[serde(rename = "questionCode")]
+    pub question_code: String,
+    #[serde(rename = "productCode")]
+    pub product_code: String,
+    pub content: String,
+    #[serde(rename = "createUserId")]
+    pub create_user_id: Option<String>,
+    #[serde(rename = "creatorName")]
+    pub creator_name: Option<String>,
+    #[serde(rename = "createTime")]
+    pub create_time: String,
+    #[serde(rename = "reviewStatus")]
+    pub review_status: i8,
+    #[serde(rename = "rejectReason")]
+    pub reject_reason: Option<String>,
+}
+
+impl ReviewItem {
+    pub fn from_db(target_type: &str, item: DbReviewItem) -> Self {
+        Self {
+            target_type: target_type.to_string(),
+            id: item.id,
+            question_code: item.question_code,
+            product_code: item.product_code,
+            content: item.content,
+            create_user_id: item.create_user_id,
+            creator_name: item.creator_name,
+            create_time: item.create_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+            review_status: item.review_status,
+            reject_reason: item.reject_reason,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use crate::utils::error::BusinessError;
+
+/// CSV导入的结果：有任何一行校验失败时不导入任何问题，errors中列出每行的错误
+#[derive(Serialize,Debug, Clone)]
+pub struct ImportQuestion {
+    #[serde(rename = "totalRows")]
+    pub total_rows: usize,
+    #[serde(rename = "importedCount")]
+    pub imported_count: usize,
+    #[serde(rename = "questionCodes")]
+    pub question_codes: Vec<String>,
+    pub errors: Vec<ImportRowError>,
+}
+
+/// 一行的校验错误，rowNumber为表格中的行号（表头为第1行）
+#[derive(Serialize,Debug, Clone)]
+pub struct ImportRowError {
+    #[serde(rename = "rowNumber")]
+    pub row_number: usize,
+    pub errors: Vec<HashMap<String, String>>,
+}
+
+impl ImportRowError {
+    pub fn new(row_number: usize, error: BusinessError) -> Self {
+        let errors = match error {
+            BusinessError::InvalidParameter((_, Some(parameters))) => parameters,
+            error => {
+                let mut parameters = HashMap::new();
+                parameters.insert("message".to_string(), error.msg());
+                vec![parameters]
+            },
+        };
+        Self { row_number, errors }
+    }
+}
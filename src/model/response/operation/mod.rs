@@ -1,4 +1,15 @@
 pub mod create_sku;
 pub mod update_sku;
 pub mod find_sku;
-pub mod find_question_list_for_trad;
\ No newline at end of file
+pub mod find_question_list_for_trad;
+pub mod find_review_queue;
+pub mod question_revision;
+pub mod recycle_bin;
+pub mod create_question;
+pub mod tag_usage;
+pub mod question_translation;
+pub mod qa_report;
+pub mod faq_snapshot;
+pub mod import_question;
+pub mod export_question;
+pub mod notification;
\ No newline at end of file
@@ -0,0 +1,47 @@
+use serde::Serialize;
+use crate::model::db::qa::Notification as DbNotification;
+
+/// 订阅通知及其发送状态
+#[derive(Serialize,Debug, Clone)]
+pub struct Notification {
+    pub id: i64,
+    #[serde(rename = "subscriptionId")]
+    pub subscription_id: i64,
+    #[serde(rename = "questionCode")]
+    pub question_code: String,
+    #[serde(rename = "subscriberId")]
+    pub subscriber_id: String,
+    #[serde(rename = "eventType")]
+    pub event_type: String,
+    pub payload: String,
+    pub status: i8,
+    #[serde(rename = "attemptCount")]
+    pub attempt_count: i32,
+    #[serde(rename = "nextRetryTime")]
+    pub next_retry_time: String,
+    #[serde(rename = "lastError")]
+    pub last_error: Option<String>,
+    #[serde(rename = "createTime")]
+    pub create_time: String,
+    #[serde(rename = "updateTime")]
+    pub update_time: String,
+}
+
+impl From<DbNotification> for Notification {
+    fn from(notification: DbNotification) -> Self {
+        Self {
+            id: notification.id,
+            subscription_id: notification.subscription_id,
+            question_code: notification.question_code,
+            subscriber_id: notification.subscriber_id,
+            event_type: notification.event_type,
+            payload: notification.payload,
+            status: notification.status,
+            attempt_count: notification.attempt_count,
+            next_retry_time: notification.next_retry_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+            last_error: notification.last_error,
+            create_time: notification.create_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+            update_time: notification.update_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use serde::Serialize;
+use crate::model::db::qa::AnswerLatencyStat;
+
+/// 浏览量排名中的问题
+#[derive(Serialize,Debug, Clone)]
+pub struct TopViewedQuestion {
+    #[serde(rename = "questionCode")]
+    pub question_code: String,
+    #[serde(rename = "questionContent")]
+    pub question_content: String,
+    #[serde(rename = "viewCount")]
+    pub view_count: i64,
+}
+
+/// 商品的回答时效统计，时效单位为秒，没有已回答的问题时为空
+#[derive(Serialize,Debug, Clone)]
+pub struct AnswerLatency {
+    #[serde(rename = "productCode")]
+    pub product_code: String,
+    #[serde(rename = "questionCount")]
+    pub question_count: i64,
+    #[serde(rename = "answeredCount")]
+    pub answered_count: i64,
+    #[serde(rename = "avgLatencySeconds")]
+    pub avg_latency_seconds: Option<i64>,
+    #[serde(rename = "minLatencySeconds")]
+    pub min_latency_seconds: Option<i64>,
+    #[serde(rename = "maxLatencySeconds")]
+    pub max_latency_seconds: Option<i64>,
+}
+
+impl AnswerLatency {
+    pub fn from_db(stat: AnswerLatencyStat) -> Self {
+        let avg_latency_seconds = stat.total_latency_seconds
+            .filter(|_|stat.answered_count > 0)
+            .map(|total|total / stat.answered_count);
+        Self {
+            product_code: stat.product_code,
+            question_count: stat.question_count,
+            answered_count: stat.answered_count,
+            avg_latency_seconds,
+            min_latency_seconds: stat.min_latency_seconds,
+            max_latency_seconds: stat.max_latency_seconds,
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use serde::Serialize;
+use crate::model::db::qa::{QuestionRevision as DbQuestionRevision, QuestionSnapshot, SnapshotChange};
+use crate::utils::error::BusinessError;
+
+#[derive(Serialize,Debug, Clone)]
+pub struct QuestionRevision {
+    #[serde(rename = "questionCode")]
+    pub question_code: String,
+    pub revision: i32,
+    pub action: String,
+    #[serde(rename = "operatorId")]
+    pub operator_id: Option<String>,
+    #[serde(rename = "createTime")]
+    pub create_time: String,
+    pub snapshot: QuestionSnapshot,
+}
+
+impl QuestionRevision {
+    pub fn from_db(revision: DbQuestionRevision) -> Result<Self, BusinessError> {
+        let snapshot = revision.snapshot()?;
+        Ok(Self {
+            question_code: revision.question_code,
+            revision: revision.revision,
+            action: revision.action,
+            operator_id: revision.operator_id,
+            create_time: revision.create_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+            snapshot,
+        })
+    }
+}
+
+#[derive(Serialize,Debug, Clone)]
+pub struct DiffQuestionRevision {
+    #[serde(rename = "questionCode")]
+    pub question_code: String,
+    #[serde(rename = "fromRevision")]
+    pub from_revision: i32,
+    #[serde(rename = "toRevision")]
+    pub to_revision: i32,
+    pub changes: Vec<SnapshotChange>,
+}
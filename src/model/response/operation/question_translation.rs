@@ -0,0 +1,54 @@
+use serde::Serialize;
+use crate::model::db::qa::{QuestionTranslation as DbQuestionTranslation, AnswerTranslation as DbAnswerTranslation};
+
+/// 问题某个语言的翻译，answers为该语言已翻译的回答
+#[derive(Serialize,Debug, Clone)]
+pub struct QuestionTranslation {
+    pub locale: String,
+    #[serde(rename = "questionContent")]
+    pub question_content: Option<String>,
+    #[serde(rename = "updateTime")]
+    pub update_time: Option<String>,
+    pub answers: Vec<AnswerTranslation>,
+}
+
+#[derive(Serialize,Debug, Clone)]
+pub struct AnswerTranslation {
+    #[serde(rename = "answerId")]
+    pub answer_id: i64,
+    #[serde(rename = "answerContent")]
+    pub answer_content: String,
+    #[serde(rename = "updateTime")]
+    pub update_time: String,
+}
+
+impl QuestionTranslation {
+    /// 按语言分组，只有回答翻译的语言questionContent为空
+    pub fn group(question_translations: Vec<DbQuestionTranslation>, answer_translations: Vec<DbAnswerTranslation>) -> Vec<Self> {
+        let mut list: Vec<Self> = question_translations.into_iter()
+            .map(|translation| Self {
+                locale: translation.locale,
+                question_content: Some(translation.question_content),
+                update_time: Some(translation.update_time.format("%Y-%m-%d %H:%M:%S").to_string()),
+                answers: vec![],
+            })
+            .collect();
+        for translation in answer_translations {
+            let answer = AnswerTranslation {
+                answer_id: translation.answer_id,
+                answer_content: translation.answer_content,
+                update_time: translation.update_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+            };
+            match list.iter_mut().find(|item| item.locale == translation.locale) {
+                Some(item) => item.answers.push(answer),
+                None => list.push(Self {
+                    locale: translation.locale,
+                    question_content: None,
+                    update_time: None,
+                    answers: vec![answer],
+                }),
+            }
+        }
+        list
+    }
+}
@@ -0,0 +1,22 @@
+use serde::Serialize;
+use crate::model::db::qa::{Question as DbQuestion, Answer as DbAnswer};
+use crate::model::response::operation::find_question_list_for_trad::Question;
+
+/// 回收站中的问题，answers为恢复问题时会一起恢复的回答
+#[derive(Serialize,Debug, Clone)]
+pub struct RecycleBinQuestion {
+    #[serde(flatten)]
+    pub question: Question,
+    #[serde(rename = "disableTime")]
+    pub disable_time: Option<String>,
+}
+
+impl RecycleBinQuestion {
+    pub fn from_db(question: DbQuestion, answers: Vec<DbAnswer>) -> Self {
+        let disable_time = question.disable_time.map(|time|time.format("%Y-%m-%d %H:%M:%S").to_string());
+        Self {
+            question: Question::from_db_questions(question, answers),
+            disable_time,
+        }
+    }
+}
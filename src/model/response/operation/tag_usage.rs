@@ -0,0 +1,10 @@
+use serde::Serialize;
+
+/// 标签及其关联的有效问题数量
+#[derive(Serialize,Debug, Clone)]
+pub struct TagUsage {
+    #[serde(rename = "tagName")]
+    pub tag_name: String,
+    #[serde(rename = "questionCount")]
+    pub question_count: i64,
+}
@@ -15,9 +15,42 @@ use crate::handlers::{
     operation_qa::top_question,
     operation_qa::cancel_top_question,
     operation_qa::disabled_question,
+    operation_qa::find_review_queue,
+    operation_qa::review_qa,
+    operation_qa::find_question_revision_list,
+    operation_qa::diff_question_revision,
+    operation_qa::restore_question_revision,
+    operation_qa::find_recycle_bin,
+    operation_qa::restore_question,
+    operation_qa::reorder_top_question,
+    operation_qa::merge_question,
+    operation_qa::find_tag_usage,
+    operation_qa::save_question_translation,
+    operation_qa::find_question_translation,
+    operation_qa::delete_question_translation,
+    operation_qa::find_top_viewed_question,
+    operation_qa::find_unanswered_question,
+    operation_qa::find_answer_latency,
+    operation_qa::build_faq_snapshot,
+    operation_qa::import_question,
+    operation_qa::export_question,
+    operation_qa::find_notification_list,
     frontend_qa::get_question_by_code,
+    frontend_qa::ask_question,
+    frontend_qa::find_question_list as front_find_question_list,
+    frontend_qa::search_question,
+    frontend_qa::vote_answer,
+    frontend_qa::reply_answer,
+    frontend_qa::find_reply_list,
+    frontend_qa::get_faq_snapshot,
+    frontend_qa::subscribe_question,
+    frontend_qa::unsubscribe_question,
     divide0::divide_0,
+    admin_cache::{find_cache_namespace_list, find_cache_key_list, get_cache_entry, evict_cache_key, evict_cache_prefix, flush_cache},
 };
+use crate::utils::admin_auth::{admin_token_middleware, AdminToken};
+use axum::Extension;
+use tower::ServiceBuilder;
 
 pub fn app_router() -> Router {
     Router::new()
@@ -36,7 +69,82 @@ pub fn app_router() -> Router {
         .route("/operation/topQuestion", get(top_question))
         .route("/operation/cancelTopQuestion", get(cancel_top_question))
         .route("/operation/deleteQuestion", post(disabled_question))
+        .route("/operation/findReviewQueue", post(find_review_queue))
+        .route("/operation/reviewQa", post(review_qa))
+        .route("/operation/findQuestionRevisionList", post(find_question_revision_list))
+        .route("/operation/diffQuestionRevision", get(diff_question_revision))
+        .route("/operation/restoreQuestionRevision", post(restore_question_revision))
+        .route("/operation/findRecycleBin", post(find_recycle_bin))
+        .route("/operation/restoreQuestion", post(restore_question))
+        .route("/operation/reorderTopQuestion", post(reorder_top_question))
+        .route("/operation/mergeQuestion", post(merge_question))
+        .route("/operation/findTagUsage", get(find_tag_usage))
+        .route("/operation/saveQuestionTranslation", post(save_question_translation))
+        .route("/operation/findQuestionTranslation", get(find_question_translation))
+        .route("/operation/deleteQuestionTranslation", post(delete_question_translation))
+        .route("/operation/findTopViewedQuestion", get(find_top_viewed_question))
+        .route("/operation/findUnansweredQuestion", get(find_unanswered_question))
+        .route("/operation/findAnswerLatency", get(find_answer_latency))
+        .route("/operation/buildFaqSnapshot", post(build_faq_snapshot))
+        .route("/operation/importQuestion", post(import_question))
+        .route("/operation/exportQuestion", post(export_question))
+        .route("/operation/findNotificationList", get(find_notification_list))
         .route("/frontend/getQuestionByCode", get(get_question_by_code))
+        .route("/frontend/askQuestion", post(ask_question))
+        .route("/frontend/findQuestionList", get(front_find_question_list))
+        .route("/frontend/searchQuestion", get(search_question))
+        .route("/frontend/voteAnswer", post(vote_answer))
+        .route("/frontend/replyAnswer", post(reply_answer))
+        .route("/frontend/findReplyList", get(find_reply_list))
+        .route("/frontend/getFaqSnapshot", get(get_faq_snapshot))
+        .route("/frontend/subscribeQuestion", post(subscribe_question))
+        .route("/frontend/unsubscribeQuestion", post(unsubscribe_question))
         .route("/test/divide0", get(divide_0))
 
     }
+
+/// 管理接口，请求头中的令牌与admin_token不一致时拒绝访问（admin_token为None时不检查）
+pub fn admin_router(admin_token: AdminToken) -> Router {
+    Router::new()
+        .route("/admin/findCacheNamespaceList", get(find_cache_namespace_list))
+        .route("/admin/findCacheKeyList", get(find_cache_key_list))
+        .route("/admin/getCacheEntry", get(get_cache_entry))
+        .route("/admin/evictCacheKey", post(evict_cache_key))
+        .route("/admin/evictCachePrefix", post(evict_cache_prefix))
+        .route("/admin/flushCache", post(flush_cache))
+        .layer(
+            ServiceBuilder::new()
+                .layer(Extension(admin_token))
+                .layer(axum::middleware::from_fn(admin_token_middleware))
+        )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+    use crate::utils::admin_auth::ADMIN_TOKEN_HEADER;
+
+    #[tokio::test]
+    async fn test_admin_router_requires_token() {
+        let router = admin_router(AdminToken(Some(Arc::from("secret"))));
+        let request = Request::builder().uri("/admin/findCacheNamespaceList").body(Body::empty()).unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let request = Request::builder()
+            .uri("/admin/findCacheNamespaceList")
+            .header(ADMIN_TOKEN_HEADER, "secret")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("\"name\":\"question\""));
+        assert!(body.contains("\"keyFormat\":\"questionCode:locale\""));
+    }
+}
@@ -0,0 +1,98 @@
+//! 管理接口的访问控制：管理接口只在单独的管理端口（环境变量ADMIN_PORT）上提供，或者在业务端口上提供并要求令牌
+//! 令牌由环境变量ADMIN_TOKEN指定，请求时放在请求头X-Admin-Token中
+use axum::body::Body;
+use axum::http::{HeaderMap, Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Extension;
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+use crate::utils::error::BusinessError;
+
+pub const ADMIN_TOKEN_HEADER: &str = "x-admin-token";
+
+/// 管理接口要求的令牌，None表示不检查令牌（只在管理端口上允许）
+#[derive(Clone, Debug)]
+pub struct AdminToken(pub Option<Arc<str>>);
+
+impl AdminToken {
+    /// 读取环境变量ADMIN_TOKEN，未配置或为空时返回None
+    pub fn from_env() -> Self {
+        AdminToken(env::var("ADMIN_TOKEN").ok().filter(|token| !token.is_empty()).map(Arc::from))
+    }
+}
+
+/// 检查请求头中的管理令牌，令牌不正确时拒绝访问，返回HTTP 403（业务错误的响应默认为HTTP 200）
+pub async fn admin_token_middleware(
+    Extension(admin_token): Extension<AdminToken>,
+    req: Request<Body>,
+    next: Next<>,
+) -> Response {
+    if let Some(expected) = &admin_token.0 {
+        if !token_matches(expected, provided_token(req.headers())) {
+            let mut parameters = HashMap::new();
+            parameters.insert("header".to_string(), ADMIN_TOKEN_HEADER.to_string());
+            return (StatusCode::FORBIDDEN, BusinessError::Forbidden((None, Some(parameters))).into_response()).into_response();
+        }
+    }
+    next.run(req).await
+}
+
+fn provided_token(headers: &HeaderMap) -> Option<&str> {
+    headers.get(ADMIN_TOKEN_HEADER).and_then(|value| value.to_str().ok())
+}
+
+/// 比较令牌，比较时间与令牌内容无关，避免逐字符猜测令牌
+fn token_matches(expected: &str, provided: Option<&str>) -> bool {
+    let Some(provided) = provided else {
+        return false;
+    };
+    if expected.len() != provided.len() {
+        return false;
+    }
+    expected.bytes()
+        .zip(provided.bytes())
+        .fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use axum::routing::get;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    fn app(admin_token: AdminToken) -> Router {
+        Router::new()
+            .route("/admin/ping", get(|| async { "pong" }))
+            .layer(axum::middleware::from_fn(admin_token_middleware))
+            .layer(Extension(admin_token))
+    }
+
+    async fn status(admin_token: AdminToken, token: Option<&str>) -> StatusCode {
+        let mut request = Request::builder().uri("/admin/ping");
+        if let Some(token) = token {
+            request = request.header(ADMIN_TOKEN_HEADER, token);
+        }
+        app(admin_token).oneshot(request.body(Body::empty()).unwrap()).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_admin_token_middleware() {
+        let admin_token = AdminToken(Some(Arc::from("secret")));
+        assert_eq!(status(admin_token.clone(), Some("secret")).await, StatusCode::OK);
+        assert_eq!(status(admin_token.clone(), Some("wrong")).await, StatusCode::FORBIDDEN);
+        assert_eq!(status(admin_token, None).await, StatusCode::FORBIDDEN);
+        //未配置令牌时不检查
+        assert_eq!(status(AdminToken(None), None).await, StatusCode::OK);
+    }
+
+    #[test]
+    fn test_token_matches() {
+        assert!(token_matches("secret", Some("secret")));
+        assert!(!token_matches("secret", Some("secreT")));
+        assert!(!token_matches("secret", Some("secret1")));
+        assert!(!token_matches("secret", None));
+    }
+}
@@ -1,39 +1,258 @@
 use moka::sync::Cache;
 use once_cell::sync::Lazy;
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
 use std::time::Duration;
 use moka:: Expiry;
 use std::time::Instant;
-use tracing::instrument;
 use tracing::trace;
-use uuid::Uuid;
 use std::sync::Arc;
 
 use super::load_balance::LoadBalance;
+use crate::model::cache::qa::{Question as CacheQuestion, QuestionPage as CacheQuestionPage};
+use crate::model::db::sku::Sku;
 
-#[derive(Clone,Debug)]
-pub enum CacheType {
-    Str(String),
-    I32(i32),
-    DateTime(String),
-    Sku(Option<crate::model::db::sku::Sku>),
-    LoadBalance(Arc<LoadBalance>),
-    Question(Option<crate::model::cache::qa::Question>),
-}
-
-// 定义一个全局的、懒加载的缓存实例
-pub static CACHE: Lazy<Cache<String, (Expiration,CacheType)>> = Lazy::new(|| {
-    let expiry = MyExpiry;
-    let eviction_listener = |key, _value, cause| {
-        trace!("Evicted key {key}. Cause: {cause:?}");
-    };
-    Cache::builder()
-        .max_capacity(1000)
-        .expire_after(expiry)
-        .eviction_listener(eviction_listener)
-        // .time_to_live(Duration::from_secs(300))
-        .build()
+/// 商品信息，key为sku_code，商品不存在时缓存None
+pub static SKU_CACHE: Lazy<TypedCache<String, Option<Sku>>> = Lazy::new(|| {
+    TypedCache::new("sku", 1000, Expiration::AfterShortTime)
 });
 
+/// 前台展示的问题详情，按问题和语言缓存，问题不存在时缓存None
+pub static QUESTION_CACHE: Lazy<TypedCache<key::QuestionKey, Option<CacheQuestion>>> = Lazy::new(|| {
+    TypedCache::new("question", 1000, Expiration::AfterShortTime)
+});
+
+/// 前台商品问题列表的分页，问题变更时按商品清除
+pub static QUESTION_PAGE_CACHE: Lazy<TypedCache<key::QuestionListKey, CacheQuestionPage>> = Lazy::new(|| {
+    TypedCache::new("question_list", 1000, Expiration::AfterLongTime)
+});
+
+/// 被合并问题重定向到的问题编号，key为被合并的问题编号，None表示没有重定向
+pub static QUESTION_REDIRECT_CACHE: Lazy<TypedCache<String, Option<String>>> = Lazy::new(|| {
+    TypedCache::new("question_redirect", 1000, Expiration::AfterShortTime)
+});
+
+/// 依赖服务的实例列表（从nacos中定期刷新），key为服务名
+pub static LOAD_BALANCE_CACHE: Lazy<TypedCache<String, Arc<LoadBalance>>> = Lazy::new(|| {
+    TypedCache::new("load_balance", 100, Expiration::AfterLongTime)
+});
+
+/// 全部缓存命名空间，新增命名空间时需要加入这里才能统一管理
+pub fn namespaces() -> Vec<&'static dyn CacheNamespace> {
+    vec![
+        &*SKU_CACHE,
+        &*QUESTION_CACHE,
+        &*QUESTION_PAGE_CACHE,
+        &*QUESTION_REDIRECT_CACHE,
+        &*LOAD_BALANCE_CACHE,
+    ]
+}
+
+/// 依据名称查找缓存命名空间
+pub fn namespace(name: &str) -> Option<&'static dyn CacheNamespace> {
+    namespaces().into_iter().find(|namespace| namespace.name() == name)
+}
+
+/// 缓存命名空间中与key、value类型无关的操作，用于统一管理，key以字符串形式表示
+pub trait CacheNamespace: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn capacity(&self) -> u64;
+    fn default_expiration(&self) -> Expiration;
+    /// key的字符串格式，管理接口按该格式解析key
+    fn key_format(&self) -> &'static str;
+    /// 当前的缓存项数量
+    fn entry_count(&self) -> u64;
+    /// key以prefix开头的缓存项，按key排序
+    fn entries(&self, prefix: &str) -> Vec<CacheEntryInfo>;
+    /// key对应的缓存项，value为调试格式的字符串，key不符合格式时返回None
+    fn entry(&self, key: &str) -> Option<CacheEntryDetail>;
+    /// 使key对应的缓存项失效，返回缓存项是否存在，key不符合格式时返回false
+    fn invalidate_key(&self, key: &str) -> bool;
+    /// 使key以prefix开头的缓存项失效，返回失效的数量
+    fn invalidate_prefix(&self, prefix: &str) -> usize;
+    fn invalidate_all(&self);
+}
+
+/// 管理接口中以字符串表示的缓存key，parse_key与Display互逆，按key直接查找缓存项而不遍历缓存
+pub trait CacheKey: Display + Sized {
+    /// key的字符串格式说明
+    const FORMAT: &'static str;
+    fn parse_key(key: &str) -> Option<Self>;
+}
+
+impl CacheKey for String {
+    const FORMAT: &'static str = "原始字符串";
+
+    fn parse_key(key: &str) -> Option<Self> {
+        Some(key.to_string())
+    }
+}
+
+/// 缓存项的key和剩余有效时间（None表示不会过期）
+#[derive(Clone, Debug, PartialEq)]
+pub struct CacheEntryInfo {
+    pub key: String,
+    pub remaining_ttl: Option<Duration>,
+}
+
+/// 缓存项的key、剩余有效时间和value的调试格式
+#[derive(Clone, Debug, PartialEq)]
+pub struct CacheEntryDetail {
+    pub key: String,
+    pub remaining_ttl: Option<Duration>,
+    pub value: String,
+}
+
+/// 缓存中保存的值，记录写入时间用于计算剩余有效时间
+#[derive(Clone, Debug)]
+pub struct CacheEntry<V> {
+    expiration: Expiration,
+    insert_time: Instant,
+    value: V,
+}
+
+impl<V> CacheEntry<V> {
+    fn remaining_ttl(&self) -> Option<Duration> {
+        self.expiration.as_duration()
+            .map(|duration| duration.saturating_sub(self.insert_time.elapsed()))
+    }
+}
+
+/// 一类缓存数据（命名空间）：key和value的类型固定，容量和默认有效时间各自独立
+/// 每个缓存项可以单独指定有效时间，未指定时使用命名空间的默认有效时间
+pub struct TypedCache<K, V> {
+    name: &'static str,
+    capacity: u64,
+    default_expiration: Expiration,
+    cache: Cache<K, CacheEntry<V>>,
+}
+
+impl<K, V> TypedCache<K, V>
+where
+    K: Hash + Eq + Debug + Send + Sync + 'static,
+    V: Clone + Debug + Send + Sync + 'static,
+{
+    pub fn new(name: &'static str, capacity: u64, default_expiration: Expiration) -> Self {
+        let eviction_listener = move |key: Arc<K>, _value, cause| {
+            trace!("Evicted {name} key {key:?}. Cause: {cause:?}");
+        };
+        let cache = Cache::builder()
+            .name(name)
+            .max_capacity(capacity)
+            .expire_after(MyExpiry)
+            .eviction_listener(eviction_listener)
+            .support_invalidation_closures()
+            .build();
+        TypedCache { name, capacity, default_expiration, cache }
+    }
+
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.cache.get(key).map(|entry| entry.value)
+    }
+
+    /// 写入缓存项，有效时间为命名空间的默认有效时间
+    pub fn insert(&self, key: K, value: V) {
+        self.insert_with_expiration(key, value, self.default_expiration);
+    }
+
+    /// 写入缓存项并单独指定有效时间
+    pub fn insert_with_expiration(&self, key: K, value: V, expiration: Expiration) {
+        self.cache.insert(key, CacheEntry { expiration, insert_time: Instant::now(), value });
+    }
+
+    pub fn invalidate(&self, key: &K) {
+        self.cache.invalidate(key);
+    }
+
+    /// 使所有key满足条件的缓存项失效
+    pub fn invalidate_if<F>(&self, predicate: F)
+    where
+        F: Fn(&K) -> bool + Send + Sync + 'static,
+    {
+        if let Err(e) = self.cache.invalidate_entries_if(move |key, _value| predicate(key)) {
+            tracing::error!("Failed to invalidate {} cache entries: {:?}", self.name, e);
+        }
+    }
+}
+
+impl<K, V> TypedCache<K, V>
+where
+    K: Hash + Eq + Debug + CacheKey + Send + Sync + 'static,
+    V: Clone + Debug + Send + Sync + 'static,
+{
+    /// key的字符串形式以prefix开头的缓存项
+    fn matching(&self, prefix: &str) -> Vec<(Arc<K>, CacheEntry<V>)> {
+        self.cache.iter()
+            .filter(|(key, _)| key.to_string().starts_with(prefix))
+            .collect()
+    }
+}
+
+impl<K, V> CacheNamespace for TypedCache<K, V>
+where
+    K: Hash + Eq + Debug + CacheKey + Send + Sync + 'static,
+    V: Clone + Debug + Send + Sync + 'static,
+{
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    fn default_expiration(&self) -> Expiration {
+        self.default_expiration
+    }
+
+    fn key_format(&self) -> &'static str {
+        K::FORMAT
+    }
+
+    fn entry_count(&self) -> u64 {
+        //写入和失效是异步处理的，先处理完再统计
+        self.cache.run_pending_tasks();
+        self.cache.entry_count()
+    }
+
+    fn entries(&self, prefix: &str) -> Vec<CacheEntryInfo> {
+        let mut entries: Vec<CacheEntryInfo> = self.matching(prefix).into_iter()
+            .map(|(key, entry)| CacheEntryInfo { key: key.to_string(), remaining_ttl: entry.remaining_ttl() })
+            .collect();
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+        entries
+    }
+
+    fn entry(&self, key: &str) -> Option<CacheEntryDetail> {
+        let key = K::parse_key(key)?;
+        self.cache.get(&key)
+            .map(|entry| CacheEntryDetail {
+                key: key.to_string(),
+                remaining_ttl: entry.remaining_ttl(),
+                value: format!("{:#?}", entry.value),
+            })
+    }
+
+    fn invalidate_key(&self, key: &str) -> bool {
+        match K::parse_key(key) {
+            Some(key) => self.cache.remove(&key).is_some(),
+            None => false,
+        }
+    }
+
+    fn invalidate_prefix(&self, prefix: &str) -> usize {
+        let matching = self.matching(prefix);
+        for (key, _) in &matching {
+            self.cache.invalidate(&**key);
+        }
+        matching.len()
+    }
+
+    fn invalidate_all(&self) {
+        self.cache.invalidate_all();
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Expiration {
     /// The value never expires.
@@ -41,8 +260,8 @@ pub enum Expiration {
     /// The value expires after a short time. (5 seconds in this example)
     AfterShortTime,
     /// The value expires after a long time. (15 seconds in this example)
-    AfterLongTime,   
-    Second5, 
+    AfterLongTime,
+    Second5,
 }
 
 impl Expiration {
@@ -61,34 +280,32 @@ impl Expiration {
 pub struct MyExpiry;
 
 
-impl Expiry<String, (Expiration, CacheType)> for MyExpiry {
+impl<K: Debug, V: Debug> Expiry<K, CacheEntry<V>> for MyExpiry {
     /// Returns the duration of the expiration of the value that was just
     /// created.
-    #[instrument(name = "cache", fields(request_id = %Uuid::new_v4()))]
     //返回值在缓存中的有效时间
     fn expire_after_create(
         &self,
-        _key: &String,
-        value: &(Expiration, CacheType),
+        _key: &K,
+        value: &CacheEntry<V>,
         _current_time: Instant,
     ) -> Option<Duration> {
-        let duration = value.0.as_duration();
-        tracing::trace!("MyExpiry: expire_after_create called with key {_key} and value {value:?}. Returning {duration:?}.");
+        let duration = value.expiration.as_duration();
+        tracing::trace!("MyExpiry: expire_after_create called with key {_key:?} and value {value:?}. Returning {duration:?}.");
         duration
     }
 
     /// 设置更新后的缓存项的过期时间
-    #[instrument(name = "cache.expire_after_update", skip_all)]
     fn expire_after_update(
         &self,
-        _key: &String,
-        value: &(Expiration, CacheType),
+        _key: &K,
+        value: &CacheEntry<V>,
         _current_time: Instant,
         _druation: Option<Duration>,
     ) -> Option<Duration> {
-        let duration = value.0.as_duration();
+        let duration = value.expiration.as_duration();
         tracing::trace!(
-            "MyExpiry: expire_after_update called with key `{}` and value {:?}. Returning {:?}.",
+            "MyExpiry: expire_after_update called with key `{:?}` and value {:?}. Returning {:?}.",
             _key, value, duration
         );
         duration
@@ -96,12 +313,120 @@ impl Expiry<String, (Expiration, CacheType)> for MyExpiry {
 }
 
 pub mod key{
-    pub fn get_service_list_key(service_name_clone:String) -> String {
-        format!("{}:{}","nacos_service_list:",&service_name_clone)
+    use std::fmt;
+    use super::CacheKey;
+    use crate::utils::locale::Locale;
+
+    /// 按语言标签（与Locale::as_str一致）解析语言
+    fn parse_locale(value: &str) -> Option<Locale> {
+        Locale::ALL.into_iter().find(|locale| locale.as_str() == value)
     }
-    pub fn get_service_instance_key(service_name:String)->String{
-        let key=format!("load_balance_{}",&service_name);
-        key
+
+    /// 问题详情的缓存key
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    pub struct QuestionKey{
+        pub question_code: String,
+        pub locale: Locale,
+    }
+
+    impl QuestionKey{
+        pub fn new(question_code:&str, locale:Locale)->Self{
+            QuestionKey{ question_code: question_code.to_string(), locale }
+        }
+    }
+
+    impl fmt::Display for QuestionKey{
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}:{}", self.question_code, self.locale.as_str())
+        }
+    }
+
+    impl CacheKey for QuestionKey{
+        const FORMAT: &'static str = "questionCode:locale";
+
+        fn parse_key(key: &str) -> Option<Self> {
+            let (question_code, locale) = key.rsplit_once(':')?;
+            Some(QuestionKey::new(question_code, parse_locale(locale)?))
+        }
+    }
+
+    /// 商品问题列表分页的缓存key
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    pub struct QuestionListKey{
+        pub product_code: String,
+        pub sku_code: Option<String>,
+        pub tag: Option<String>,
+        pub locale: Locale,
+        pub current_pageno: i64,
+        pub page_size: i64,
+    }
+
+    impl fmt::Display for QuestionListKey{
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}:{}:{}:{}:{}:{}",
+                self.product_code,
+                self.sku_code.as_deref().unwrap_or(""),
+                self.tag.as_deref().unwrap_or(""),
+                self.locale.as_str(),
+                self.current_pageno,
+                self.page_size)
+        }
+    }
+
+    impl CacheKey for QuestionListKey{
+        const FORMAT: &'static str = "productCode:skuCode:tag:locale:pageNum:pageSize（skuCode、tag为空时留空）";
+
+        fn parse_key(key: &str) -> Option<Self> {
+            //商品编号中可能有:，从右往左解析
+            let mut parts = key.rsplitn(6, ':');
+            let page_size = parts.next()?.parse().ok()?;
+            let current_pageno = parts.next()?.parse().ok()?;
+            let locale = parse_locale(parts.next()?)?;
+            let optional = |value: &str| Some(value).filter(|value| !value.is_empty()).map(str::to_string);
+            let tag = optional(parts.next()?);
+            let sku_code = optional(parts.next()?);
+            let product_code = parts.next()?.to_string();
+            Some(QuestionListKey{ product_code, sku_code, tag, locale, current_pageno, page_size })
+        }
     }
 }
 
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::key::{QuestionKey, QuestionListKey};
+    use crate::utils::locale::Locale;
+
+    #[test]
+    fn test_parse_key() {
+        let key = QuestionKey::new("Q:001", Locale::ZhHk);
+        assert_eq!(QuestionKey::parse_key(&key.to_string()), Some(key));
+        assert_eq!(QuestionKey::parse_key("Q001:zh-hk"), None);
+        assert_eq!(QuestionKey::parse_key("Q001"), None);
+
+        let key = QuestionListKey{
+            product_code: "P001".to_string(),
+            sku_code: None,
+            tag: Some("尺码".to_string()),
+            locale: Locale::En,
+            current_pageno: 2,
+            page_size: 10,
+        };
+        assert_eq!(key.to_string(), "P001::尺码:en:2:10");
+        assert_eq!(QuestionListKey::parse_key(&key.to_string()), Some(key));
+        assert_eq!(QuestionListKey::parse_key("P001::尺码:en:x:10"), None);
+    }
+
+    #[test]
+    fn test_entry_by_key() {
+        let cache: TypedCache<QuestionKey, Option<String>> = TypedCache::new("test", 10, Expiration::AfterLongTime);
+        cache.insert(QuestionKey::new("Q001", Locale::ZhCn), None);
+        assert_eq!(cache.key_format(), "questionCode:locale");
+        assert_eq!(cache.entry("Q001:zh-CN").map(|entry| entry.key), Some("Q001:zh-CN".to_string()));
+        assert!(cache.entry("Q001:en").is_none());
+        assert!(cache.entry("not a key").is_none());
+        assert!(!cache.invalidate_key("Q001:en"));
+        assert!(cache.invalidate_key("Q001:zh-CN"));
+        assert!(cache.get(&QuestionKey::new("Q001", Locale::ZhCn)).is_none());
+    }
+}
@@ -0,0 +1,90 @@
+//! 前台请求的调用方身份，用于限流、投票去重和订阅；请求体中的用户id可以随意填写，不能用于这些场景
+//! 网关认证用户后在请求头X-User-Id中传入用户id（网关会丢弃客户端自带的该请求头），没有认证用户时以客户端IP标识调用方
+//! 客户端IP取网关追加在X-Forwarded-For末尾的地址，没有该请求头时取连接的对端地址
+use axum::async_trait;
+use axum::extract::{ConnectInfo, FromRequestParts};
+use axum::http::request::Parts;
+use axum::http::HeaderMap;
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+
+pub const USER_ID_HEADER: &str = "x-user-id";
+pub const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClientIdentity {
+    /// 网关认证的用户id，未登录时为None
+    pub user_id: Option<String>,
+    pub client_ip: Option<IpAddr>,
+}
+
+impl ClientIdentity {
+    pub fn from_parts(headers: &HeaderMap, peer_addr: Option<SocketAddr>) -> Self {
+        let user_id = headers.get(USER_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+        let forwarded_ip = headers.get_all(FORWARDED_FOR_HEADER).iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .last()
+            .and_then(|value| value.trim().parse::<IpAddr>().ok());
+        ClientIdentity {
+            user_id,
+            client_ip: forwarded_ip.or(peer_addr.map(|addr| addr.ip())),
+        }
+    }
+
+    /// 标识调用方的key：认证用户为user:用户id，否则为ip:客户端IP，两者都没有时为None
+    pub fn principal(&self) -> Option<String> {
+        match (&self.user_id, &self.client_ip) {
+            (Some(user_id), _) => Some(format!("user:{}", user_id)),
+            (None, Some(client_ip)) => Some(format!("ip:{}", client_ip)),
+            (None, None) => None,
+        }
+    }
+
+    /// 限流使用的key，无法识别调用方时共用一个key
+    pub fn rate_limit_key(&self) -> String {
+        self.principal().unwrap_or_else(|| "unknown".to_string())
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for ClientIdentity {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let peer_addr = parts.extensions.get::<ConnectInfo<SocketAddr>>().map(|info| info.0);
+        Ok(ClientIdentity::from_parts(&parts.headers, peer_addr))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_from_parts() {
+        let peer_addr: SocketAddr = "10.0.0.1:5000".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        let identity = ClientIdentity::from_parts(&headers, Some(peer_addr));
+        assert_eq!(identity.user_id, None);
+        assert_eq!(identity.rate_limit_key(), "ip:10.0.0.1");
+
+        //客户端自带的地址在前，网关追加的地址在最后
+        headers.insert(FORWARDED_FOR_HEADER, HeaderValue::from_static("1.1.1.1, 203.0.113.7"));
+        let identity = ClientIdentity::from_parts(&headers, Some(peer_addr));
+        assert_eq!(identity.rate_limit_key(), "ip:203.0.113.7");
+
+        headers.insert(USER_ID_HEADER, HeaderValue::from_static(" user_1 "));
+        let identity = ClientIdentity::from_parts(&headers, Some(peer_addr));
+        assert_eq!(identity.user_id.as_deref(), Some("user_1"));
+        assert_eq!(identity.principal().as_deref(), Some("user:user_1"));
+
+        let identity = ClientIdentity::from_parts(&HeaderMap::new(), None);
+        assert_eq!(identity.principal(), None);
+        assert_eq!(identity.rate_limit_key(), "unknown");
+    }
+}
@@ -10,6 +10,12 @@ pub enum BusinessError{
     InvalidParameter((Option<String>,Option<Vec<HashMap<String, String>>>)),
     SkuNotFound((Option<String>,Option<HashMap<String, String>>)),
     QuestionNotFound((Option<String>,Option<HashMap<String, String>>)),
+    AnswerNotFound((Option<String>,Option<HashMap<String, String>>)),
+    TooManyRequests((Option<String>,Option<HashMap<String, String>>)),
+    RevisionNotFound((Option<String>,Option<HashMap<String, String>>)),
+    ReplyNotFound((Option<String>,Option<HashMap<String, String>>)),
+    SnapshotNotFound((Option<String>,Option<HashMap<String, String>>)),
+    Forbidden((Option<String>,Option<HashMap<String, String>>)),
     DivedByCannotBe0((Option<String>,Option<HashMap<String, String>>)),
 }
 #[derive(Serialize)]
@@ -43,6 +49,12 @@ impl IntoResponse for BusinessError{
             | BusinessError::SkuAlreadyExists((message,error_parameters)) 
             | BusinessError::SkuNotFound((message,error_parameters))
             | BusinessError::QuestionNotFound((message,error_parameters))
+            | BusinessError::AnswerNotFound((message,error_parameters))
+            | BusinessError::TooManyRequests((message,error_parameters))
+            | BusinessError::RevisionNotFound((message,error_parameters))
+            | BusinessError::ReplyNotFound((message,error_parameters))
+            | BusinessError::SnapshotNotFound((message,error_parameters))
+            | BusinessError::Forbidden((message,error_parameters))
             | BusinessError::DivedByCannotBe0((message,error_parameters))=>{
                 let into_response_tuple: Resp<String, HashMap<String, String>> = Resp::err(
                     code,
@@ -79,6 +91,12 @@ impl BusinessError{
             Self::SkuNotFound(_) => "0201022".to_string(),
             Self::QuestionNotFound(_) => "1301002".to_string(),
             Self::DivedByCannotBe0(_) => "1301003".to_string(),
+            Self::AnswerNotFound(_) => "1301004".to_string(),
+            Self::TooManyRequests(_) => "1301005".to_string(),
+            Self::RevisionNotFound(_) => "1301006".to_string(),
+            Self::ReplyNotFound(_) => "1301007".to_string(),
+            Self::SnapshotNotFound(_) => "1301008".to_string(),
+            Self::Forbidden(_) => "1399002".to_string(),
         }
     }
     pub fn msg(&self) -> String {
@@ -89,6 +107,12 @@ impl BusinessError{
             Self::SkuNotFound(_) => "商品找不到".to_string(),
             Self::QuestionNotFound(_) => "找不到对应的问题记录".to_string(),
             Self::DivedByCannotBe0(_) => "除数不能为0".to_string(),
+            Self::AnswerNotFound(_) => "找不到对应的回答记录".to_string(),
+            Self::TooManyRequests(_) => "操作过于频繁，请稍后再试".to_string(),
+            Self::RevisionNotFound(_) => "找不到对应的修订记录".to_string(),
+            Self::ReplyNotFound(_) => "找不到对应的回复记录".to_string(),
+            Self::SnapshotNotFound(_) => "找不到对应的FAQ快照".to_string(),
+            Self::Forbidden(_) => "无权访问".to_string(),
         }
     }
 }
@@ -138,4 +162,12 @@ impl From<serde_json::Error> for BusinessError {
             (Some(error.to_string()),None)
         )
     }
-}
\ No newline at end of file
+}
+
+impl From<std::io::Error> for BusinessError {
+    fn from(error: std::io::Error) -> Self {
+        BusinessError::InternalServerError(
+            (Some(error.to_string()),None)
+        )
+    }
+}
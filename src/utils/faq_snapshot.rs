@@ -0,0 +1,160 @@
+//! 商品FAQ静态快照的存储：每个商品一个JSON文件，保存在本地目录中，供CDN预渲染拉取
+//! 快照记录问题内容的哈希值，内容不变时不重新生成，内容变化时版本号加1；哈希值同时作为ETag
+//! 版本号和哈希值另外保存在旁边的小文件（商品编号.meta.json）中，验证ETag时不需要读取和解析整个快照
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::OwnedMutexGuard;
+
+/// 快照文件的存储目录，由环境变量FAQ_SNAPSHOT_DIR指定，默认为faq_snapshot
+pub static SNAPSHOT_DIR: Lazy<PathBuf> = Lazy::new(|| {
+    PathBuf::from(env::var("FAQ_SNAPSHOT_DIR").unwrap_or_else(|_| "faq_snapshot".to_string()))
+});
+
+/// 已生成快照的版本信息，读取快照文件时忽略其它字段
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SnapshotMeta {
+    pub version: i64,
+    #[serde(rename = "contentHash")]
+    pub content_hash: String,
+}
+
+/// 商品编号只允许字母、数字、-和_，避免拼接文件路径时跳出存储目录
+pub fn is_valid_product_code(product_code: &str) -> bool {
+    !product_code.is_empty()
+        && product_code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// 商品快照文件的路径，商品编号不合法时返回None
+pub fn snapshot_file(dir: &Path, product_code: &str) -> Option<PathBuf> {
+    if !is_valid_product_code(product_code) {
+        return None;
+    }
+    Some(dir.join(format!("{}.json", product_code)))
+}
+
+/// 快照版本信息文件的路径，商品编号中不能有.，不会与其它商品的快照文件重名
+pub fn meta_file(file: &Path) -> PathBuf {
+    file.with_extension("meta.json")
+}
+
+// 每个商品一把锁，同一商品的快照依次生成，避免并发生成时版本号重复或互相覆盖
+static BUILD_LOCKS: Lazy<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 获取商品快照的生成锁，持有期间其它任务不能生成同一商品的快照
+pub async fn lock(product_code: &str) -> OwnedMutexGuard<()> {
+    let lock = BUILD_LOCKS.lock().unwrap()
+        .entry(product_code.to_string())
+        .or_default()
+        .clone();
+    lock.lock_owned().await
+}
+
+/// 内容的SHA-256哈希值（十六进制）
+pub fn content_hash(content: &[u8]) -> String {
+    hex::encode(Sha256::digest(content))
+}
+
+/// 快照内容对应的ETag
+pub fn etag(content_hash: &str) -> String {
+    format!("\"{}\"", content_hash)
+}
+
+/// 请求头If-None-Match是否与ETag匹配（匹配时客户端的快照未变化），支持多个ETag、弱ETag和*
+pub fn if_none_match(header_value: &str, etag: &str) -> bool {
+    header_value.split(',')
+        .map(|value| value.trim())
+        .any(|value| value == "*" || value.strip_prefix("W/").unwrap_or(value) == etag)
+}
+
+/// 读取快照文件，文件不存在时返回None
+pub async fn read(file: &Path) -> std::io::Result<Option<Vec<u8>>> {
+    match tokio::fs::read(file).await {
+        Ok(content) => Ok(Some(content)),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// 读取已生成快照的版本信息，没有版本信息文件时从快照文件中解析，快照不存在或无法解析时返回None
+pub async fn read_meta(file: &Path) -> std::io::Result<Option<SnapshotMeta>> {
+    let meta_file = meta_file(file);
+    let (path, content) = match read(&meta_file).await? {
+        Some(content) => (meta_file.as_path(), content),
+        None => match read(file).await? {
+            Some(content) => (file, content),
+            None => return Ok(None),
+        },
+    };
+    let meta = serde_json::from_slice::<SnapshotMeta>(&content)
+        .map_err(|error| tracing::warn!("FAQ快照无法解析，将重新生成: {:?} {:?}", path, error))
+        .ok();
+    Ok(meta)
+}
+
+/// 写入快照文件和版本信息文件，先写快照再写版本信息，ETag变化时快照内容已经是新的
+pub async fn write(file: &Path, content: &[u8], meta: &SnapshotMeta) -> std::io::Result<()> {
+    if let Some(dir) = file.parent() {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+    write_file(file, content).await?;
+    let meta = serde_json::to_vec(meta).map_err(std::io::Error::other)?;
+    write_file(&meta_file(file), &meta).await
+}
+
+// 临时文件名的序号，多个任务或进程同时写同一文件时不会共用临时文件
+static TEMP_FILE_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// 先写临时文件再重命名，读取方不会读到写了一半的文件
+async fn write_file(file: &Path, content: &[u8]) -> std::io::Result<()> {
+    let seq = TEMP_FILE_SEQ.fetch_add(1, Ordering::Relaxed);
+    let temp_file = file.with_extension(format!("{}.{}.tmp", std::process::id(), seq));
+    tokio::fs::write(&temp_file, content).await?;
+    tokio::fs::rename(&temp_file, file).await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_file() {
+        let dir = Path::new("faq_snapshot");
+        assert_eq!(snapshot_file(dir, "P-001_a"), Some(dir.join("P-001_a.json")));
+        assert_eq!(snapshot_file(dir, "../etc/passwd"), None);
+        assert_eq!(snapshot_file(dir, ""), None);
+    }
+
+    #[test]
+    fn test_if_none_match() {
+        let etag = etag(&content_hash(b"[]"));
+        assert!(if_none_match(&etag, &etag));
+        assert!(if_none_match(&format!("\"other\", W/{}", etag), &etag));
+        assert!(if_none_match("*", &etag));
+        assert!(!if_none_match("\"other\"", &etag));
+    }
+
+    #[tokio::test]
+    async fn test_write_and_read_meta() {
+        let dir = env::temp_dir().join(format!("faq_snapshot_test_{}", std::process::id()));
+        let file = snapshot_file(&dir, "P001").unwrap();
+        assert_eq!(read_meta(&file).await.unwrap(), None);
+        //没有版本信息文件时从快照中解析
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        tokio::fs::write(&file, br#"{"version":1,"contentHash":"old","questions":[]}"#).await.unwrap();
+        assert_eq!(read_meta(&file).await.unwrap(), Some(SnapshotMeta { version: 1, content_hash: "old".to_string() }));
+        let meta = SnapshotMeta { version: 2, content_hash: "abc".to_string() };
+        write(&file, br#"{"version":2,"contentHash":"abc","questions":[]}"#, &meta).await.unwrap();
+        assert_eq!(read_meta(&file).await.unwrap(), Some(meta));
+        assert!(meta_file(&file).ends_with("P001.meta.json"));
+        //版本信息文件存在时不读取快照
+        tokio::fs::write(&file, b"not json").await.unwrap();
+        assert_eq!(read_meta(&file).await.unwrap().map(|meta| meta.version), Some(2));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,111 @@
+//! 搜索结果的高亮摘要
+//! 关键词按空白拆分；含中文且长于2个字的关键词额外拆成2字词组，与MySQL ngram分词（ngram_token_size=2）保持一致
+//! 内容先做HTML转义，再用<em>包裹命中的关键词
+
+const ELLIPSIS: &str = "...";
+
+/// 拆分搜索关键词，按长度从长到短排序，匹配时优先命中较长的关键词
+pub fn split_keywords(keyword:&str)->Vec<Vec<char>>{
+    let mut keywords: Vec<Vec<char>> = vec![];
+    for term in keyword.split_whitespace(){
+        let chars: Vec<char> = term.chars().map(|c|c.to_ascii_lowercase()).collect();
+        if !chars.iter().all(|c|c.is_ascii()) && chars.len() > 2 {
+            for gram in chars.windows(2){
+                keywords.push(gram.to_vec());
+            }
+        }
+        keywords.push(chars);
+    }
+    keywords.sort_by(|a, b| b.len().cmp(&a.len()));
+    keywords.dedup();
+    keywords
+}
+
+/// 生成最多max_chars个字符的摘要，摘要窗口以第一个命中的关键词为中心
+pub fn highlight(content:&str, keywords:&[Vec<char>], max_chars:usize)->String{
+    let chars: Vec<char> = content.chars().collect();
+    let lower: Vec<char> = chars.iter().map(|c|c.to_ascii_lowercase()).collect();
+    let first_match = (0..lower.len()).find(|&i| match_at(&lower, i, keywords).is_some());
+    let start = match first_match {
+        Some(position) if chars.len() > max_chars => position.saturating_sub(max_chars/4).min(chars.len()-max_chars),
+        _ => 0,
+    };
+    let end = (start+max_chars).min(chars.len());
+
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push_str(ELLIPSIS);
+    }
+    let mut i = start;
+    while i < end {
+        match match_at(&lower, i, keywords) {
+            Some(len) if i+len <= end => {
+                snippet.push_str("<em>");
+                chars[i..i+len].iter().for_each(|c|push_escaped(&mut snippet, *c));
+                snippet.push_str("</em>");
+                i += len;
+            },
+            _ => {
+                push_escaped(&mut snippet, chars[i]);
+                i += 1;
+            }
+        }
+    }
+    if end < chars.len() {
+        snippet.push_str(ELLIPSIS);
+    }
+    snippet
+}
+
+/// 返回在position处命中的最长关键词的长度
+fn match_at(lower:&[char], position:usize, keywords:&[Vec<char>])->Option<usize>{
+    keywords.iter()
+        .filter(|keyword|!keyword.is_empty())
+        .find(|keyword|lower[position..].starts_with(keyword))
+        .map(|keyword|keyword.len())
+}
+
+fn push_escaped(snippet:&mut String, c:char){
+    match c {
+        '<' => snippet.push_str("&lt;"),
+        '>' => snippet.push_str("&gt;"),
+        '&' => snippet.push_str("&amp;"),
+        '"' => snippet.push_str("&quot;"),
+        '\'' => snippet.push_str("&#39;"),
+        _ => snippet.push(c),
+    }
+}
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_highlight_ascii_ignore_case(){
+        let keywords = split_keywords("Cotton");
+        assert_eq!(highlight("100% cotton shirt", &keywords, 100), "100% <em>cotton</em> shirt");
+    }
+
+    #[test]
+    fn test_highlight_chinese_bigram(){
+        //整个关键词没有出现时，按2字词组高亮
+        let keywords = split_keywords("会缩水吗");
+        assert_eq!(highlight("这件衣服洗后缩水", &keywords, 100), "这件衣服洗后<em>缩水</em>");
+        //整个关键词出现时优先高亮整个关键词
+        assert_eq!(highlight("请问会缩水吗", &keywords, 100), "请问<em>会缩水吗</em>");
+    }
+
+    #[test]
+    fn test_highlight_window_and_escape(){
+        let keywords = split_keywords("size");
+        let content = format!("{}<b>size</b>{}", "a".repeat(50), "b".repeat(50));
+        let snippet = highlight(&content, &keywords, 20);
+        assert_eq!(snippet, "...aa&lt;b&gt;<em>size</em>&lt;/b&gt;bbbbbbb...");
+    }
+
+    #[test]
+    fn test_highlight_no_match(){
+        let keywords = split_keywords("warranty");
+        assert_eq!(highlight("abcdef", &keywords, 3), "abc...");
+    }
+}
@@ -8,7 +8,7 @@ use serde_json::Value;
 use tokio::time::Duration;
 use std::env; 
 use std::sync::Arc;
-use crate::utils::cache::{CacheType,Expiration,CACHE};
+use crate::utils::cache::LOAD_BALANCE_CACHE;
 use crate::services_dependence::get_services_dependence_list;
 
 const REENABLE_DELAY_MS : u64 = 5000;//对于不可用的实例，超过这个时间，重置失败次数
@@ -190,10 +190,8 @@ pub async fn fetch_load_balance_from_nacos(){
             // .expect("Failed to start Nacos manager");
             match load_balance{
                 Ok(load_balance)=>{
-                    let key=format!("load_balance_{}",&service_name);
                     let arc_lb = Arc::new(load_balance);
-                    let lb_with_cache_type = CacheType::LoadBalance(arc_lb.clone());
-                    CACHE.insert(key, (Expiration::AfterLongTime,lb_with_cache_type));
+                    LOAD_BALANCE_CACHE.insert(service_name.clone(), arc_lb.clone());
                     tokio::spawn(
                 async move{
                     arc_lb.clone().reenable_instances().await;
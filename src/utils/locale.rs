@@ -0,0 +1,130 @@
+//! 问答内容的语言：rc_qa_question/rc_qa_answer中保存的是默认语言的内容，其它语言的内容保存在翻译表中
+//! 前台依据Accept-Language选择首选语言，没有对应翻译时按配置的回退链依次查找，最终使用默认语言的内容
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::env;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Locale {
+    #[serde(rename = "zh-CN")]
+    ZhCn,
+    #[serde(rename = "zh-HK")]
+    ZhHk,
+    #[serde(rename = "en")]
+    En,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 3] = [Locale::ZhCn, Locale::ZhHk, Locale::En];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::ZhCn => "zh-CN",
+            Locale::ZhHk => "zh-HK",
+            Locale::En => "en",
+        }
+    }
+
+    /// 识别语言标签（不区分大小写），繁体中文地区归为zh-HK，其它中文归为zh-CN
+    pub fn parse(tag: &str) -> Option<Self> {
+        let tag = tag.trim().to_ascii_lowercase();
+        if tag == "en" || tag.starts_with("en-") {
+            return Some(Locale::En);
+        }
+        if tag == "zh" || tag.starts_with("zh-") {
+            if ["zh-hk", "zh-tw", "zh-mo"].contains(&tag.as_str()) || tag.starts_with("zh-hant") {
+                return Some(Locale::ZhHk);
+            }
+            return Some(Locale::ZhCn);
+        }
+        None
+    }
+
+    /// 依据Accept-Language选择首选语言：按q值从高到低取第一个支持的语言，都不支持时使用默认语言
+    pub fn from_accept_language(accept_language: Option<&str>) -> Self {
+        let mut candidates: Vec<(f32, Locale)> = accept_language.unwrap_or("")
+            .split(',')
+            .filter_map(|item| {
+                let mut parts = item.split(';');
+                let locale = Locale::parse(parts.next()?)?;
+                let quality = parts
+                    .filter_map(|part| part.trim().strip_prefix("q="))
+                    .find_map(|value| value.trim().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                Some((quality, locale))
+            })
+            .filter(|(quality, _)| *quality > 0.0)
+            .collect();
+        //稳定排序，q值相同时保持出现顺序
+        candidates.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        candidates.first().map(|(_, locale)| *locale).unwrap_or(*DEFAULT_LOCALE)
+    }
+
+    /// 查找内容时依次尝试的语言：首选语言、回退链，到默认语言为止
+    pub fn chain(&self) -> Vec<Locale> {
+        build_chain(*self, &FALLBACK_CHAIN, *DEFAULT_LOCALE)
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == *DEFAULT_LOCALE
+    }
+}
+
+/// 问题、回答原始内容的语言，由环境变量QA_DEFAULT_LOCALE指定，默认为zh-CN
+pub static DEFAULT_LOCALE: Lazy<Locale> = Lazy::new(|| {
+    env::var("QA_DEFAULT_LOCALE").ok()
+        .and_then(|value| Locale::parse(&value))
+        .unwrap_or(Locale::ZhCn)
+});
+
+/// 首选语言没有翻译时的回退链，由环境变量QA_LOCALE_FALLBACK_CHAIN指定（逗号分隔），默认直接回退到默认语言
+static FALLBACK_CHAIN: Lazy<Vec<Locale>> = Lazy::new(|| {
+    env::var("QA_LOCALE_FALLBACK_CHAIN").ok()
+        .map(|value| value.split(',').filter_map(Locale::parse).collect())
+        .unwrap_or_default()
+});
+
+fn build_chain(preferred: Locale, fallback_chain: &[Locale], default_locale: Locale) -> Vec<Locale> {
+    let mut chain = vec![];
+    for locale in std::iter::once(&preferred).chain(fallback_chain).chain(std::iter::once(&default_locale)) {
+        if !chain.contains(locale) {
+            chain.push(*locale);
+        }
+        //默认语言的内容总是存在，之后的语言不会再用到
+        if *locale == default_locale {
+            break;
+        }
+    }
+    chain
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Locale::parse("zh-CN"), Some(Locale::ZhCn));
+        assert_eq!(Locale::parse("zh"), Some(Locale::ZhCn));
+        assert_eq!(Locale::parse("zh-TW"), Some(Locale::ZhHk));
+        assert_eq!(Locale::parse("zh-Hant-HK"), Some(Locale::ZhHk));
+        assert_eq!(Locale::parse("EN-us"), Some(Locale::En));
+        assert_eq!(Locale::parse("fr"), None);
+    }
+
+    #[test]
+    fn test_from_accept_language() {
+        assert_eq!(Locale::from_accept_language(Some("fr-FR,en;q=0.8,zh-HK;q=0.9")), Locale::ZhHk);
+        assert_eq!(Locale::from_accept_language(Some("en-US,zh-CN")), Locale::En);
+        assert_eq!(Locale::from_accept_language(Some("en;q=0,zh-HK;q=0.5")), Locale::ZhHk);
+        assert_eq!(Locale::from_accept_language(Some("fr")), *DEFAULT_LOCALE);
+        assert_eq!(Locale::from_accept_language(None), *DEFAULT_LOCALE);
+    }
+
+    #[test]
+    fn test_build_chain() {
+        assert_eq!(build_chain(Locale::En, &[], Locale::ZhCn), vec![Locale::En, Locale::ZhCn]);
+        assert_eq!(build_chain(Locale::En, &[Locale::ZhHk, Locale::En], Locale::ZhCn), vec![Locale::En, Locale::ZhHk, Locale::ZhCn]);
+        assert_eq!(build_chain(Locale::ZhCn, &[Locale::ZhHk], Locale::ZhCn), vec![Locale::ZhCn]);
+    }
+}
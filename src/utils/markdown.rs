@@ -0,0 +1,48 @@
+//! 回答内容的Markdown渲染：写入时渲染为HTML并清理，只保留基本的格式（段落、列表、粗体、斜体、链接、代码）
+//! 脚本、事件属性以及javascript:等不安全的链接都会被去掉
+use ammonia::Builder;
+use once_cell::sync::Lazy;
+use pulldown_cmark::{html, Options, Parser};
+use std::collections::HashSet;
+
+static SANITIZER: Lazy<Builder<'static>> = Lazy::new(|| {
+    let mut builder = Builder::empty();
+    builder
+        .add_tags(&["p", "br", "strong", "em", "del", "ul", "ol", "li", "a", "code", "pre", "blockquote"])
+        .add_tag_attributes("a", &["href", "title"])
+        .add_tag_attributes("ol", &["start"])
+        .url_schemes(HashSet::from(["http", "https", "mailto"]))
+        .link_rel(Some("nofollow noopener noreferrer"));
+    builder
+});
+
+/// 将Markdown渲染为清理后的HTML
+pub fn render(source: &str) -> String {
+    let parser = Parser::new_ext(source, Options::ENABLE_STRIKETHROUGH);
+    let mut unsafe_html = String::new();
+    html::push_html(&mut unsafe_html, parser);
+    SANITIZER.clean(&unsafe_html).to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_render_basic_format() {
+        let html = render("**不会**缩水\n\n- 冷水洗\n- 平铺晾干\n\n详见[洗涤说明](https://example.com/care)");
+        assert!(html.contains("<strong>不会</strong>缩水"));
+        assert!(html.contains("<ul>\n<li>冷水洗</li>\n<li>平铺晾干</li>\n</ul>"));
+        assert!(html.contains("<a href=\"https://example.com/care\" rel=\"nofollow noopener noreferrer\">洗涤说明</a>"));
+    }
+
+    #[test]
+    fn test_render_strips_unsafe_content() {
+        let html = render("<script>alert(1)</script>\n\n[点我](javascript:alert(1))\n\n<img src=x onerror=alert(1)>");
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("href"));
+        assert!(!html.contains("onerror"));
+        //不安全的链接只去掉href，保留链接文字
+        assert!(html.contains("点我</a>"));
+    }
+}
@@ -0,0 +1,204 @@
+//! 订阅通知的发送方式：由环境变量QA_NOTIFIER选择，webhook为向QA_NOTIFY_WEBHOOK_URL发送POST请求，
+//! file为追加写入QA_NOTIFY_FILE指定的文件（每行一条JSON，未指定文件时只输出日志），默认为file，方便本地测试
+use async_trait::async_trait;
+use hyper::client::HttpConnector;
+use hyper::header::CONTENT_TYPE;
+use hyper::{Body, Client, Method, Request};
+use hyper_tls::HttpsConnector;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+/// 发送给订阅用户的一条通知
+#[derive(Serialize, Debug, Clone)]
+pub struct NotificationMessage {
+    #[serde(rename = "notificationId")]
+    pub notification_id: i64,
+    #[serde(rename = "subscriberId")]
+    pub subscriber_id: String,
+    #[serde(rename = "questionCode")]
+    pub question_code: String,
+    #[serde(rename = "eventType")]
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    #[serde(rename = "createTime")]
+    pub create_time: String,
+    /// 取消订阅的令牌，未配置签名密钥时为空
+    #[serde(rename = "unsubscribeToken", skip_serializing_if = "Option::is_none")]
+    pub unsubscribe_token: Option<String>,
+}
+
+/// 通知的发送方式，返回Err时该通知会按退避时间重试
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    fn name(&self) -> &'static str;
+    async fn send(&self, message: &NotificationMessage) -> Result<(), String>;
+}
+
+/// 以JSON格式向webhook地址发送POST请求，返回2xx状态码视为发送成功
+/// 支持http和https地址，所有请求共用一个客户端以复用连接
+pub struct WebhookNotifier {
+    url: hyper::Uri,
+    timeout: Duration,
+    client: Client<HttpsConnector<HttpConnector>>,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: &str, timeout: Duration) -> Result<Self, String> {
+        let url = url.parse::<hyper::Uri>().map_err(|error| format!("webhook地址不合法: {}", error))?;
+        match url.scheme_str() {
+            Some("http") | Some("https") => {},
+            _ => return Err(format!("webhook地址不合法: {}", url)),
+        }
+        let client = Client::builder().build::<_, Body>(HttpsConnector::new());
+        Ok(WebhookNotifier { url, timeout, client })
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    async fn send(&self, message: &NotificationMessage) -> Result<(), String> {
+        let body = serde_json::to_string(message).map_err(|error| error.to_string())?;
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(self.url.clone())
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .map_err(|error| format!("Failed to create request: {}", error))?;
+        let response = tokio::time::timeout(self.timeout, self.client.request(request)).await
+            .map_err(|_| "webhook请求超时".to_string())?
+            .map_err(|error| format!("webhook请求失败: {}", error))?;
+        if !response.status().is_success() {
+            return Err(format!("webhook返回状态码{}", response.status()));
+        }
+        Ok(())
+    }
+}
+
+/// 将通知逐行追加写入文件，未指定文件时只输出日志
+pub struct FileNotifier {
+    path: Option<String>,
+}
+
+impl FileNotifier {
+    pub fn new(path: Option<String>) -> Self {
+        FileNotifier { path }
+    }
+}
+
+#[async_trait]
+impl Notifier for FileNotifier {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    async fn send(&self, message: &NotificationMessage) -> Result<(), String> {
+        let line = serde_json::to_string(message).map_err(|error| error.to_string())?;
+        let Some(path) = &self.path else {
+            tracing::info!("订阅通知: {}", line);
+            return Ok(());
+        };
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await
+            .map_err(|error| format!("打开通知文件失败: {}", error))?;
+        file.write_all(format!("{}\n", line).as_bytes()).await
+            .map_err(|error| format!("写入通知文件失败: {}", error))
+    }
+}
+
+/// 依据环境变量创建通知的发送方式，webhook地址未配置或不合法时退回到file
+static NOTIFIER: Lazy<Arc<dyn Notifier>> = Lazy::new(|| {
+    let file_notifier = || Arc::new(FileNotifier::new(env::var("QA_NOTIFY_FILE").ok())) as Arc<dyn Notifier>;
+    match env::var("QA_NOTIFIER").unwrap_or_default().as_str() {
+        "webhook" => {
+            let timeout_seconds = env::var("QA_NOTIFY_WEBHOOK_TIMEOUT_SECONDS").ok()
+                .and_then(|value| value.parse::<u64>().ok())
+                .unwrap_or(5);
+            let notifier = env::var("QA_NOTIFY_WEBHOOK_URL")
+                .map_err(|_| "未配置QA_NOTIFY_WEBHOOK_URL".to_string())
+                .and_then(|url| WebhookNotifier::new(&url, Duration::from_secs(timeout_seconds)));
+            match notifier {
+                Ok(notifier) => Arc::new(notifier),
+                Err(error) => {
+                    tracing::warn!("{}，订阅通知改为写入文件", error);
+                    file_notifier()
+                }
+            }
+        },
+        _ => file_notifier(),
+    }
+});
+
+/// 当前使用的通知发送方式
+pub fn current() -> Arc<dyn Notifier> {
+    NOTIFIER.clone()
+}
+
+/// 每条通知最多尝试发送的次数，由环境变量QA_NOTIFY_MAX_ATTEMPTS指定，默认5次，超过后不再重试
+pub static MAX_ATTEMPTS: Lazy<i32> = Lazy::new(|| {
+    env::var("QA_NOTIFY_MAX_ATTEMPTS").ok()
+        .and_then(|value| value.parse::<i32>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(5)
+});
+
+/// 第attempt次发送失败后到下次发送的等待秒数：从60秒开始每次翻倍，最长1小时
+pub fn retry_delay_seconds(attempt: i32) -> i64 {
+    const BASE_DELAY_SECONDS: i64 = 60;
+    const MAX_DELAY_SECONDS: i64 = 3600;
+    let exponent = (attempt.max(1) - 1).min(16) as u32;
+    (BASE_DELAY_SECONDS * 2i64.pow(exponent)).min(MAX_DELAY_SECONDS)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_seconds() {
+        assert_eq!(retry_delay_seconds(1), 60);
+        assert_eq!(retry_delay_seconds(2), 120);
+        assert_eq!(retry_delay_seconds(6), 1920);
+        assert_eq!(retry_delay_seconds(7), 3600);
+        assert_eq!(retry_delay_seconds(100), 3600);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_notifier_url() {
+        assert!(WebhookNotifier::new("https://example.com/notify", Duration::from_secs(5)).is_ok());
+        assert!(WebhookNotifier::new("http://127.0.0.1:8080/notify", Duration::from_secs(5)).is_ok());
+        assert!(WebhookNotifier::new("ftp://example.com/notify", Duration::from_secs(5)).is_err());
+        assert!(WebhookNotifier::new("/notify", Duration::from_secs(5)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_file_notifier() {
+        let path = env::temp_dir().join(format!("qa_notify_test_{}.log", std::process::id()));
+        let notifier = FileNotifier::new(Some(path.to_string_lossy().to_string()));
+        let message = NotificationMessage {
+            notification_id: 1,
+            subscriber_id: "user_1".to_string(),
+            question_code: "q1".to_string(),
+            event_type: "answerAdded".to_string(),
+            payload: serde_json::json!({"answerCount": 1}),
+            create_time: "2024-11-15 00:00:00".to_string(),
+            unsubscribe_token: None,
+        };
+        notifier.send(&message).await.unwrap();
+        notifier.send(&message).await.unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content.lines().count(), 2);
+        assert!(content.starts_with("{\"notificationId\":1,\"subscriberId\":\"user_1\""));
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,53 @@
+use moka::sync::Cache;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+/// 固定窗口限流器：每个key在一个时间窗口内最多允许max_requests次请求
+/// 窗口从该key第一次请求开始计时，窗口结束后计数随缓存过期自动清零
+pub struct RateLimiter{
+    max_requests:u32,
+    counters:Cache<String, Arc<AtomicU32>>,
+}
+
+impl RateLimiter{
+    pub fn new(max_requests:u32, window:Duration)->Self{
+        RateLimiter{
+            max_requests,
+            counters:Cache::builder()
+                .max_capacity(100_000)
+                .time_to_live(window)
+                .build(),
+        }
+    }
+
+    /// 记录一次请求，超过窗口内的上限时返回false
+    pub fn try_acquire(&self, key:&str)->bool{
+        let counter = self.counters.get_with(key.to_string(), || Arc::new(AtomicU32::new(0)));
+        counter.fetch_add(1, Ordering::SeqCst) < self.max_requests
+    }
+}
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_try_acquire(){
+        let rate_limiter = RateLimiter::new(2, Duration::from_secs(60));
+        assert_eq!(rate_limiter.try_acquire("user_1"), true);
+        assert_eq!(rate_limiter.try_acquire("user_1"), true);
+        assert_eq!(rate_limiter.try_acquire("user_1"), false);
+        //不同的key分别计数
+        assert_eq!(rate_limiter.try_acquire("user_2"), true);
+    }
+
+    #[test]
+    fn test_window_expired(){
+        let rate_limiter = RateLimiter::new(1, Duration::from_millis(100));
+        assert_eq!(rate_limiter.try_acquire("user_1"), true);
+        assert_eq!(rate_limiter.try_acquire("user_1"), false);
+        std::thread::sleep(Duration::from_millis(200));
+        assert_eq!(rate_limiter.try_acquire("user_1"), true);
+    }
+}
@@ -1,6 +1,5 @@
 use hyper::header::CONTENT_TYPE;
-use crate::utils::cache::{CACHE,CacheType};
-use crate::utils::cache::key::get_service_instance_key;
+use crate::utils::cache::LOAD_BALANCE_CACHE;
 use tracing::{info, instrument, error};
 use uuid::Uuid;
 use hyper::{Body, Client, Method, Request, StatusCode};
@@ -12,34 +11,26 @@ enum ResponseResult{
 }
 #[instrument(name="request",fields(request_id = %Uuid::new_v4()))]
 pub async fn request(service_name:&String,url:&String,method:&Method,body:&String) -> Result<String,String> {
-    let key: String = get_service_instance_key(service_name.to_string());
-    let (_,cache_type) = CACHE.get(&key)
+    let service = LOAD_BALANCE_CACHE.get(service_name)
         .ok_or("Failed to get SERVICE_URL".to_string())?;
-    match cache_type {       
-        CacheType::LoadBalance(service) => {  
-            const MAX_RETRIES: usize = 3;
-            for attempt in 1..=MAX_RETRIES {
-                let response_result = req(service.next().await,method,body, &attempt, &MAX_RETRIES).await;  
-                match response_result{
-                    ResponseResult::Success(body)=>{
-                        return Ok(body);
-                    },
-                    ResponseResult::Retry() => {
-                        info!("再次重试请求，目前次数:{}",&attempt);
-                        continue;
-                    },
-                    ResponseResult::Error(e) => {
-                        error!("请求失败:{}",e);
-                        return Err(e);
-                    }
-                }
+    const MAX_RETRIES: usize = 3;
+    for attempt in 1..=MAX_RETRIES {
+        let response_result = req(service.next().await,method,body, &attempt, &MAX_RETRIES).await;  
+        match response_result{
+            ResponseResult::Success(body)=>{
+                return Ok(body);
+            },
+            ResponseResult::Retry() => {
+                info!("再次重试请求，目前次数:{}",&attempt);
+                continue;
+            },
+            ResponseResult::Error(e) => {
+                error!("请求失败:{}",e);
+                return Err(e);
             }
-            return Err(format!("已达到重试上限"));
-        },
-        _ => {
-            return Err("Failed to get SERVICE_URL".to_string());
-        }        
-    };
+        }
+    }
+    Err("已达到重试上限".to_string())
 }
 
 async fn req(instance:Option<Instance>,method:&Method,body:&String, attempt: &usize,max_retries:&usize) -> ResponseResult{
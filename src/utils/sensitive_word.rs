@@ -0,0 +1,257 @@
+//! 敏感词过滤：词典从文件加载，文件变更后自动重新加载，用Aho-Corasick自动机一次扫描匹配全部敏感词
+//! 词典每行一个规则，格式为`敏感词,处理方式`，处理方式为reject（拒绝，默认）、mask（替换为*）、flag（转人工审核），#开头的行为注释
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::env;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use crate::utils::error::BusinessError;
+
+/// 命中敏感词后的处理方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SensitiveAction {
+    /// 拒绝提交
+    Reject,
+    /// 将敏感词替换为*后保存
+    Mask,
+    /// 允许保存，但需要重新人工审核
+    Flag,
+}
+
+impl FromStr for SensitiveAction {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "reject" => Ok(SensitiveAction::Reject),
+            "mask" => Ok(SensitiveAction::Mask),
+            "flag" => Ok(SensitiveAction::Flag),
+            _ => Err(()),
+        }
+    }
+}
+
+/// 一段文本的敏感词检查结果，每类敏感词已去重并保持出现顺序
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SensitiveCheck {
+    pub rejected: Vec<String>,
+    pub masked: Vec<String>,
+    pub flagged: Vec<String>,
+}
+
+pub struct SensitiveWordFilter {
+    /// 词典为空时为None
+    matcher: Option<AhoCorasick>,
+    /// 与自动机中的模式一一对应
+    rules: Vec<(String, SensitiveAction)>,
+}
+
+impl SensitiveWordFilter {
+    pub fn empty() -> Self {
+        SensitiveWordFilter { matcher: None, rules: vec![] }
+    }
+
+    /// 解析词典内容，无法识别的处理方式按reject处理，重复的敏感词以最后一条规则为准
+    pub fn parse(content: &str) -> Self {
+        let mut rules: Vec<(String, SensitiveAction)> = vec![];
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (word, action) = match line.rsplit_once(',') {
+                Some((word, action)) => (word.trim(), action.parse::<SensitiveAction>().unwrap_or_else(|_| {
+                    tracing::warn!("敏感词规则的处理方式无法识别，按reject处理: {}", line);
+                    SensitiveAction::Reject
+                })),
+                None => (line, SensitiveAction::Reject),
+            };
+            if word.is_empty() {
+                continue;
+            }
+            match rules.iter_mut().find(|(existing, _)| existing == word) {
+                Some(rule) => rule.1 = action,
+                None => rules.push((word.to_string(), action)),
+            }
+        }
+        if rules.is_empty() {
+            return Self::empty();
+        }
+        //最长匹配，避免短词把长词截断（如同时配置了“微信”和“微信号”）
+        let matcher = AhoCorasickBuilder::new()
+            .ascii_case_insensitive(true)
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(rules.iter().map(|(word, _)| word))
+            .map_err(|error| tracing::error!("构建敏感词自动机失败: {:?}", error))
+            .ok();
+        SensitiveWordFilter { matcher, rules }
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn check(&self, text: &str) -> SensitiveCheck {
+        let mut result = SensitiveCheck::default();
+        let Some(matcher) = &self.matcher else {
+            return result;
+        };
+        for found in matcher.find_iter(text) {
+            let (word, action) = &self.rules[found.pattern().as_usize()];
+            let words = match action {
+                SensitiveAction::Reject => &mut result.rejected,
+                SensitiveAction::Mask => &mut result.masked,
+                SensitiveAction::Flag => &mut result.flagged,
+            };
+            if !words.contains(word) {
+                words.push(word.clone());
+            }
+        }
+        result
+    }
+
+    /// 将处理方式为mask的敏感词按字符替换为*，其它文本保持不变
+    pub fn mask(&self, text: &str) -> String {
+        let Some(matcher) = &self.matcher else {
+            return text.to_string();
+        };
+        let mut masked = String::with_capacity(text.len());
+        let mut last = 0;
+        for found in matcher.find_iter(text) {
+            if self.rules[found.pattern().as_usize()].1 != SensitiveAction::Mask {
+                continue;
+            }
+            masked.push_str(&text[last..found.start()]);
+            masked.push_str(&"*".repeat(text[found.start()..found.end()].chars().count()));
+            last = found.end();
+        }
+        masked.push_str(&text[last..]);
+        masked
+    }
+
+    /// 文本包含reject类敏感词时返回参数校验异常，words参数为命中的敏感词（逗号分隔）
+    pub fn reject(&self, field: &str, text: &str) -> Result<(), BusinessError> {
+        let rejected = self.check(text).rejected;
+        if rejected.is_empty() {
+            return Ok(());
+        }
+        let mut parameters = HashMap::new();
+        parameters.insert("field".to_string(), field.to_string());
+        parameters.insert("message".to_string(), format!("{}包含敏感词", field));
+        parameters.insert("words".to_string(), rejected.join(","));
+        Err(BusinessError::InvalidParameter((None, Some(vec![parameters]))))
+    }
+}
+
+/// 词典文件路径，由环境变量SENSITIVE_WORD_FILE指定，默认为sensitive_words.txt
+fn dictionary_path() -> String {
+    env::var("SENSITIVE_WORD_FILE").unwrap_or_else(|_| "sensitive_words.txt".to_string())
+}
+
+static SENSITIVE_WORD_FILTER: Lazy<RwLock<Arc<SensitiveWordFilter>>> = Lazy::new(|| {
+    let filter = match std::fs::read_to_string(dictionary_path()) {
+        Ok(content) => SensitiveWordFilter::parse(&content),
+        Err(error) => {
+            tracing::warn!("敏感词词典加载失败，不做敏感词过滤: {:?}", error);
+            SensitiveWordFilter::empty()
+        }
+    };
+    RwLock::new(Arc::new(filter))
+});
+
+/// 当前生效的敏感词过滤器，重新加载词典不影响正在使用旧词典的请求
+pub fn current() -> Arc<SensitiveWordFilter> {
+    SENSITIVE_WORD_FILTER.read().unwrap().clone()
+}
+
+/// 重新读取词典文件并替换当前的过滤器，返回规则数量
+pub fn reload() -> std::io::Result<usize> {
+    let content = std::fs::read_to_string(dictionary_path())?;
+    let filter = SensitiveWordFilter::parse(&content);
+    let count = filter.len();
+    *SENSITIVE_WORD_FILTER.write().unwrap() = Arc::new(filter);
+    Ok(count)
+}
+
+/// 启动后台任务，定期检查词典文件的修改时间，变更后重新加载
+/// 间隔由环境变量SENSITIVE_WORD_RELOAD_INTERVAL_SECONDS指定，默认30秒
+pub fn start_sensitive_word_reloader() {
+    let interval_seconds = env::var("SENSITIVE_WORD_RELOAD_INTERVAL_SECONDS").ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or(30);
+    let modified_time = |path: &str| -> Option<SystemTime> {
+        std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+    };
+    //先初始化过滤器，再记录词典当前的修改时间
+    let _ = current();
+    let mut last_modified = modified_time(&dictionary_path());
+    tokio::spawn(
+        async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(interval_seconds)).await;
+                let modified = modified_time(&dictionary_path());
+                if modified.is_none() || modified == last_modified {
+                    continue;
+                }
+                match reload() {
+                    Ok(count) => {
+                        last_modified = modified;
+                        tracing::info!("敏感词词典已重新加载，共{}条规则", count);
+                    },
+                    Err(error) => tracing::error!("重新加载敏感词词典失败: {:?}", error),
+                }
+            }
+        }
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn filter() -> SensitiveWordFilter {
+        SensitiveWordFilter::parse("# 注释\n假货\n微信,mask\n微信号,mask\nrefund,flag\n仿品,unknown\n\n")
+    }
+
+    #[test]
+    fn test_check() {
+        let filter = filter();
+        assert_eq!(filter.len(), 5);
+        assert!(!filter.is_empty());
+        assert!(SensitiveWordFilter::empty().is_empty());
+        assert_eq!(" Mask ".parse::<SensitiveAction>(), Ok(SensitiveAction::Mask));
+        let result = filter.check("这是假货吗？加微信号，REFUND可以吗，假货");
+        assert_eq!(result.rejected, vec!["假货".to_string()]);
+        assert_eq!(result.masked, vec!["微信号".to_string()]);
+        assert_eq!(result.flagged, vec!["refund".to_string()]);
+        //无法识别的处理方式按reject处理
+        assert_eq!(filter.check("是仿品").rejected, vec!["仿品".to_string()]);
+        assert_eq!(filter.check("尺码偏大吗"), SensitiveCheck::default());
+    }
+
+    #[test]
+    fn test_mask() {
+        let filter = filter();
+        assert_eq!(filter.mask("加微信号或微信，假货"), "加***或**，假货");
+        assert_eq!(SensitiveWordFilter::empty().mask("加微信"), "加微信");
+    }
+
+    #[test]
+    fn test_reject() {
+        let filter = filter();
+        assert!(filter.reject("questionContent", "加微信").is_ok());
+        match filter.reject("questionContent", "是假货还是仿品") {
+            Err(BusinessError::InvalidParameter((_, Some(parameters)))) => {
+                assert_eq!(parameters[0].get("field").unwrap(), "questionContent");
+                assert_eq!(parameters[0].get("words").unwrap(), "假货,仿品");
+            },
+            _ => panic!("错误类型不匹配"),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! 文本相似度，用于发现同一商品下重复的问题
+//! 先归一化（全角转半角、英文转小写、去掉标点和空白），再按2字词组计算Dice系数，结果在0到1之间
+use std::collections::HashSet;
+
+/// 归一化文本，只保留字母、数字和汉字
+pub fn normalize(text:&str)->Vec<char>{
+    text.chars()
+        .map(|c|match c {
+            //全角字符转半角
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            '\u{3000}' => ' ',
+            _ => c,
+        })
+        .filter(|c|c.is_alphanumeric())
+        .map(|c|c.to_ascii_lowercase())
+        .collect()
+}
+
+fn bigrams(chars:&[char])->HashSet<(char, char)>{
+    chars.windows(2).map(|pair|(pair[0], pair[1])).collect()
+}
+
+/// 两段文本归一化后的相似度，归一化后完全相同时为1
+pub fn similarity(a:&str, b:&str)->f64{
+    let a = normalize(a);
+    let b = normalize(b);
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    if a == b {
+        return 1.0;
+    }
+    let a = bigrams(&a);
+    let b = bigrams(&b);
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let common = a.intersection(&b).count();
+    2.0 * common as f64 / (a.len() + b.len()) as f64
+}
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_normalize(){
+        assert_eq!(normalize("Ｉｓ it 100% Cotton？"), "isit100cotton".chars().collect::<Vec<char>>());
+        assert_eq!(normalize("会缩水吗？ "), "会缩水吗".chars().collect::<Vec<char>>());
+    }
+
+    #[test]
+    fn test_similarity(){
+        //只有标点、空白和大小写不同视为完全相同
+        assert_eq!(similarity("这件衣服会缩水吗？", "这件衣服会缩水吗"), 1.0);
+        assert_eq!(similarity("Is it cotton?", "is it COTTON"), 1.0);
+        assert!(similarity("这件衣服洗后会缩水吗", "这件衣服会缩水吗") > 0.6);
+        assert!(similarity("这件衣服会缩水吗", "快递几天能到") < 0.2);
+        assert_eq!(similarity("？？", "会缩水吗"), 0.0);
+    }
+}
@@ -0,0 +1,117 @@
+//! 合并同一个key的并发加载：缓存未命中时，同时到达的请求只有第一个执行加载，其它请求等待并共享其结果
+//! 加载完成后即移除记录，之后的请求应当已能命中缓存；执行加载的请求被取消时，由等待中的请求接着加载
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+pub struct SingleFlight<K, T> {
+    calls: Mutex<HashMap<K, Arc<OnceCell<T>>>>,
+}
+
+impl<K: Hash + Eq + Clone, T: Clone> SingleFlight<K, T> {
+    pub fn new() -> Self {
+        SingleFlight { calls: Mutex::new(HashMap::new()) }
+    }
+
+    /// 执行key对应的加载，同一个key正在加载时等待其结果
+    pub async fn run<F, Fut>(&self, key: &K, load: F) -> T
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let cell = self.calls.lock().unwrap()
+            .entry(key.clone())
+            .or_insert_with(|| Arc::new(OnceCell::new()))
+            .clone();
+        let _guard = CallGuard { calls: &self.calls, key, cell: &cell };
+        cell.get_or_init(load).await.clone()
+    }
+
+    /// 正在加载的key的数量
+    pub fn len(&self) -> usize {
+        self.calls.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K: Hash + Eq + Clone, T: Clone> Default for SingleFlight<K, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 加载结束或被取消时移除记录，记录已被替换为新的加载时保留
+struct CallGuard<'a, K: Hash + Eq, T> {
+    calls: &'a Mutex<HashMap<K, Arc<OnceCell<T>>>>,
+    key: &'a K,
+    cell: &'a Arc<OnceCell<T>>,
+}
+
+impl<K: Hash + Eq, T> Drop for CallGuard<'_, K, T> {
+    fn drop(&mut self) {
+        let mut calls = self.calls.lock().unwrap();
+        if calls.get(self.key).map(|cell| Arc::ptr_eq(cell, self.cell)).unwrap_or(false) {
+            calls.remove(self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_concurrent_calls_share_one_load() {
+        let single_flight = Arc::new(SingleFlight::<&str, Result<usize, String>>::new());
+        let loads = Arc::new(AtomicUsize::new(0));
+        let handles: Vec<_> = (0..10).map(|_| {
+            let single_flight = single_flight.clone();
+            let loads = loads.clone();
+            tokio::spawn(async move {
+                single_flight.run(&"q1", || async {
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    Ok(loads.fetch_add(1, Ordering::SeqCst) + 1)
+                }).await
+            })
+        }).collect();
+        for handle in handles {
+            assert_eq!(handle.await.unwrap(), Ok(1));
+        }
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+        assert!(single_flight.is_empty());
+
+        //加载完成后再次调用会重新加载
+        let result = single_flight.run(&"q1", || async { Ok(loads.fetch_add(1, Ordering::SeqCst) + 1) }).await;
+        assert_eq!(result, Ok(2));
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_load_is_taken_over() {
+        let single_flight = Arc::new(SingleFlight::<&str, usize>::new());
+        let leader = {
+            let single_flight = single_flight.clone();
+            tokio::spawn(async move {
+                single_flight.run(&"q1", || async {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                    1
+                }).await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let follower = {
+            let single_flight = single_flight.clone();
+            tokio::spawn(async move { single_flight.run(&"q1", || async { 2 }).await })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        leader.abort();
+        assert_eq!(follower.await.unwrap(), 2);
+        assert!(single_flight.is_empty());
+    }
+}
@@ -0,0 +1,60 @@
+//! 取消订阅的签名令牌：通知中附带令牌，没有登录的用户凭通知中的令牌取消订阅
+//! 令牌为HMAC-SHA256(问题编码 + 换行 + 订阅用户id)的十六进制字符串，密钥由环境变量QA_UNSUBSCRIBE_SECRET指定
+//! 未配置密钥时不生成令牌，只能由登录用户取消自己的订阅
+use hmac::{Hmac, Mac};
+use once_cell::sync::Lazy;
+use sha2::Sha256;
+use std::env;
+
+type HmacSha256 = Hmac<Sha256>;
+
+static UNSUBSCRIBE_SECRET: Lazy<Option<String>> = Lazy::new(|| {
+    env::var("QA_UNSUBSCRIBE_SECRET").ok().filter(|secret| !secret.is_empty())
+});
+
+fn mac(secret: &str, question_code: &str, subscriber_id: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC可以使用任意长度的密钥");
+    mac.update(question_code.as_bytes());
+    mac.update(b"\n");
+    mac.update(subscriber_id.as_bytes());
+    mac
+}
+
+fn sign_with(secret: &str, question_code: &str, subscriber_id: &str) -> String {
+    hex::encode(mac(secret, question_code, subscriber_id).finalize().into_bytes())
+}
+
+fn verify_with(secret: &str, question_code: &str, subscriber_id: &str, token: &str) -> bool {
+    match hex::decode(token) {
+        Ok(token) => mac(secret, question_code, subscriber_id).verify_slice(&token).is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// 生成取消订阅的令牌，未配置密钥时返回None
+pub fn sign(question_code: &str, subscriber_id: &str) -> Option<String> {
+    UNSUBSCRIBE_SECRET.as_deref().map(|secret| sign_with(secret, question_code, subscriber_id))
+}
+
+/// 校验取消订阅的令牌，未配置密钥时总是校验失败
+pub fn verify(question_code: &str, subscriber_id: &str, token: &str) -> bool {
+    UNSUBSCRIBE_SECRET.as_deref().is_some_and(|secret| verify_with(secret, question_code, subscriber_id, token))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let token = sign_with("secret", "Q001", "user_1");
+        assert_eq!(token.len(), 64);
+        assert!(verify_with("secret", "Q001", "user_1", &token));
+        assert!(!verify_with("other", "Q001", "user_1", &token));
+        assert!(!verify_with("secret", "Q002", "user_1", &token));
+        assert!(!verify_with("secret", "Q001", "user_2", &token));
+        assert!(!verify_with("secret", "Q001", "user_1", "not hex"));
+        //问题编码和用户id之间有分隔符，拼接相同的两组参数令牌不同
+        assert_ne!(sign_with("secret", "Q00", "1user_1"), token);
+    }
+}
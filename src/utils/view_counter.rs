@@ -0,0 +1,86 @@
+//! 问题浏览量统计：前台查询问题时只在内存中按(问题编号,日期)累加，由后台任务定期取出后批量写入数据库
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// 一条待写入的浏览量：(问题编号,浏览日期,浏览次数)
+pub type QuestionView = (String, NaiveDate, i64);
+
+#[derive(Default)]
+pub struct ViewCounter {
+    counts: Mutex<HashMap<(String, NaiveDate), i64>>,
+}
+
+impl ViewCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录一次浏览
+    pub fn record(&self, question_code: &str, view_date: NaiveDate) {
+        let mut counts = self.counts.lock().unwrap();
+        *counts.entry((question_code.to_string(), view_date)).or_insert(0) += 1;
+    }
+
+    /// 取出并清空缓冲区中的浏览量
+    pub fn take(&self) -> Vec<QuestionView> {
+        let counts = std::mem::take(&mut *self.counts.lock().unwrap());
+        counts.into_iter()
+            .map(|((question_code, view_date), view_count)| (question_code, view_date, view_count))
+            .collect()
+    }
+
+    /// 写入数据库失败时将取出的浏览量放回缓冲区，与期间新增的浏览量合并，下次一起写入
+    pub fn restore(&self, views: Vec<QuestionView>) {
+        let mut counts = self.counts.lock().unwrap();
+        for (question_code, view_date, view_count) in views {
+            *counts.entry((question_code, view_date)).or_insert(0) += view_count;
+        }
+    }
+
+    /// 缓冲区中不同(问题编号,日期)的数量
+    pub fn len(&self) -> usize {
+        self.counts.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// 前台问题浏览量的缓冲区
+pub static QUESTION_VIEW_COUNTER: Lazy<ViewCounter> = Lazy::new(ViewCounter::new);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_record_and_take() {
+        let counter = ViewCounter::new();
+        let today = NaiveDate::from_ymd_opt(2024, 11, 14).unwrap();
+        let yesterday = NaiveDate::from_ymd_opt(2024, 11, 13).unwrap();
+        counter.record("q1", today);
+        counter.record("q1", today);
+        counter.record("q1", yesterday);
+        counter.record("q2", today);
+        assert_eq!(counter.len(), 3);
+
+        let mut views = counter.take();
+        views.sort();
+        assert_eq!(views, vec![
+            ("q1".to_string(), yesterday, 1),
+            ("q1".to_string(), today, 2),
+            ("q2".to_string(), today, 1),
+        ]);
+        assert!(counter.is_empty());
+
+        //放回的浏览量与新增的浏览量合并
+        counter.record("q2", today);
+        counter.restore(views);
+        let mut views = counter.take();
+        views.sort();
+        assert_eq!(views[2], ("q2".to_string(), today, 2));
+    }
+}
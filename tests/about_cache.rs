@@ -2,13 +2,14 @@ extern crate rust_axum_example;
 
 #[cfg(test)]
 mod test{
-    use rust_axum_example::utils::cache::CACHE;
+    use rust_axum_example::utils::cache::{TypedCache,Expiration};
     use tracing::{info, Level};    
     use tracing_subscriber;
     use std::sync::Once;
-    use rust_axum_example::utils::cache::{CacheType,Expiration};
+    use once_cell::sync::Lazy;
     use chrono::prelude::*;
     static INIT: Once = Once::new();
+    static NOW_CACHE: Lazy<TypedCache<String, String>> = Lazy::new(|| TypedCache::new("now", 10, Expiration::AfterShortTime));
     fn init_tracing(){
         INIT.call_once(||{
             tracing_subscriber::fmt().with_max_level(Level::INFO).init();
@@ -21,13 +22,13 @@ mod test{
             async move {
                 let now = Local::now();
                 let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-                CACHE.insert("now".to_string(), (Expiration::AfterShortTime,CacheType::Str(format_time.to_string())));
+                NOW_CACHE.insert("now".to_string(), format_time.to_string());
                 loop {                    
-                    info!("now::{:?}",CACHE.get("now").unwrap());
+                    info!("now::{:?}",NOW_CACHE.get(&"now".to_string()).unwrap());
                     tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
                     let now = Local::now();
                     let format_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
-                    CACHE.insert("now".to_string(), (Expiration::AfterShortTime,CacheType::Str(format_time.to_string())));
+                    NOW_CACHE.insert("now".to_string(), format_time.to_string());
                 }
             }
         );
@@ -35,4 +36,61 @@ mod test{
         handle.await?;
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_invalidate_if(){
+        use rust_axum_example::utils::cache::key::QuestionListKey;
+        use rust_axum_example::utils::locale::Locale;
+        let cache: TypedCache<QuestionListKey, i32> = TypedCache::new("test_question_list", 10, Expiration::AfterLongTime);
+        let list_key = |product_code: &str, sku_code: Option<&str>, current_pageno: i64| QuestionListKey{
+            product_code: product_code.to_string(),
+            sku_code: sku_code.map(|sku_code|sku_code.to_string()),
+            tag: None,
+            locale: Locale::ZhCn,
+            current_pageno,
+            page_size: 10,
+        };
+        let product_key = list_key("test_invalidate_if", None, 1);
+        let sku_key = list_key("test_invalidate_if", Some("sku_code"), 2);
+        let other_key = list_key("test_invalidate_if_other", None, 1);
+        for key in [&product_key, &sku_key, &other_key]{
+            cache.insert(key.clone(), 1);
+        }
+        cache.invalidate_if(|key|key.product_code == "test_invalidate_if");
+        assert!(cache.get(&product_key).is_none());
+        assert!(cache.get(&sku_key).is_none());
+        assert_eq!(cache.get(&other_key), Some(1));
+    }
+
+    #[test]
+    fn test_namespace_registry(){
+        use rust_axum_example::utils::cache::{namespace, namespaces, CacheNamespace, SKU_CACHE};
+        assert!(namespaces().iter().any(|namespace|namespace.name() == "question"));
+        SKU_CACHE.insert("test_namespace_registry".to_string(), None);
+        let sku = namespace("sku").unwrap();
+        assert_eq!(sku.default_expiration(), Expiration::AfterShortTime);
+        assert_eq!(sku.entry_count(), 1);
+        sku.invalidate_all();
+        assert!(SKU_CACHE.get(&"test_namespace_registry".to_string()).is_none());
+        assert!(namespace("unknown").is_none());
+    }
+
+    #[test]
+    fn test_entries_and_evict(){
+        use rust_axum_example::utils::cache::CacheNamespace;
+        let cache: TypedCache<String, i32> = TypedCache::new("test_entries", 10, Expiration::AfterLongTime);
+        cache.insert("question:1".to_string(), 1);
+        cache.insert("question:2".to_string(), 2);
+        cache.insert_with_expiration("sku:1".to_string(), 3, Expiration::Never);
+        let entries = cache.entries("question:");
+        assert_eq!(entries.iter().map(|entry|entry.key.as_str()).collect::<Vec<_>>(), vec!["question:1", "question:2"]);
+        assert!(entries[0].remaining_ttl.unwrap().as_secs() > 1400);
+        let entry = cache.entry("sku:1").unwrap();
+        assert_eq!(entry.remaining_ttl, None);
+        assert_eq!(entry.value, "3");
+        assert_eq!(cache.invalidate_prefix("question:"), 2);
+        assert!(cache.invalidate_key("sku:1"));
+        assert!(!cache.invalidate_key("sku:1"));
+        assert_eq!(cache.entry_count(), 0);
+    }
+}